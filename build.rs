@@ -2,17 +2,22 @@
 #![allow(non_camel_case_types)]
 #![allow(non_upper_case_globals)]
 
+use std::env;
+use std::path::PathBuf;
+
 fn main() {
+    // ecat_options.h (and the demo object dictionary) from esi.json
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let esi = SOES_rs_codegen::esi_path("src/soes-c/soes-esi/esi.json");
+    SOES_rs_codegen::generate(&esi, &out_dir)
+        .unwrap_or_else(|err| panic!("{}: {err}", esi.display()));
+
     // Compile C-code
     cc::Build::new()
-        .file("./src/soes-c/esc.c")
-        .file("./src/soes-c/esc_foe.c")
         .file("./src/soes-c/esc_eoe.c")
-        .file("./src/soes-c/esc_eep.c")
-        .file("./src/soes-c/esc_coe.c")
         .file("./src/soes-c/tinyprintf.c")
-        .file("./src/soes-c/objectlist.c")
         .include("./src/soes-c")
+        .include(&out_dir)
         .define("EC_LITTLE_ENDIAN", None)
         .flag_if_supported("-Wno-address-of-packed-member")
         .compile("soes");
@@ -22,11 +27,16 @@ fn main() {
         .header("./src/soes-c/esc.h")
         .clang_arg("-Isrc/soes-c") // include path for headers
         .clang_arg("-Isrc/soes-c/soes-esi")
+        .clang_arg(format!("-I{}", out_dir.display()))
         .clang_arg("--target=arm-none-eabi")
         .clang_arg("-DEC_LITTLE_ENDIAN") // force little endian for conditional fields
         .clang_arg("-DCC_PACKED=") // ignore CC_PACKED macros
         .clang_arg("-DCC_PACKED_BEGIN=")
         .clang_arg("-DCC_PACKED_END=")
+        // Functions and globals of esc.c, esc_coe.c, esc_foe.c and esc_eep.c,
+        // ported to Rust: only their types and constants are still bound
+        .blocklist_function("ESC_.*|COE_.*|SDO_.*|MBX_error|sizeOfPDO|APP_safeoutput")
+        .blocklist_var("ESCvar|MBXcontrol|MBX|SMmap2|SMmap3|SDOobjects")
         .clang_arg("-nostdinc") // optional, avoid using system includes
         .clang_arg("-I/usr/local/arm-none-eabi/bin/../arm-none-eabi/include/")
         .clang_arg("-I/usr/local/arm-none-eabi/lib/gcc/arm-none-eabi/14.2.1/include/")
@@ -39,7 +49,8 @@ fn main() {
         .generate()
         .expect("Unable to generate bindings");
 
+    // Built with the ecat_options.h of this build, overrides included
     bindings
-        .write_to_file("src/bindings.rs")
+        .write_to_file(out_dir.join("bindings.rs"))
         .expect("Couldn't write bindings!");
 }
@@ -0,0 +1,218 @@
+//! Build-time code generation from the `esi.json` of the SOES ESI tool:
+//! `ecat_options.h` (mailbox and sync manager [`Layout`] of the stack), the
+//! Rust object dictionary with its `Objects` storage struct, and the
+//! `SiiConfig` of the SII/ESI generators.
+//!
+//! ```ignore
+//! // build.rs
+//! let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+//! let esi = SOES_rs_codegen::esi_path("esi.json");
+//! SOES_rs_codegen::generate(&esi, &out_dir).unwrap();
+//!
+//! // main.rs
+//! mod objectlist {
+//!     include!(concat!(env!("OUT_DIR"), "/objectlist.rs"));
+//! }
+//! ```
+
+#![allow(non_snake_case)]
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{env, fmt, fs, io};
+
+use serde::Deserialize;
+
+mod objectlist;
+mod options;
+mod sii;
+
+pub use options::{env_override, Layout};
+
+/// Environment variable naming the `esi.json` to build from
+pub const ESI_ENV: &str = "SOES_ESI";
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// A form field or object the generator cannot use
+    Invalid(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Json(err) => write!(f, "invalid JSON: {err}"),
+            Self::Invalid(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// `form` section: identity, mailbox and sync manager layout, SII texts
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Form {
+    #[serde(rename = "VendorName")]
+    pub vendor_name: String,
+    #[serde(rename = "VendorID")]
+    pub vendor_id: String,
+    #[serde(rename = "ProductCode")]
+    pub product_code: String,
+    #[serde(rename = "ProfileNo")]
+    pub profile_no: String,
+    #[serde(rename = "RevisionNumber")]
+    pub revision_number: String,
+    #[serde(rename = "SerialNumber")]
+    pub serial_number: String,
+    #[serde(rename = "HWversion")]
+    pub hw_version: String,
+    #[serde(rename = "SWversion")]
+    pub sw_version: String,
+    #[serde(rename = "EEPROMsize")]
+    pub eeprom_size: String,
+    #[serde(rename = "RxMailboxOffset")]
+    pub rx_mailbox_offset: String,
+    #[serde(rename = "TxMailboxOffset")]
+    pub tx_mailbox_offset: String,
+    #[serde(rename = "MailboxSize")]
+    pub mailbox_size: String,
+    #[serde(rename = "SM2Offset")]
+    pub sm2_offset: String,
+    #[serde(rename = "SM3Offset")]
+    pub sm3_offset: String,
+    #[serde(rename = "TextGroupType")]
+    pub group_type: String,
+    #[serde(rename = "TextGroupName5")]
+    pub group_name: String,
+    #[serde(rename = "ImageName")]
+    pub image_name: String,
+    #[serde(rename = "TextDeviceType")]
+    pub device_type: String,
+    #[serde(rename = "TextDeviceName")]
+    pub device_name: String,
+    #[serde(rename = "Port0Physical")]
+    pub port0_physical: String,
+    #[serde(rename = "Port1Physical")]
+    pub port1_physical: String,
+    #[serde(rename = "Port2Physical")]
+    pub port2_physical: String,
+    #[serde(rename = "Port3Physical")]
+    pub port3_physical: String,
+    #[serde(rename = "ESC")]
+    pub esc: String,
+    #[serde(rename = "SPImode")]
+    pub spi_mode: String,
+    /// CoE and mailbox options, enabled when not empty
+    #[serde(rename = "CoeDetailsEnableSDO")]
+    pub coe_sdo: String,
+    #[serde(rename = "CoeDetailsEnableSDOInfo")]
+    pub coe_sdo_info: String,
+    #[serde(rename = "CoeDetailsEnablePDOAssign")]
+    pub coe_pdo_assign: String,
+    #[serde(rename = "CoeDetailsEnablePDOConfiguration")]
+    pub coe_pdo_config: String,
+    #[serde(rename = "CoeDetailsEnableUploadAtStartup")]
+    pub coe_upload_at_startup: String,
+    #[serde(rename = "CoeDetailsEnableSDOCompleteAccess")]
+    pub coe_complete_access: String,
+    #[serde(rename = "FoeDetailsEnableFoE")]
+    pub foe: String,
+    #[serde(rename = "EoeDetailsEnableEoE")]
+    pub eoe: String,
+}
+
+/// Object or array/record subitem of the `od` section
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct OdObject {
+    /// `VAR`, `ARRAY` or `RECORD`
+    pub otype: String,
+    pub name: String,
+    pub access: Option<String>,
+    pub dtype: Option<String>,
+    pub value: Option<String>,
+    /// Storage the entry is bound to, `&Obj.Name`, `&Obj.Name[1]` or
+    /// `&Obj.Record.Name`
+    pub data: Option<String>,
+    /// Subitems of arrays and records, from subindex 0
+    pub items: Vec<OdObject>,
+}
+
+/// `od` section, objects by hexadecimal index
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Od {
+    pub sdo: BTreeMap<String, OdObject>,
+    pub txpdo: BTreeMap<String, OdObject>,
+    pub rxpdo: BTreeMap<String, OdObject>,
+}
+
+/// Decoded `esi.json`
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Esi {
+    pub form: Form,
+    pub od: Od,
+}
+
+impl Esi {
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+}
+
+/// `SOES_ESI` if set, `default` otherwise (relative to the package of the
+/// build script)
+pub fn esi_path(default: impl AsRef<Path>) -> PathBuf {
+    println!("cargo:rerun-if-env-changed={ESI_ENV}");
+    env::var_os(ESI_ENV).map_or_else(|| default.as_ref().into(), PathBuf::from)
+}
+
+/// Write `ecat_options.h`, `objectlist.rs` and `sii_config.rs` generated
+/// from `esi` to `out_dir`, from a build script. `SOES_<DEFINE>` variables
+/// override the layout of `ecat_options.h` (see [`env_override`]).
+pub fn generate(esi: &Path, out_dir: &Path) -> Result<(), Error> {
+    println!("cargo:rerun-if-changed={}", esi.display());
+    let esi = Esi::load(esi)?;
+    let layout = esi.layout(env_override)?;
+    fs::write(out_dir.join("ecat_options.h"), layout.ecat_options())?;
+    fs::write(out_dir.join("objectlist.rs"), esi.objectlist()?)?;
+    fs::write(out_dir.join("sii_config.rs"), esi.sii_config(&layout)?)?;
+    Ok(())
+}
+
+/// Decimal or `0x` hexadecimal number of a form field or value
+fn number(field: &str, text: &str) -> Result<u64, Error> {
+    let text = text.trim();
+    let parsed = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => text.parse(),
+    };
+    parsed.map_err(|_| Error::Invalid(format!("{field}: invalid number \"{text}\"")))
+}
+
+/// Number of a form field that must fit in 32 bits
+fn number32(field: &str, text: &str) -> Result<u32, Error> {
+    u32::try_from(number(field, text)?)
+        .map_err(|_| Error::Invalid(format!("{field}: {text} does not fit in 32 bits")))
+}
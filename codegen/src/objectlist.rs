@@ -0,0 +1,574 @@
+//! `objectlist.rs`: `Objects` storage struct with the process data fields,
+//! SDO objects and the `object_dictionary!` of the application
+
+use std::fmt::Write;
+
+use crate::{number, number32, Error, Esi, OdObject};
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Direction {
+    Rx,
+    Tx,
+}
+
+/// CoE data type of a `dtype`
+struct DataType {
+    dtype: &'static str,
+    /// `SOES_rs::od::DataType` variant
+    variant: &'static str,
+    /// Storage field type, `None` when entries cannot be bound
+    rust: Option<&'static str>,
+    bits: u32,
+    signed: bool,
+}
+
+const fn data_type(
+    dtype: &'static str,
+    variant: &'static str,
+    rust: Option<&'static str>,
+    bits: u32,
+    signed: bool,
+) -> DataType {
+    DataType {
+        dtype,
+        variant,
+        rust,
+        bits,
+        signed,
+    }
+}
+
+const DATA_TYPES: &[DataType] = &[
+    data_type("BOOLEAN", "Boolean", Some("bool"), 1, false),
+    data_type("INTEGER8", "Integer8", Some("i8"), 8, true),
+    data_type("INTEGER16", "Integer16", Some("i16"), 16, true),
+    data_type("INTEGER32", "Integer32", Some("i32"), 32, true),
+    data_type("INTEGER64", "Integer64", Some("i64"), 64, true),
+    data_type("UNSIGNED8", "Unsigned8", Some("u8"), 8, false),
+    data_type("UNSIGNED16", "Unsigned16", Some("u16"), 16, false),
+    data_type("UNSIGNED32", "Unsigned32", Some("u32"), 32, false),
+    data_type("UNSIGNED64", "Unsigned64", Some("u64"), 64, false),
+    data_type("REAL32", "Real32", Some("f32"), 32, true),
+    data_type("REAL64", "Real64", Some("f64"), 64, true),
+    data_type("VISIBLE_STRING", "VisibleString", None, 0, false),
+    data_type("BITARR8", "BitArr8", None, 8, false),
+    data_type("BITARR16", "BitArr16", None, 16, false),
+    data_type("BITARR32", "BitArr32", None, 32, false),
+    data_type("BIT1", "Bit1", None, 1, false),
+    data_type("BIT2", "Bit2", None, 2, false),
+    data_type("BIT3", "Bit3", None, 3, false),
+    data_type("BIT4", "Bit4", None, 4, false),
+    data_type("BIT5", "Bit5", None, 5, false),
+    data_type("BIT6", "Bit6", None, 6, false),
+    data_type("BIT7", "Bit7", None, 7, false),
+    data_type("BIT8", "Bit8", None, 8, false),
+];
+
+const UNSIGNED32: &DataType = &DATA_TYPES[7];
+
+fn find_type(index: u16, dtype: Option<&str>) -> Result<&'static DataType, Error> {
+    let dtype = dtype.unwrap_or_default();
+    DATA_TYPES
+        .iter()
+        .find(|data_type| data_type.dtype == dtype)
+        .ok_or_else(|| Error::Invalid(format!("{index:04X}: unsupported dtype \"{dtype}\"")))
+}
+
+fn access(index: u16, access: Option<&str>) -> Result<&'static str, Error> {
+    Ok(match access.unwrap_or("RO") {
+        "RO" => "RO",
+        "WO" => "WO",
+        "RW" => "RW",
+        "RWpre" => "RW_PRE",
+        other => {
+            return Err(Error::Invalid(format!(
+                "{index:04X}: unsupported access \"{other}\""
+            )))
+        }
+    })
+}
+
+/// Object index of an `od` key
+fn object_index(key: &str) -> Result<u16, Error> {
+    u16::from_str_radix(key.trim_start_matches("0x"), 16)
+        .map_err(|_| Error::Invalid(format!("invalid object index \"{key}\"")))
+}
+
+/// `LedIn` -> `led_in`
+pub(crate) fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1);
+        if c.is_ascii_uppercase()
+            && !snake.is_empty()
+            && !snake.ends_with('_')
+            && (previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+                || (previous.is_some_and(|p| p.is_ascii_uppercase())
+                    && next.is_some_and(|n| n.is_ascii_lowercase())))
+        {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    let snake = snake.trim_end_matches('_');
+    if snake.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{snake}")
+    } else {
+        snake.into()
+    }
+}
+
+fn signed(field: &str, text: &str) -> Result<i64, Error> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let magnitude = number(field, digits)? as i64;
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+/// Argument of `Entry::value` for a default value
+fn default_value(field: &str, data_type: &DataType, text: &str) -> Result<String, Error> {
+    let text = text.trim();
+    let invalid = || Error::Invalid(format!("{field}: invalid {} \"{text}\"", data_type.dtype));
+    Ok(match data_type.variant {
+        "Boolean" => match text {
+            "0" | "false" => "0".into(),
+            "1" | "true" => "1".into(),
+            _ => return Err(invalid()),
+        },
+        "Real32" | "Real64" => {
+            let value: f64 = text.parse().map_err(|_| invalid())?;
+            if value == 0.0 {
+                "0".into()
+            } else if data_type.variant == "Real32" {
+                format!("{:?}f32.to_bits()", value as f32)
+            } else {
+                return Err(Error::Invalid(format!(
+                    "{field}: REAL64 defaults other than 0 are not supported"
+                )));
+            }
+        }
+        _ if data_type.signed => {
+            let value = signed(field, text)?;
+            let bits = data_type.bits.min(32);
+            if value < -(1 << (bits - 1)) || value >= 1 << (bits - 1) {
+                return Err(invalid());
+            }
+            if value < 0 {
+                let rust = data_type.rust.unwrap_or("i32");
+                format!("{value}{rust} as u{} as u32", data_type.bits)
+            } else {
+                value.to_string()
+            }
+        }
+        _ => {
+            let value = number(field, text)?;
+            if value >= 1 << data_type.bits.min(32) {
+                return Err(invalid());
+            }
+            if text.starts_with("0x") || text.starts_with("0X") {
+                format!("0x{value:X}")
+            } else {
+                value.to_string()
+            }
+        }
+    })
+}
+
+/// Initial value of a process data field
+fn initial_value(field: &str, data_type: &DataType, text: &str) -> Result<String, Error> {
+    let text = text.trim();
+    let invalid = || Error::Invalid(format!("{field}: invalid {} \"{text}\"", data_type.dtype));
+    Ok(match data_type.variant {
+        "Boolean" => (default_value(field, data_type, text)? == "1").to_string(),
+        "Real32" | "Real64" => format!("{:?}", text.parse::<f64>().map_err(|_| invalid())?),
+        _ if data_type.signed => signed(field, text)?.to_string(),
+        _ => number(field, text)?.to_string(),
+    })
+}
+
+fn zero(rust: &str) -> &'static str {
+    match rust {
+        "bool" => "false",
+        "f32" | "f64" => "0.0",
+        _ => "0",
+    }
+}
+
+/// Field of the `Objects` storage struct
+struct Field {
+    name: String,
+    rust: &'static str,
+    /// Length of `&Obj.Name[n]` bound arrays
+    len: Option<usize>,
+    init: String,
+    /// `#[txpdo(...)]` / `#[rxpdo(...)]`
+    attr: Option<String>,
+}
+
+#[derive(Default)]
+struct Storage(Vec<Field>);
+
+impl Storage {
+    fn add(&mut self, field: Field) -> Result<(), Error> {
+        if self.0.iter().any(|other| other.name == field.name) {
+            return Err(Error::Invalid(format!(
+                "Obj.{} is bound to more than one entry",
+                field.name
+            )));
+        }
+        self.0.push(field);
+        Ok(())
+    }
+
+    /// Add the field of a `&Obj.Name`, `&Obj.Name[n]` or `&Obj.Record.Name`
+    /// binding, records are flattened to `record_name`. Returns the place
+    /// to bind.
+    fn bind(&mut self, index: u16, data: &str, data_type: &DataType) -> Result<String, Error> {
+        let invalid = |reason: &str| Error::Invalid(format!("{index:04X}: {data} {reason}"));
+        let path = data
+            .trim()
+            .strip_prefix("&Obj.")
+            .ok_or_else(|| invalid("is not an &Obj. field"))?;
+        let rust = data_type
+            .rust
+            .ok_or_else(|| invalid(&format!("cannot bind a {}", data_type.dtype)))?;
+
+        let (path, element) = match path.strip_suffix(']').and_then(|path| path.split_once('[')) {
+            Some((path, element)) => (
+                path,
+                Some(
+                    element
+                        .parse::<usize>()
+                        .map_err(|_| invalid("has an invalid array index"))?,
+                ),
+            ),
+            None => (path, None),
+        };
+        let name = path
+            .split('.')
+            .map(snake_case)
+            .collect::<Vec<_>>()
+            .join("_");
+
+        let Some(element) = element else {
+            self.add(Field {
+                name: name.clone(),
+                rust,
+                len: None,
+                init: zero(rust).into(),
+                attr: None,
+            })?;
+            return Ok(format!("OBJ.{name}"));
+        };
+        match self.0.iter_mut().find(|field| field.name == name) {
+            Some(field) if field.len.is_some() && field.rust == rust => {
+                field.len = field.len.max(Some(element + 1));
+            }
+            Some(_) => return Err(invalid("is bound to more than one entry")),
+            None => self.0.push(Field {
+                name: name.clone(),
+                rust,
+                len: Some(element + 1),
+                init: zero(rust).into(),
+                attr: None,
+            }),
+        }
+        Ok(format!("OBJ.{name}[{element}]"))
+    }
+}
+
+/// `Entry` expression of a VAR object or subitem
+fn entry(
+    index: u16,
+    subindex: u8,
+    item: &OdObject,
+    data_type: &DataType,
+    access: &str,
+    storage: &mut Storage,
+) -> Result<String, Error> {
+    let field = format!("{index:04X}:{subindex:02X}");
+    let value = item.value.as_deref().unwrap_or_default();
+    if data_type.variant == "VisibleString" {
+        if item.data.is_some() {
+            return Err(Error::Invalid(format!(
+                "{field}: bound strings are not supported"
+            )));
+        }
+        return Ok(format!(
+            "Entry::string(0x{subindex:02X}, c{:?}, c{value:?})",
+            item.name
+        ));
+    }
+
+    let mut entry = format!(
+        "Entry::new(0x{subindex:02X}, c{:?}, DataType::{}, Access::{access})",
+        item.name, data_type.variant
+    );
+    if !value.is_empty() {
+        write!(
+            entry,
+            ".value({})",
+            default_value(&field, data_type, value)?
+        )
+        .unwrap();
+    }
+    if let Some(data) = &item.data {
+        let place = storage.bind(index, data, data_type)?;
+        write!(entry, ".bind(unsafe {{ addr_of_mut!({place}) }})").unwrap();
+    }
+    Ok(entry)
+}
+
+impl Esi {
+    /// VAR objects of the `txpdo` or `rxpdo` section by index
+    fn process_data(
+        &self,
+        direction: Direction,
+    ) -> Result<Vec<(u16, &OdObject, &'static DataType)>, Error> {
+        let section = match direction {
+            Direction::Rx => &self.od.rxpdo,
+            Direction::Tx => &self.od.txpdo,
+        };
+        let mut objects = Vec::new();
+        for (key, object) in section {
+            let index = object_index(key)?;
+            if object.otype != "VAR" {
+                return Err(Error::Invalid(format!(
+                    "{index:04X}: only VAR process data objects are supported"
+                )));
+            }
+            let data_type = find_type(index, object.dtype.as_deref())?;
+            if data_type.rust.is_none() {
+                return Err(Error::Invalid(format!(
+                    "{index:04X}: {} cannot be mapped",
+                    data_type.dtype
+                )));
+            }
+            objects.push((index, object, data_type));
+        }
+        objects.sort_by_key(|(index, _, _)| *index);
+        Ok(objects)
+    }
+
+    /// Entries of the generated 0x1600 / 0x1A00 mapping and the process
+    /// data size in bytes, bit fields are padded to the next byte as in
+    /// `#[derive(EtherCatObjects)]`
+    pub(crate) fn mapping(&self, direction: Direction) -> Result<(u32, u32), Error> {
+        let padding = |bits: u32| (8 - bits % 8) % 8;
+        let mut bits = 0;
+        let mut count = 0;
+        for (_, _, data_type) in self.process_data(direction)? {
+            if data_type.bits >= 8 && padding(bits) > 0 {
+                bits += padding(bits);
+                count += 1;
+            }
+            bits += data_type.bits;
+            count += 1;
+        }
+        if padding(bits) > 0 {
+            bits += padding(bits);
+            count += 1;
+        }
+        Ok((count, bits / 8))
+    }
+
+    /// Identity and device objects of the form (0x1000-0x1018, 0x1C00),
+    /// the `sdo` objects, the process data of `txpdo` / `rxpdo` and the
+    /// DC sync manager parameters (0x1C32/0x1C33)
+    pub fn objectlist(&self) -> Result<String, Error> {
+        let form = &self.form;
+        let mut storage = Storage::default();
+        let mut statics = Vec::new();
+        let mut objects = Vec::new();
+
+        let form_value = |field: &str, text: &str| -> Result<String, Error> {
+            number32(field, text)?;
+            default_value(field, UNSIGNED32, text)
+        };
+        statics.push(format!(
+            "static SDO1000: Entry =\n    \
+             Entry::new(0x00, c\"Device Type\", DataType::Unsigned32, Access::RO).value({});",
+            form_value("ProfileNo", &form.profile_no)?
+        ));
+        objects.push("Object::var(0x1000, &SDO1000)".to_string());
+        for (index, name, text) in [
+            (0x1008, "Device Name", &form.device_name),
+            (0x1009, "Hardware Version", &form.hw_version),
+            (0x100A, "Software Version", &form.sw_version),
+        ] {
+            if text.is_empty() {
+                continue;
+            }
+            statics.push(format!(
+                "static SDO{index:04X}: Entry = Entry::string(0x00, c{name:?}, c{text:?});"
+            ));
+            objects.push(format!("Object::var(0x{index:04X}, &SDO{index:04X})"));
+        }
+
+        let serial = storage.bind(0x1018, "&Obj.serial", UNSIGNED32)?;
+        statics.push(format!(
+            "static SDO1018: [Entry; 5] = [\n    \
+             Entry::max_subindex(4),\n    \
+             Entry::new(0x01, c\"Vendor ID\", DataType::Unsigned32, Access::RO).value({}),\n    \
+             Entry::new(0x02, c\"Product Code\", DataType::Unsigned32, Access::RO).value({}),\n    \
+             Entry::new(0x03, c\"Revision Number\", DataType::Unsigned32, Access::RO).value({}),\n    \
+             Entry::new(0x04, c\"Serial Number\", DataType::Unsigned32, Access::RO)\n        \
+             .value({})\n        \
+             .bind(unsafe {{ addr_of_mut!({serial}) }}),\n];",
+            form_value("VendorID", &form.vendor_id)?,
+            form_value("ProductCode", &form.product_code)?,
+            form_value("RevisionNumber", &form.revision_number)?,
+            form_value("SerialNumber", &form.serial_number)?,
+        ));
+        objects.push("Object::record(0x1018, c\"Identity Object\", &SDO1018)".into());
+
+        let mut sm_types = String::new();
+        for sm in 0..4 {
+            write!(
+                sm_types,
+                "\n    Entry::new(0x{:02X}, c\"Communications Type SM{sm}\", \
+                 DataType::Unsigned8, Access::RO).value({}),",
+                sm + 1,
+                sm + 1
+            )
+            .unwrap();
+        }
+        statics.push(format!(
+            "static SDO1C00: [Entry; 5] = [\n    Entry::max_subindex(4),{sm_types}\n];"
+        ));
+        objects
+            .push("Object::array(0x1C00, c\"Sync Manager Communication Type\", &SDO1C00)".into());
+
+        let mut sdo: Vec<(u16, &OdObject)> = Vec::new();
+        for (key, object) in &self.od.sdo {
+            sdo.push((object_index(key)?, object));
+        }
+        sdo.sort_by_key(|(index, _)| *index);
+        for (index, object) in sdo {
+            let kind = match object.otype.as_str() {
+                "VAR" => {
+                    let data_type = find_type(index, object.dtype.as_deref())?;
+                    let access = access(index, object.access.as_deref())?;
+                    let entry = entry(index, 0, object, data_type, access, &mut storage)?;
+                    statics.push(format!("static SDO{index:04X}: Entry = {entry};"));
+                    objects.push(format!("Object::var(0x{index:04X}, &SDO{index:04X})"));
+                    continue;
+                }
+                "ARRAY" => "array",
+                "RECORD" => "record",
+                other => {
+                    return Err(Error::Invalid(format!(
+                        "{index:04X}: unsupported otype \"{other}\""
+                    )))
+                }
+            };
+            if object.items.is_empty() || object.items.len() > 256 {
+                return Err(Error::Invalid(format!(
+                    "{index:04X}: {} needs 1 to 256 items",
+                    object.otype
+                )));
+            }
+            let mut entries = vec![format!("Entry::max_subindex({})", object.items.len() - 1)];
+            for (subindex, item) in object.items.iter().enumerate().skip(1) {
+                // Array items inherit the type and access of the object
+                let data_type =
+                    find_type(index, item.dtype.as_deref().or(object.dtype.as_deref()))?;
+                let access = access(index, item.access.as_deref().or(object.access.as_deref()))?;
+                entries.push(entry(
+                    index,
+                    subindex as u8,
+                    item,
+                    data_type,
+                    access,
+                    &mut storage,
+                )?);
+            }
+            statics.push(format!(
+                "static SDO{index:04X}: [Entry; {}] = [\n    {},\n];",
+                entries.len(),
+                entries.join(",\n    ")
+            ));
+            objects.push(format!(
+                "Object::{kind}(0x{index:04X}, c{:?}, &SDO{index:04X})",
+                object.name
+            ));
+        }
+
+        for (direction, attr) in [(Direction::Tx, "txpdo"), (Direction::Rx, "rxpdo")] {
+            for (index, object, data_type) in self.process_data(direction)? {
+                let name = match object.data.as_deref() {
+                    Some(data) => data
+                        .trim()
+                        .strip_prefix("&Obj.")
+                        .filter(|name| name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+                        .map(snake_case)
+                        .ok_or_else(|| {
+                            Error::Invalid(format!("{index:04X}: {data} is not an &Obj. field"))
+                        })?,
+                    None => snake_case(&object.name),
+                };
+                let rust = data_type.rust.unwrap_or_default();
+                let init = match object.value.as_deref() {
+                    Some(value) => initial_value(&format!("{index:04X}"), data_type, value)?,
+                    None => zero(rust).into(),
+                };
+                storage.add(Field {
+                    name,
+                    rust,
+                    len: None,
+                    init,
+                    attr: Some(format!(
+                        "#[{attr}(index = 0x{index:04X}, name = {:?})]",
+                        object.name
+                    )),
+                })?;
+            }
+        }
+        objects.push("dc::SM2_PARAMETERS".into());
+        objects.push("dc::SM3_PARAMETERS".into());
+
+        let mut fields = String::new();
+        let mut inits = String::new();
+        for field in &storage.0 {
+            let (rust, init) = match field.len {
+                Some(len) => (
+                    format!("[{}; {len}]", field.rust),
+                    format!("[{}; {len}]", field.init),
+                ),
+                None => (field.rust.to_string(), field.init.clone()),
+            };
+            if let Some(attr) = &field.attr {
+                writeln!(fields, "    {attr}").unwrap();
+            }
+            writeln!(fields, "    pub {}: {rust},", field.name).unwrap();
+            writeln!(inits, "    {}: {init},", field.name).unwrap();
+        }
+
+        Ok(format!(
+            "// Generated by SOES-rs-codegen from esi.json, do not edit\n\n\
+             use core::ptr::addr_of_mut;\n\
+             use SOES_rs::dc;\n\
+             use SOES_rs::object_dictionary;\n\
+             use SOES_rs::od::{{Access, DataType, Entry, EtherCatObjects, Object}};\n\n\
+             /// Object dictionary storage, process data objects are generated from it\n\
+             #[derive(EtherCatObjects)]\n\
+             #[ethercat(storage = OBJ)]\n\
+             pub struct Objects {{\n{fields}}}\n\n\
+             pub static mut OBJ: Objects = Objects {{\n{inits}}};\n\n\
+             {}\n\n\
+             object_dictionary![\n    {};\n    Objects\n];\n",
+            statics.join("\n\n"),
+            objects.join(",\n    ")
+        ))
+    }
+}
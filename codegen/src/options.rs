@@ -0,0 +1,251 @@
+//! `ecat_options.h`: mailbox and sync manager layout read by the stack
+
+use std::env;
+use std::fmt::Write;
+
+use crate::objectlist::Direction;
+use crate::{number, number32, Error, Esi};
+
+/// Process data buffers, the largest SM2/SM3 size
+const MAX_PDO_SIZE: u32 = 512;
+/// PDO mapping entries of SM2 / SM3, as in `options.h`. The esi.json
+/// mappings only set the minimum, the master may map more.
+const MAX_MAPPINGS: u32 = 16;
+
+/// Mailbox and sync manager layout of the stack, the `ecat_options.h`
+/// defines. Sizes and offsets are in bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub use_foe: bool,
+    pub use_eoe: bool,
+    pub mbx_size: u32,
+    pub mbx_size_boot: u32,
+    pub mbx_buffers: u32,
+    /// Receive (SM0) and send (SM1) mailbox offsets, `_b` in BOOT
+    pub mbx0_sma: u32,
+    pub mbx1_sma: u32,
+    pub mbx0_sma_b: u32,
+    pub mbx1_sma_b: u32,
+    pub sm2_sma: u32,
+    pub sm3_sma: u32,
+    /// PDO mapping entries of SM2 / SM3
+    pub max_mappings_sm2: u32,
+    pub max_mappings_sm3: u32,
+    pub max_rxpdo_size: u32,
+    pub max_txpdo_size: u32,
+}
+
+/// `SOES_<DEFINE>` environment variable overriding a define, named as in
+/// `ecat_options.h`, e.g. `SOES_MBXBUFFERS` or `SOES_SM2_sma`, for build
+/// scripts
+pub fn env_override(define: &str) -> Option<String> {
+    let var = format!("SOES_{define}");
+    println!("cargo:rerun-if-env-changed={var}");
+    env::var(var).ok()
+}
+
+impl Layout {
+    /// Numeric defines
+    fn values(&mut self) -> [(&'static str, &mut u32); 13] {
+        [
+            ("MBXSIZE", &mut self.mbx_size),
+            ("MBXSIZEBOOT", &mut self.mbx_size_boot),
+            ("MBXBUFFERS", &mut self.mbx_buffers),
+            ("MBX0_sma", &mut self.mbx0_sma),
+            ("MBX1_sma", &mut self.mbx1_sma),
+            ("MBX0_sma_b", &mut self.mbx0_sma_b),
+            ("MBX1_sma_b", &mut self.mbx1_sma_b),
+            ("SM2_sma", &mut self.sm2_sma),
+            ("SM3_sma", &mut self.sm3_sma),
+            ("MAX_MAPPINGS_SM2", &mut self.max_mappings_sm2),
+            ("MAX_MAPPINGS_SM3", &mut self.max_mappings_sm3),
+            ("MAX_RXPDO_SIZE", &mut self.max_rxpdo_size),
+            ("MAX_TXPDO_SIZE", &mut self.max_txpdo_size),
+        ]
+    }
+
+    /// Mailboxes fit without overlapping each other or the process data
+    /// buffers, the mapping tables and process data buffers hold the PDOs
+    /// (entries, bytes) and the three SM2 buffers end before SM3
+    fn check(&self, rxpdo: (u32, u32), txpdo: (u32, u32)) -> Result<(), Error> {
+        let invalid = |reason: String| Err(Error::Invalid(reason));
+        for (define, value) in [
+            ("MBXSIZE", self.mbx_size),
+            ("MBXSIZEBOOT", self.mbx_size_boot),
+            ("MBXBUFFERS", self.mbx_buffers),
+        ] {
+            if value == 0 {
+                return invalid(format!("{define} must not be 0"));
+            }
+        }
+        for (rx, tx, size) in [
+            (self.mbx0_sma, self.mbx1_sma, self.mbx_size),
+            (self.mbx0_sma_b, self.mbx1_sma_b, self.mbx_size_boot),
+        ] {
+            if rx.abs_diff(tx) < size {
+                return invalid(format!(
+                    "mailboxes at 0x{rx:X} and 0x{tx:X} overlap ({size} bytes)"
+                ));
+            }
+        }
+        for (define, max, needed, what) in [
+            (
+                "MAX_MAPPINGS_SM2",
+                self.max_mappings_sm2,
+                rxpdo.0,
+                "RxPDO entries",
+            ),
+            (
+                "MAX_MAPPINGS_SM3",
+                self.max_mappings_sm3,
+                txpdo.0,
+                "TxPDO entries",
+            ),
+            (
+                "MAX_RXPDO_SIZE",
+                self.max_rxpdo_size,
+                rxpdo.1,
+                "RxPDO bytes",
+            ),
+            (
+                "MAX_TXPDO_SIZE",
+                self.max_txpdo_size,
+                txpdo.1,
+                "TxPDO bytes",
+            ),
+        ] {
+            if max < needed {
+                return invalid(format!("{define} {max} is less than the {needed} {what}"));
+            }
+        }
+        if self.sm2_sma + rxpdo.1 * 3 > self.sm3_sma {
+            return invalid(format!(
+                "3 RxPDO buffers of {} bytes at SM2 0x{:X} overlap SM3 0x{:X}",
+                rxpdo.1, self.sm2_sma, self.sm3_sma
+            ));
+        }
+        // The bootstrap mailboxes are only used with SM2/SM3 disabled
+        for mbx in [self.mbx0_sma, self.mbx1_sma] {
+            for (sm, sma, size) in [
+                ("SM2", self.sm2_sma, rxpdo.1 * 3),
+                ("SM3", self.sm3_sma, txpdo.1 * 3),
+            ] {
+                if mbx < sma + size && sma < mbx + self.mbx_size {
+                    return invalid(format!(
+                        "mailbox at 0x{mbx:X} overlaps the {sm} buffers at 0x{sma:X} ({size} bytes)"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn ecat_options(&self) -> String {
+        let mut header = String::new();
+        let mut define = |name: &str, value: String| {
+            writeln!(header, "#define {name:<16} {value}").unwrap();
+        };
+
+        define("USE_FOE", (self.use_foe as u8).to_string());
+        define("USE_EOE", (self.use_eoe as u8).to_string());
+        define("MBXSIZE", self.mbx_size.to_string());
+        define("MBXSIZEBOOT", self.mbx_size_boot.to_string());
+        define("MBXBUFFERS", self.mbx_buffers.to_string());
+        for (suffix, size, rx, tx) in [
+            ("", "MBXSIZE", self.mbx0_sma, self.mbx1_sma),
+            ("_b", "MBXSIZEBOOT", self.mbx0_sma_b, self.mbx1_sma_b),
+        ] {
+            define(&format!("MBX0_sma{suffix}"), format!("0x{rx:04X}"));
+            define(&format!("MBX0_sml{suffix}"), size.into());
+            define(
+                &format!("MBX0_sme{suffix}"),
+                format!("MBX0_sma{suffix}+MBX0_sml{suffix}-1"),
+            );
+            define(&format!("MBX0_smc{suffix}"), "0x26".into());
+            define(&format!("MBX1_sma{suffix}"), format!("0x{tx:04X}"));
+            define(&format!("MBX1_sml{suffix}"), size.into());
+            define(
+                &format!("MBX1_sme{suffix}"),
+                format!("MBX1_sma{suffix}+MBX1_sml{suffix}-1"),
+            );
+            define(&format!("MBX1_smc{suffix}"), "0x22".into());
+        }
+        define("SM2_sma", format!("0x{:04X}", self.sm2_sma));
+        define("SM2_smc", "0x24".into());
+        define("SM2_act", "1".into());
+        define("SM3_sma", format!("0x{:04X}", self.sm3_sma));
+        define("SM3_smc", "0x20".into());
+        define("SM3_act", "1".into());
+        define("MAX_MAPPINGS_SM2", self.max_mappings_sm2.to_string());
+        define("MAX_MAPPINGS_SM3", self.max_mappings_sm3.to_string());
+        define("MAX_RXPDO_SIZE", self.max_rxpdo_size.to_string());
+        define("MAX_TXPDO_SIZE", self.max_txpdo_size.to_string());
+
+        format!(
+            "/* Generated by SOES-rs-codegen from esi.json, do not edit */\n\
+             #ifndef __ECAT_OPTIONS_H__\n\
+             #define __ECAT_OPTIONS_H__\n\n\
+             {header}\n\
+             #endif /* __ECAT_OPTIONS_H__ */\n"
+        )
+    }
+}
+
+impl Esi {
+    /// Layout of the form (the `options.h` defaults for missing fields,
+    /// bootstrap mailboxes as the standard ones) and of the process data
+    /// objects, then `overrides` of single defines (e.g. [`env_override`]).
+    /// The result is checked against the process data objects.
+    pub fn layout(&self, overrides: impl Fn(&str) -> Option<String>) -> Result<Layout, Error> {
+        let form = &self.form;
+        let field = |field: &str, text: &str, default: u32| {
+            if text.trim().is_empty() {
+                Ok(default)
+            } else {
+                number32(field, text)
+            }
+        };
+        let mbx_size = field("MailboxSize", &form.mailbox_size, 128)?;
+        let mbx0_sma = field("RxMailboxOffset", &form.rx_mailbox_offset, 0x1000)?;
+        let mbx1_sma = field(
+            "TxMailboxOffset",
+            &form.tx_mailbox_offset,
+            mbx0_sma + mbx_size,
+        )?;
+        let rxpdo = self.mapping(Direction::Rx)?;
+        let txpdo = self.mapping(Direction::Tx)?;
+        let mut layout = Layout {
+            use_foe: !form.foe.is_empty(),
+            use_eoe: !form.eoe.is_empty(),
+            mbx_size,
+            mbx_size_boot: mbx_size,
+            mbx_buffers: 3,
+            mbx0_sma,
+            mbx1_sma,
+            mbx0_sma_b: mbx0_sma,
+            mbx1_sma_b: mbx1_sma,
+            sm2_sma: field("SM2Offset", &form.sm2_offset, 0x1600)?,
+            sm3_sma: field("SM3Offset", &form.sm3_offset, 0x1A00)?,
+            max_mappings_sm2: MAX_MAPPINGS,
+            max_mappings_sm3: MAX_MAPPINGS,
+            max_rxpdo_size: MAX_PDO_SIZE,
+            max_txpdo_size: MAX_PDO_SIZE,
+        };
+
+        for (define, flag) in [
+            ("USE_FOE", &mut layout.use_foe),
+            ("USE_EOE", &mut layout.use_eoe),
+        ] {
+            if let Some(text) = overrides(define) {
+                *flag = number(define, &text)? != 0;
+            }
+        }
+        for (define, value) in layout.values() {
+            if let Some(text) = overrides(define) {
+                *value = number32(define, &text)?;
+            }
+        }
+        layout.check(rxpdo, txpdo)?;
+        Ok(layout)
+    }
+}
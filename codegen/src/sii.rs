@@ -0,0 +1,108 @@
+//! `sii_config.rs`: `SiiConfig` of the SII image and ESI file generators
+
+use crate::{number, number32, Error, Esi, Layout};
+
+/// Mailbox protocols, as in `SOES_rs::sii`
+const MBX_EOE: u16 = 0x0002;
+const MBX_COE: u16 = 0x0004;
+const MBX_FOE: u16 = 0x0008;
+
+/// Physical layer nibble of a `PortNPhysical` letter
+fn physical(port: &str) -> Result<u16, Error> {
+    match port.trim() {
+        "" => Ok(0),
+        "Y" => Ok(1),
+        "K" => Ok(3),
+        other => Err(Error::Invalid(format!(
+            "unsupported port physical layer \"{other}\""
+        ))),
+    }
+}
+
+impl Esi {
+    /// `fn sii_config() -> SiiConfig` for the texts, EEPROM size, CoE
+    /// details and ports of the form, FoE and EoE as enabled in `layout`.
+    /// Only the LAN9252 PDI is supported.
+    pub fn sii_config(&self, layout: &Layout) -> Result<String, Error> {
+        let form = &self.form;
+        if !matches!(form.esc.as_str(), "" | "LAN9252") {
+            return Err(Error::Invalid(format!(
+                "ESC {} is not supported, only LAN9252",
+                form.esc
+            )));
+        }
+        let spi_mode = match form.spi_mode.as_str() {
+            "" => 0,
+            mode => number("SPImode", mode)?,
+        };
+        if spi_mode > 3 {
+            return Err(Error::Invalid(format!("invalid SPI mode {spi_mode}")));
+        }
+
+        let coe_details = [
+            &form.coe_sdo,
+            &form.coe_sdo_info,
+            &form.coe_pdo_assign,
+            &form.coe_pdo_config,
+            &form.coe_upload_at_startup,
+            &form.coe_complete_access,
+        ]
+        .iter()
+        .enumerate()
+        .filter(|(_, enabled)| !enabled.is_empty())
+        .fold(0u8, |details, (bit, _)| details | 1 << bit);
+        let mut protocols = 0;
+        for (enabled, protocol) in [
+            (coe_details != 0, MBX_COE),
+            (layout.use_foe, MBX_FOE),
+            (layout.use_eoe, MBX_EOE),
+        ] {
+            if enabled {
+                protocols |= protocol;
+            }
+        }
+        let mut ports = 0;
+        for (n, port) in [
+            &form.port0_physical,
+            &form.port1_physical,
+            &form.port2_physical,
+            &form.port3_physical,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            ports |= physical(port)? << (n * 4);
+        }
+
+        Ok(format!(
+            "// Generated by SOES-rs-codegen from esi.json, do not edit\n\n\
+             /// SII and ESI settings of the esi.json form\n\
+             pub fn sii_config() -> SOES_rs::sii::SiiConfig {{\n    \
+             SOES_rs::sii::SiiConfig {{\n        \
+             pdi: [0x0680, 0x{:04X}, 0x0064, 0x0000],\n        \
+             eeprom_size: {},\n        \
+             mailbox_protocols: 0x{protocols:04X},\n        \
+             group: {:?}.into(),\n        \
+             image: {:?}.into(),\n        \
+             device_type: {:?}.into(),\n        \
+             coe_details: 0x{coe_details:02X},\n        \
+             foe_details: {},\n        \
+             eoe_details: {},\n        \
+             physical_ports: 0x{ports:04X},\n        \
+             vendor_name: {:?}.into(),\n        \
+             group_name: {:?}.into(),\n        \
+             ..Default::default()\n    \
+             }}\n\
+             }}\n",
+            0x4400 | spi_mode,
+            number32("EEPROMsize", &form.eeprom_size)?,
+            form.group_type,
+            form.image_name,
+            form.device_type,
+            layout.use_foe as u8,
+            layout.use_eoe as u8,
+            form.vendor_name,
+            form.group_name,
+        ))
+    }
+}
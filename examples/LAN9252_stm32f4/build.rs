@@ -1,4 +1,13 @@
+use std::env;
+use std::path::PathBuf;
+
 fn main() {
+    // Object dictionary from the esi.json also configuring the stack
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let esi = SOES_rs_codegen::esi_path("../../src/soes-c/soes-esi/esi.json");
+    SOES_rs_codegen::generate(&esi, &out_dir)
+        .unwrap_or_else(|err| panic!("{}: {err}", esi.display()));
+
     println!("cargo:rustc-link-arg-bins=--nmagic");
     println!("cargo:rustc-link-arg-bins=-Tlink.x");
     #[cfg(feature = "defmt")]
@@ -0,0 +1,48 @@
+#![no_std]
+#![no_main]
+
+use {defmt_rtt as _, panic_probe as _};
+
+use embassy_executor::Spawner;
+use embassy_stm32::gpio::{Level, Output, Speed};
+use embassy_stm32::spi::{Config, Mode, Phase, Polarity, Spi};
+use embassy_stm32::time::Hertz;
+use embedded_hal_bus::spi::ExclusiveDevice;
+
+use SOES_rs::drivers::Lan9252Async;
+use SOES_rs::esc_driver::AsyncEscDriver;
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {
+    let p = embassy_stm32::init(Default::default()); // ⚠️ didn't check if clock config is correct
+
+    let mut spi_config = Config::default();
+    spi_config.frequency = Hertz(1_000_000);
+    spi_config.mode = Mode {
+        polarity: Polarity::IdleLow,
+        phase: Phase::CaptureOnFirstTransition,
+    };
+
+    // SPI1 with DMA2 stream 3 (TX) / stream 0 (RX)
+    let spi = Spi::new(
+        p.SPI1, p.PA5, p.PA7, p.PA6, p.DMA2_CH3, p.DMA2_CH0, spi_config,
+    );
+    let cs = Output::new(p.PB6, Level::High, Speed::VeryHigh);
+    let spi_dev = ExclusiveDevice::new_no_delay(spi, cs).unwrap();
+
+    defmt::info!("Hello, World!");
+
+    let mut lan = Lan9252Async::new(spi_dev);
+    if let Err(err) = lan.init().await {
+        defmt::error!("LAN9252 init failed: {}", err);
+    }
+
+    // Read ESC type / revision through the async CSR path
+    let mut buf = [0u8; 2];
+    match lan.read(0x0000, &mut buf).await {
+        Ok(()) => defmt::info!("ESC type: 0x{:02X}, revision: 0x{:02X}", buf[0], buf[1]),
+        Err(err) => defmt::error!("ESC read failed: {}", err),
+    }
+
+    loop {}
+}
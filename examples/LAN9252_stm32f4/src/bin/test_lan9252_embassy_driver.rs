@@ -57,9 +57,12 @@ async fn main(_spawner: Spawner) {
 
     defmt::info!("Hello, World!");
 
-    let mut lan = Lan9252Blocking::new(spi, cs);
+    let mut lan = Lan9252Blocking::from_embassy(spi, cs);
     let cfg = dummy_esc_cfg();
-    lan.init();
+    match lan.bring_up() {
+        Ok(chip) => defmt::info!("LAN9252 ready: {}", chip),
+        Err(err) => defmt::error!("LAN9252 bring-up failed: {}", err),
+    }
 
     loop {}
 }
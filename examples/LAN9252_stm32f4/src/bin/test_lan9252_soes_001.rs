@@ -12,32 +12,13 @@ use embassy_time::{Duration, Timer};
 
 use core::ptr;
 use SOES_rs::bindings::esc_cfg;
-use SOES_rs::drivers::set_driver;
 use SOES_rs::drivers::Lan9252Blocking;
-use SOES_rs::esc_driver::EscDriver;
 use SOES_rs::soes;
 
-use core::cell::RefCell;
-//static DRIVER: RefCell<Option<Lan9252Blocking<'static>>> = RefCell::new(None);
-
-#[repr(C)]
-pub struct _Objects {
-    pub serial: u32,
-    pub Key1: u8,
-    pub Key2: u8,
-    pub Counter: u32,
-    pub LedIn: u8,
+mod objectlist {
+    include!(concat!(env!("OUT_DIR"), "/objectlist.rs"));
 }
-
-// global variable expected by soes-c
-#[no_mangle]
-pub static mut Obj: _Objects = _Objects {
-    serial: 0,
-    Key1: 0,
-    Key2: 0,
-    Counter: 0,
-    LedIn: 0,
-};
+use objectlist::OBJ;
 
 // Dummy esc_cfg for test
 fn dummy_esc_cfg() -> esc_cfg {
@@ -66,7 +47,7 @@ fn dummy_esc_cfg() -> esc_cfg {
 
 fn my_outputs() {
     unsafe {
-        if (Obj.LedIn != 0) {
+        if OBJ.led_in {
             defmt::info!("LED ON \r\n");
         } else {
             defmt::info!("LED OFF \r\n");
@@ -76,7 +57,7 @@ fn my_outputs() {
 
 fn my_inputs() {
     unsafe {
-        Obj.Counter += 1;
+        OBJ.counter += 1;
     }
 }
 
@@ -97,21 +78,19 @@ async fn main(_spawner: Spawner) {
 
     defmt::info!("Hello, World!");
 
-    let mut driver = Lan9252Blocking::new(spi, cs);
-    let drv_ptr: *mut dyn EscDriver = &mut driver;
-
-    unsafe {
-        let drv_ref: &mut dyn EscDriver = &mut *drv_ptr;
-        set_driver(drv_ref);
-    }
-    let mut ecat_slv = soes::EcatSlave::new(dummy_esc_cfg());
+    let driver = Lan9252Blocking::from_embassy(spi, cs);
+    let mut ecat_slv = soes::EcatSlave::new(driver, dummy_esc_cfg());
 
     ecat_slv.set_output_cb(my_outputs);
     ecat_slv.set_input_cb(my_inputs);
-    ecat_slv.init();
+    if let Err(err) = ecat_slv.init() {
+        defmt::error!("ESC startup failed: {}", err);
+    }
     ecat_slv.pdi_debug();
 
     loop {
-        ecat_slv.run();
+        if let Err(err) = ecat_slv.run() {
+            defmt::warn!("ESC driver error, slave moved to safe state: {}", err);
+        }
     }
 }
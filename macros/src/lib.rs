@@ -0,0 +1,317 @@
+//! `#[derive(EtherCatObjects)]`: object dictionary entries, PDO mappings
+//! and SM assignments generated from a process data struct.
+
+#![allow(non_snake_case)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Expr, Fields, Ident, LitByteStr, LitInt, LitStr,
+    Type,
+};
+
+const RXPDO_MAPPING: u16 = 0x1600;
+const TXPDO_MAPPING: u16 = 0x1A00;
+const RXPDO_ASSIGN: u16 = 0x1C12;
+const TXPDO_ASSIGN: u16 = 0x1C13;
+
+/// Generate the OD objects of a process data struct.
+///
+/// ```ignore
+/// #[derive(EtherCatObjects)]
+/// #[ethercat(storage = OBJ)]
+/// pub struct ProcessData {
+///     #[txpdo(index = 0x6000, name = "Key1")]
+///     pub key1: bool,
+///     #[txpdo(index = 0x6002)]
+///     pub counter: u32,
+///     #[rxpdo(index = 0x7000)]
+///     pub led_in: bool,
+/// }
+/// pub static mut OBJ: ProcessData = ...;
+/// ```
+///
+/// Each annotated field becomes a VAR object bound to `storage.field`.
+/// RxPDO fields are mapped in 0x1600 and assigned in 0x1C12, TxPDO fields
+/// in 0x1A00 / 0x1C13 (`rxpdo_mapping` / `txpdo_mapping` change the mapping
+/// indexes). Bit fields are padded to the next byte.
+#[proc_macro_derive(EtherCatObjects, attributes(ethercat, rxpdo, txpdo))]
+pub fn derive_ethercat_objects(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Rx,
+    Tx,
+}
+
+struct PdoField {
+    ident: Ident,
+    direction: Direction,
+    index: u16,
+    name: String,
+    datatype: Ident,
+    bits: u16,
+}
+
+struct Config {
+    storage: Expr,
+    rxpdo_mapping: u16,
+    txpdo_mapping: u16,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let config = parse_config(&input)?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(&input, "EtherCatObjects needs a struct"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input,
+            "EtherCatObjects needs named fields",
+        ));
+    };
+
+    let mut pdo_fields = Vec::new();
+    for field in &fields.named {
+        for attr in &field.attrs {
+            let direction = if attr.path().is_ident("rxpdo") {
+                Direction::Rx
+            } else if attr.path().is_ident("txpdo") {
+                Direction::Tx
+            } else {
+                continue;
+            };
+
+            let ident = field.ident.clone().unwrap();
+            let mut index = None;
+            let mut name = ident.to_string();
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("index") {
+                    index = Some(meta.value()?.parse::<LitInt>()?.base10_parse::<u16>()?);
+                    Ok(())
+                } else if meta.path.is_ident("name") {
+                    name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `index` or `name`"))
+                }
+            })?;
+            let index =
+                index.ok_or_else(|| Error::new_spanned(attr, "missing `index = 0x....`"))?;
+            let (datatype, bits) = data_type(&field.ty)?;
+
+            pdo_fields.push(PdoField {
+                ident,
+                direction,
+                index,
+                name,
+                datatype: Ident::new(datatype, Span::call_site()),
+                bits,
+            });
+        }
+    }
+
+    let storage = &config.storage;
+    let mut statics = Vec::new();
+    let mut objects = Vec::new();
+
+    for field in &pdo_fields {
+        let entry = format_ident!("__ENTRY_{}", field.ident.to_string().to_uppercase());
+        let ident = &field.ident;
+        let name = cstr(&field.name);
+        let datatype = &field.datatype;
+        let pdo = match field.direction {
+            Direction::Rx => quote!(rxpdo),
+            Direction::Tx => quote!(txpdo),
+        };
+        let index = field.index;
+        statics.push(quote! {
+            static #entry: Entry = Entry::new(0, #name, DataType::#datatype, Access::RO)
+                .#pdo()
+                .bind(unsafe { ::core::ptr::addr_of_mut!(#storage.#ident) });
+        });
+        objects.push(quote!(Object::var(#index, &#entry)));
+    }
+
+    for (direction, mapping_index, assign_index, mapping_name, assign_name) in [
+        (
+            Direction::Rx,
+            config.rxpdo_mapping,
+            RXPDO_ASSIGN,
+            "Outputs",
+            "Sync Manager 2 PDO Assignment",
+        ),
+        (
+            Direction::Tx,
+            config.txpdo_mapping,
+            TXPDO_ASSIGN,
+            "Inputs",
+            "Sync Manager 3 PDO Assignment",
+        ),
+    ] {
+        let fields: Vec<&PdoField> = pdo_fields
+            .iter()
+            .filter(|field| field.direction == direction)
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+
+        let entries = mapping_entries(&fields);
+        let count = entries.len() + 1;
+        let max_subindex = entries.len() as u8;
+        let (mapping, assign) = match direction {
+            Direction::Rx => (quote!(__RXPDO_MAPPING), quote!(__RXPDO_ASSIGN)),
+            Direction::Tx => (quote!(__TXPDO_MAPPING), quote!(__TXPDO_ASSIGN)),
+        };
+        let mapping_name = cstr(mapping_name);
+        let assign_name = cstr(assign_name);
+
+        statics.push(quote! {
+            static #mapping: [Entry; #count] = [Entry::max_subindex(#max_subindex), #(#entries),*];
+            static #assign: [Entry; 2] = [
+                Entry::max_subindex(1),
+                Entry::new(0x01, c"PDO Mapping", DataType::Unsigned16, Access::RO)
+                    .value(#mapping_index as u32),
+            ];
+        });
+        objects.push(quote!(Object::record(#mapping_index, #mapping_name, &#mapping)));
+        objects.push(quote!(Object::array(#assign_index, #assign_name, &#assign)));
+    }
+
+    let ident = &input.ident;
+    Ok(quote! {
+        const _: () = {
+            use ::SOES_rs::od::{Access, DataType, Entry, Object};
+
+            #(#statics)*
+
+            impl ::SOES_rs::od::EtherCatObjects for #ident {
+                const OBJECTS: &'static [Object] = &[#(#objects),*];
+            }
+        };
+    })
+}
+
+fn parse_config(input: &DeriveInput) -> syn::Result<Config> {
+    let mut storage = None;
+    let mut rxpdo_mapping = RXPDO_MAPPING;
+    let mut txpdo_mapping = TXPDO_MAPPING;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("ethercat"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("storage") {
+                storage = Some(meta.value()?.parse::<Expr>()?);
+            } else if meta.path.is_ident("rxpdo_mapping") {
+                rxpdo_mapping = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+            } else if meta.path.is_ident("txpdo_mapping") {
+                txpdo_mapping = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+            } else {
+                return Err(meta.error("expected `storage`, `rxpdo_mapping` or `txpdo_mapping`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let storage = storage.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "missing `#[ethercat(storage = STATIC)]` naming the object storage",
+        )
+    })?;
+    Ok(Config {
+        storage,
+        rxpdo_mapping,
+        txpdo_mapping,
+    })
+}
+
+/// CoE data type and bit length of a supported field type
+fn data_type(ty: &Type) -> syn::Result<(&'static str, u16)> {
+    let name = match ty {
+        Type::Path(path) if path.qself.is_none() => path
+            .path
+            .get_ident()
+            .map(|ident| ident.to_string())
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+    Ok(match name.as_str() {
+        "bool" => ("Boolean", 1),
+        "u8" => ("Unsigned8", 8),
+        "i8" => ("Integer8", 8),
+        "u16" => ("Unsigned16", 16),
+        "i16" => ("Integer16", 16),
+        "u32" => ("Unsigned32", 32),
+        "i32" => ("Integer32", 32),
+        "f32" => ("Real32", 32),
+        "u64" => ("Unsigned64", 64),
+        "i64" => ("Integer64", 64),
+        "f64" => ("Real64", 64),
+        _ => {
+            return Err(Error::new_spanned(
+                ty,
+                "unsupported PDO type, expected bool or a primitive integer/float",
+            ))
+        }
+    })
+}
+
+/// Mapping entries (`index << 16 | subindex << 8 | bits`), padding bit
+/// fields to the next byte
+fn mapping_entries(fields: &[&PdoField]) -> Vec<TokenStream2> {
+    let mut entries = Vec::new();
+    let mut bit_offset: u16 = 0;
+    let mut subindex: u8 = 1;
+
+    let mut push = |entries: &mut Vec<TokenStream2>, name: &str, value: u32| {
+        let name = cstr(name);
+        entries.push(quote! {
+            Entry::new(#subindex, #name, DataType::Unsigned32, Access::RO).value(#value)
+        });
+        subindex += 1;
+    };
+
+    for field in fields {
+        let padding = padding_bits(bit_offset);
+        if field.bits >= 8 && padding > 0 {
+            push(&mut entries, "Padding", padding as u32);
+            bit_offset += padding;
+        }
+        push(
+            &mut entries,
+            &field.name,
+            ((field.index as u32) << 16) | field.bits as u32,
+        );
+        bit_offset += field.bits;
+    }
+    let padding = padding_bits(bit_offset);
+    if padding > 0 {
+        push(&mut entries, "Padding", padding as u32);
+    }
+    entries
+}
+
+/// Bits from `bit_offset` to the next byte boundary
+fn padding_bits(bit_offset: u16) -> u16 {
+    (8 - bit_offset % 8) % 8
+}
+
+/// `&'static CStr` expression for `text`
+fn cstr(text: &str) -> TokenStream2 {
+    let mut bytes = text.as_bytes().to_vec();
+    bytes.push(0);
+    let bytes = LitByteStr::new(&bytes, Span::call_site());
+    quote!(::SOES_rs::od::cstr(#bytes))
+}
@@ -0,0 +1,583 @@
+//! EtherCAT AL state machine (INIT/PREOP/SAFEOP/OP/BOOT), port of
+//! `ESC_state` and `ESC_sm_act_event` from `esc.c`
+
+use core::ptr::addr_of_mut;
+
+use crate::bindings::*;
+use crate::coe::pdo;
+use crate::dc;
+use crate::esc;
+use crate::foe;
+use crate::mbx;
+use crate::soes::{APP_safeoutput, ESCvar, SMmap2, SMmap3};
+
+/// AL state, as requested in AL control (0x120) and reported in AL status
+/// (0x130)
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum AlState {
+    Init = ESCinit as u8,
+    PreOp = ESCpreop as u8,
+    Boot = ESCboot as u8,
+    SafeOp = ESCsafeop as u8,
+    Op = ESCop as u8,
+}
+
+impl AlState {
+    /// State from the low nibble of AL control / AL status
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        match bits & ESCREG_AL_ERRACKMASK as u8 {
+            b if b == ESCinit as u8 => Some(Self::Init),
+            b if b == ESCpreop as u8 => Some(Self::PreOp),
+            b if b == ESCboot as u8 => Some(Self::Boot),
+            b if b == ESCsafeop as u8 => Some(Self::SafeOp),
+            b if b == ESCop as u8 => Some(Self::Op),
+            _ => None,
+        }
+    }
+
+    pub const fn bits(self) -> u8 {
+        self as u8
+    }
+}
+
+/// AL status code (0x134). Vendor specific codes (0x8000..) are plain values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct AlStatusCode(pub u16);
+
+impl AlStatusCode {
+    pub const NONE: Self = Self(ALERR_NONE as u16);
+    pub const UNSPECIFIED_ERROR: Self = Self(ALERR_UNSPECIFIEDERROR as u16);
+    pub const NO_MEMORY: Self = Self(ALERR_NOMEMORY as u16);
+    pub const INVALID_STATE_CHANGE: Self = Self(ALERR_INVALIDSTATECHANGE as u16);
+    pub const UNKNOWN_STATE: Self = Self(ALERR_UNKNOWNSTATE as u16);
+    pub const BOOT_NOT_SUPPORTED: Self = Self(ALERR_BOOTNOTSUPPORTED as u16);
+    pub const NO_VALID_FIRMWARE: Self = Self(ALERR_NOVALIDFIRMWARE as u16);
+    pub const INVALID_BOOT_MBX_CONFIG: Self = Self(ALERR_INVALIDBOOTMBXCONFIG as u16);
+    pub const INVALID_MBX_CONFIG: Self = Self(ALERR_INVALIDMBXCONFIG as u16);
+    pub const INVALID_SM_CONFIG: Self = Self(ALERR_INVALIDSMCONFIG as u16);
+    pub const NO_VALID_INPUTS: Self = Self(ALERR_NOVALIDINPUTS as u16);
+    pub const NO_VALID_OUTPUTS: Self = Self(ALERR_NOVALIDOUTPUTS as u16);
+    pub const SYNC_ERROR: Self = Self(ALERR_SYNCERROR as u16);
+    pub const WATCHDOG: Self = Self(ALERR_WATCHDOG as u16);
+    pub const INVALID_SYNC_MANAGER_TYPE: Self = Self(ALERR_INVALIDSYNCMANAGERTYP as u16);
+    pub const INVALID_OUTPUT_SM: Self = Self(ALERR_INVALIDOUTPUTSM as u16);
+    pub const INVALID_INPUT_SM: Self = Self(ALERR_INVALIDINPUTSM as u16);
+    pub const INVALID_WDT_CONFIG: Self = Self(ALERR_INVALIDWDTCFG as u16);
+    pub const NEEDS_COLD_START: Self = Self(ALERR_SLAVENEEDSCOLDSTART as u16);
+    pub const NEEDS_INIT: Self = Self(ALERR_SLAVENEEDSINIT as u16);
+    pub const NEEDS_PREOP: Self = Self(ALERR_SLAVENEEDSPREOP as u16);
+    pub const NEEDS_SAFEOP: Self = Self(ALERR_SLAVENEEDSSAFEOP as u16);
+    pub const INVALID_INPUT_MAPPING: Self = Self(ALERR_INVALIDINPUTMAPPING as u16);
+    pub const INVALID_OUTPUT_MAPPING: Self = Self(ALERR_INVALIDOUTPUTMAPPING as u16);
+    pub const INCONSISTENT_SETTINGS: Self = Self(ALERR_INCONSISTENTSETTINGS as u16);
+    pub const FREERUN_NOT_SUPPORTED: Self = Self(ALERR_FREERUNNOTSUPPORTED as u16);
+    pub const SYNC_NOT_SUPPORTED: Self = Self(ALERR_SYNCNOTSUPPORTED as u16);
+    pub const FREERUN_NEEDS_3BUFFER_MODE: Self = Self(ALERR_FREERUNNEEDS3BUFFMODE as u16);
+    pub const BACKGROUND_WATCHDOG: Self = Self(ALERR_BACKGROUNDWATCHDOG as u16);
+    pub const NO_VALID_INPUTS_OUTPUTS: Self = Self(ALERR_NOVALIDINPUTSOUTPUTS as u16);
+    pub const FATAL_SYNC_ERROR: Self = Self(ALERR_FATALSYNCERROR as u16);
+    pub const NO_SYNC_ERROR: Self = Self(ALERR_NOSYNCERROR as u16);
+    pub const INVALID_INPUT_FMMU_CONFIG: Self = Self(ALERR_INVALIDINPUTFMMUCFG as u16);
+    pub const DC_INVALID_SYNC_CONFIG: Self = Self(ALERR_DCINVALIDSYNCCFG as u16);
+    pub const INVALID_DC_LATCH_CONFIG: Self = Self(ALERR_INVALIDDCLATCHCFG as u16);
+    pub const PLL_ERROR: Self = Self(ALERR_PLLERROR as u16);
+    pub const DC_SYNC_IO_ERROR: Self = Self(ALERR_DCSYNCIOERROR as u16);
+    pub const DC_SYNC_TIMEOUT: Self = Self(ALERR_DCSYNCTIMEOUT as u16);
+    pub const DC_SYNC_CYCLE_TIME: Self = Self(ALERR_DCSYNCCYCLETIME as u16);
+    pub const DC_SYNC0_CYCLE_TIME: Self = Self(ALERR_DCSYNC0CYCLETIME as u16);
+    pub const DC_SYNC1_CYCLE_TIME: Self = Self(ALERR_DCSYNC1CYCLETIME as u16);
+    pub const MBX_AOE: Self = Self(ALERR_MBXAOE as u16);
+    pub const MBX_EOE: Self = Self(ALERR_MBXEOE as u16);
+    pub const MBX_COE: Self = Self(ALERR_MBXCOE as u16);
+    pub const MBX_FOE: Self = Self(ALERR_MBXFOE as u16);
+    pub const MBX_SOE: Self = Self(ALERR_MBXSOE as u16);
+    pub const MBX_VOE: Self = Self(ALERR_MBXVOE as u16);
+    pub const EEPROM_NO_ACCESS: Self = Self(ALERR_EEPROMNOACCESS as u16);
+    pub const EEPROM_ERROR: Self = Self(ALERR_EEPROMERROR as u16);
+    pub const RESTARTED_LOCALLY: Self = Self(ALERR_SLAVERESTARTEDLOCALLY as u16);
+    pub const DEVICE_ID_UPDATED: Self = Self(ALERR_DEVICEIDVALUEUPDATED as u16);
+    pub const APPLICATION_CONTROLLER_AVAILABLE: Self = Self(ALERR_APPLCTRLAVAILABLE as u16);
+
+    pub const fn is_error(self) -> bool {
+        self.0 != ALERR_NONE as u16
+    }
+}
+
+const ERROR: u8 = ESCerror as u8;
+const INIT: u8 = ESCinit as u8;
+const PREOP: u8 = ESCpreop as u8;
+const SAFEOP: u8 = ESCsafeop as u8;
+const STATE_MASK: u8 = ESCREG_AL_STATEMASK as u8;
+const ERRACK_MASK: u8 = ESCREG_AL_ERRACKMASK as u8;
+
+/// Current AL state
+pub fn state() -> Option<AlState> {
+    AlState::from_bits(unsafe { ESCvar.ALstatus } as u8)
+}
+
+/// True when the AL status error indication is set
+pub fn error_flag() -> bool {
+    (unsafe { ESCvar.ALstatus } as u8) & ERROR != 0
+}
+
+/// Last AL status code written
+pub fn status_code() -> AlStatusCode {
+    AlStatusCode(unsafe { ESCvar.ALerror })
+}
+
+/// Write the AL status code register (0x134)
+pub(crate) fn write_status_code(code: AlStatusCode) {
+    unsafe { ESCvar.ALerror = code.0 };
+    esc::write_u16(ESCREG_ALERROR as u16, code.0);
+}
+
+/// Write the AL status register (0x130): state plus error indication
+pub(crate) fn write_status(status: u8) {
+    unsafe { ESCvar.ALstatus = status as u16 };
+    esc::write_u16(ESCREG_ALSTATUS as u16, status as u16);
+}
+
+/// Drop to `state` with the error indication set and `code`, stopping the
+/// outputs. Requests for OP are ignored.
+pub fn goto_error(state: AlState, code: AlStatusCode) {
+    goto_error_status(state.bits() | ERROR, code);
+}
+
+fn goto_error_status(status: u8, code: AlStatusCode) {
+    if status & ESCop as u8 != 0 {
+        // Erroneous input, ignore
+        return;
+    }
+    let mut an = unsafe { ESCvar.ALstatus } as u8 & ERRACK_MASK;
+    // New state in the high nibble, old one in the low nibble
+    let mut transition = ((status & ERRACK_MASK) << 4) | (an & 0x0f);
+    state_change_hook(
+        unsafe { ESCvar.pre_state_change_hook },
+        &mut transition,
+        &mut an,
+    );
+
+    if app_state() & APPSTATE_OUTPUT as u8 != 0 {
+        stop_output();
+    }
+    write_status_code(code);
+    write_status(status);
+
+    an = status;
+    state_change_hook(
+        unsafe { ESCvar.post_state_change_hook },
+        &mut transition,
+        &mut an,
+    );
+}
+
+fn state_change_hook(
+    hook: Option<unsafe extern "C" fn(as_: *mut u8, an: *mut u8)>,
+    transition: &mut u8,
+    an: &mut u8,
+) {
+    if let Some(hook) = hook {
+        unsafe { hook(transition, an) };
+    }
+}
+
+fn app_state() -> u8 {
+    unsafe { ESCvar.App.state }
+}
+
+fn set_app_state(state: u32) {
+    unsafe { ESCvar.App.state = state as u8 };
+}
+
+/// Check the SM0/SM1 mailbox configuration written by the master.
+///
+/// Returns `state`, or INIT + error (mailboxes disabled) on mismatch.
+fn check_mbx(state: u8) -> u8 {
+    esc::sm_read(0);
+    esc::sm_read(1);
+    let (mb0, mb1) = unsafe { (*ESCvar.activemb0, *ESCvar.activemb1) };
+
+    for (n, mb, result) in [(0, mb0, SMRESULT_ERRSM0), (1, mb1, SMRESULT_ERRSM1)] {
+        let (psa, length, command) = esc::sm_config(n);
+        if psa != mb.cfg_sma
+            || length != mb.cfg_sml
+            || command != mb.cfg_smc
+            || esc::sm(n).ECsm() == 0
+        {
+            unsafe { ESCvar.SMtestresult = result as u8 };
+            esc::sm_disable(0);
+            esc::sm_disable(1);
+            return INIT | ERROR;
+        }
+    }
+    state
+}
+
+/// Check the SM2/SM3 process data configuration written by the master
+/// against the mapped PDO sizes.
+///
+/// Returns `state`, or PREOP + error on mismatch.
+fn check_sm23(state: u8) -> u8 {
+    esc::sm_read(2);
+    let (psa, length, command) = esc::sm_config(2);
+    if psa != SM2_sma as u16
+        || length != unsafe { ESCvar.ESC_SM2_sml }
+        || command != SM2_smc as u8
+        || esc::sm_activation(2) & SM2_act as u8 == 0
+    {
+        unsafe { ESCvar.SMtestresult = SMRESULT_ERRSM2 as u8 };
+        return PREOP | ERROR;
+    }
+    if SM2_sma + length as u32 * 3 > SM3_sma {
+        // SM2 (3 buffers) overlaps SM3
+        unsafe { ESCvar.SMtestresult = SMRESULT_ERRSM2 as u8 };
+        return PREOP | ERROR;
+    }
+
+    esc::sm_read(3);
+    let (psa, length, command) = esc::sm_config(3);
+    if psa != SM3_sma as u16
+        || length != unsafe { ESCvar.ESC_SM3_sml }
+        || command != SM3_smc as u8
+        || esc::sm_activation(3) & SM3_act as u8 == 0
+    {
+        unsafe { ESCvar.SMtestresult = SMRESULT_ERRSM3 as u8 };
+        return PREOP | ERROR;
+    }
+    state
+}
+
+/// Input/output SM error code after a failed SM2/SM3 check
+fn sm23_status_code() -> AlStatusCode {
+    if unsafe { ESCvar.SMtestresult } & SMRESULT_ERRSM3 as u8 != 0 {
+        AlStatusCode::INVALID_INPUT_SM
+    } else {
+        AlStatusCode::INVALID_OUTPUT_SM
+    }
+}
+
+/// Start the mailboxes (standard or bootstrap configuration)
+fn start_mbx(state: u8, boot: bool) -> u8 {
+    unsafe {
+        if boot {
+            ESCvar.activembxsize = MBXSIZEBOOT as usize;
+            ESCvar.activemb0 = addr_of_mut!(ESCvar.mbboot[0]);
+            ESCvar.activemb1 = addr_of_mut!(ESCvar.mbboot[1]);
+        } else {
+            ESCvar.activembxsize = MBXSIZE as usize;
+            ESCvar.activemb0 = addr_of_mut!(ESCvar.mb[0]);
+            ESCvar.activemb1 = addr_of_mut!(ESCvar.mb[1]);
+        }
+    }
+
+    esc::sm_enable(0);
+    esc::sm_enable(1);
+    esc::sm_status(0);
+    esc::sm_status(1);
+
+    let state = check_mbx(state);
+    if state & ERROR != 0 {
+        write_status_code(if boot {
+            AlStatusCode::INVALID_BOOT_MBX_CONFIG
+        } else {
+            AlStatusCode::INVALID_MBX_CONFIG
+        });
+        unsafe { ESCvar.MBXrun = 0 };
+    } else {
+        mbx::start();
+        unsafe { ESCvar.MBXrun = 1 };
+    }
+    state
+}
+
+/// Stop the mailboxes and reset the mailbox state
+pub(crate) fn stop_mbx() {
+    unsafe { ESCvar.MBXrun = 0 };
+    esc::sm_disable(0);
+    esc::sm_disable(1);
+    mbx::reset();
+}
+
+/// Start the inputs after checking SM2/SM3 and, in interrupt mode, the DC
+/// setup. Returns `state`, or PREOP + error.
+fn start_input(state: u8) -> u8 {
+    let mut state = check_sm23(state);
+
+    if state != PREOP | ERROR {
+        esc::sm_enable(3);
+        set_app_state(APPSTATE_INPUT);
+    } else {
+        esc::sm_disable(2);
+        esc::sm_disable(3);
+        write_status_code(sm23_status_code());
+    }
+
+    // Polling mode stops here
+    if unsafe { ESCvar.use_interrupt } == 0 || state == PREOP | ERROR {
+        return state;
+    }
+
+    let dc_check = esc::check_dc();
+    if dc_check.is_error() {
+        write_status_code(dc_check);
+        state = PREOP | ERROR;
+        esc::sm_disable(2);
+        esc::sm_disable(3);
+        set_app_state(APPSTATE_IDLE);
+    } else if let Some(enable) = unsafe { ESCvar.esc_hw_interrupt_enable } {
+        let mask = if unsafe { ESCvar.dcsync } > 0 {
+            ESCREG_ALEVENT_DC_SYNC0 | ESCREG_ALEVENT_SM2
+        } else {
+            ESCREG_ALEVENT_SM2
+        };
+        unsafe { enable(mask) };
+    }
+
+    state
+}
+
+/// Stop the inputs (SM2 and SM3 disabled) and go back to free run
+pub(crate) fn stop_input() {
+    set_app_state(APPSTATE_IDLE);
+    esc::sm_disable(3);
+    esc::sm_disable(2);
+    dc::reset_sync_mode();
+
+    if unsafe { ESCvar.use_interrupt } != 0 {
+        if let Some(disable) = unsafe { ESCvar.esc_hw_interrupt_disable } {
+            unsafe { disable(ESCREG_ALEVENT_DC_SYNC0 | ESCREG_ALEVENT_SM2) };
+        }
+    }
+}
+
+/// Start the outputs (SM2 enabled)
+fn start_output(state: u8) -> u8 {
+    esc::sm_enable(2);
+    unsafe { ESCvar.App.state |= APPSTATE_OUTPUT as u8 };
+    state
+}
+
+/// Stop the outputs (SM2 disabled) and let the application set safe outputs
+pub(crate) fn stop_output() {
+    unsafe { ESCvar.App.state &= APPSTATE_INPUT as u8 };
+    esc::sm_disable(2);
+    APP_safeoutput();
+}
+
+/// PDO sizes of SM2/SM3 from the 0x1C12/0x1C13 assignments, false (with the
+/// AL status code set) on an invalid mapping or one larger than the process
+/// data buffer
+fn size_pdos() -> bool {
+    // SAFETY: the mappings are only used from the slave stack calls
+    let (sm2, sm3) = unsafe { (&mut *addr_of_mut!(SMmap2), &mut *addr_of_mut!(SMmap3)) };
+
+    let rx = pdo::size_of_pdo(RX_PDO_OBJIDX as u16, sm2);
+    let Some((size, mappings)) = rx.filter(|(size, _)| u32::from(*size) <= MAX_RXPDO_SIZE) else {
+        unsafe { ESCvar.sm2mappings = -1 };
+        write_status_code(AlStatusCode::INVALID_OUTPUT_SM);
+        return false;
+    };
+    unsafe {
+        ESCvar.ESC_SM2_sml = size;
+        ESCvar.sm2mappings = mappings as i32;
+    }
+
+    let tx = pdo::size_of_pdo(TX_PDO_OBJIDX as u16, sm3);
+    let Some((size, mappings)) = tx.filter(|(size, _)| u32::from(*size) <= MAX_TXPDO_SIZE) else {
+        unsafe { ESCvar.sm3mappings = -1 };
+        write_status_code(AlStatusCode::INVALID_INPUT_SM);
+        return false;
+    };
+    unsafe {
+        ESCvar.ESC_SM3_sml = size;
+        ESCvar.sm3mappings = mappings as i32;
+    }
+    true
+}
+
+/// Requested (AL control) and current (AL status) state bits, with the
+/// error indication acknowledged when requested or when going to INIT
+fn requested_and_current() -> (u8, u8, u8) {
+    let mut ac = unsafe { ESCvar.ALcontrol } as u8 & STATE_MASK;
+    let current = unsafe { ESCvar.ALstatus } as u8 & STATE_MASK;
+    let mut an = current;
+    if ac & ERROR != 0 || ac == INIT {
+        ac &= ERRACK_MASK;
+        an &= ERRACK_MASK;
+    }
+    (ac, current, an)
+}
+
+/// Handle an AL control request (AL event bit 0): run the requested state
+/// transition and write AL status / AL status code
+pub(crate) fn state_event() {
+    unsafe {
+        if ESCvar.ALevent & ESCREG_ALEVENT_CONTROL as u16 == 0 {
+            return;
+        }
+        ESCvar.ALcontrol = esc::read_u16(ESCREG_ALCONTROL as u16);
+    }
+
+    let (ac, current, mut an) = requested_and_current();
+
+    // Error not acknowledged, keep the current state
+    if an & ERROR != 0 && ac & ERROR == 0 {
+        return;
+    }
+
+    // Requested state in the high nibble, current one in the low nibble
+    let mut transition = (ac << 4) | (current & 0x0f);
+    state_change_hook(
+        unsafe { ESCvar.pre_state_change_hook },
+        &mut transition,
+        &mut an,
+    );
+
+    use AlState::*;
+    let from = AlState::from_bits(transition & 0x0f);
+    let to = AlState::from_bits(transition >> 4);
+    match (from, to) {
+        (Some(Init), Some(Init)) | (Some(PreOp), Some(PreOp)) | (Some(Op), Some(Op)) => {}
+        (Some(Init), Some(PreOp)) => {
+            esc::read_address();
+            an = start_mbx(ac, false);
+        }
+        (Some(Init | Boot), Some(Boot)) => {
+            esc::read_address();
+            an = start_mbx(ac, true);
+        }
+        (Some(Init), Some(SafeOp | Op)) => {
+            an = INIT | ERROR;
+            write_status_code(AlStatusCode::INVALID_STATE_CHANGE);
+        }
+        (Some(Op), Some(Init)) => {
+            stop_output();
+            stop_input();
+            stop_mbx();
+            an = INIT;
+        }
+        (Some(SafeOp), Some(Init)) => {
+            stop_input();
+            stop_mbx();
+            an = INIT;
+        }
+        (Some(PreOp), Some(Init)) => {
+            stop_mbx();
+            an = INIT;
+        }
+        (Some(Boot), Some(Init)) => {
+            stop_mbx();
+            foe::boot_exit();
+            an = INIT;
+        }
+        (Some(PreOp), Some(Boot | Op)) | (Some(Boot), Some(PreOp | SafeOp | Op)) => {
+            an = PREOP | ERROR;
+            write_status_code(AlStatusCode::INVALID_STATE_CHANGE);
+        }
+        (Some(PreOp | SafeOp), Some(SafeOp)) => {
+            if !size_pdos() {
+                an = PREOP | ERROR;
+            } else {
+                an = start_input(ac);
+                if an == ac {
+                    esc::sm_enable(2);
+                }
+            }
+        }
+        (Some(Op), Some(PreOp)) => {
+            stop_output();
+            stop_input();
+            an = PREOP;
+        }
+        (Some(SafeOp), Some(PreOp)) => {
+            stop_input();
+            an = PREOP;
+        }
+        (Some(SafeOp), Some(Boot)) => {
+            an = SAFEOP | ERROR;
+            write_status_code(AlStatusCode::INVALID_STATE_CHANGE);
+        }
+        (Some(SafeOp), Some(Op)) => {
+            an = start_output(ac);
+        }
+        (Some(Op), Some(Boot)) => {
+            an = SAFEOP | ERROR;
+            write_status_code(AlStatusCode::INVALID_STATE_CHANGE);
+            stop_output();
+        }
+        (Some(Op), Some(SafeOp)) => {
+            an = SAFEOP;
+            stop_output();
+        }
+        _ => {
+            if an == Op.bits() {
+                stop_output();
+                an = SAFEOP;
+            }
+            // Same check as the C stack: only a request of state 0 from
+            // SAFEOP gets here with this value
+            if transition == SAFEOP {
+                stop_input();
+            }
+            an |= ERROR;
+            write_status_code(AlStatusCode::UNKNOWN_STATE);
+        }
+    }
+
+    state_change_hook(
+        unsafe { ESCvar.post_state_change_hook },
+        &mut transition,
+        &mut an,
+    );
+
+    if an & ERROR == 0 && status_code().is_error() {
+        write_status_code(AlStatusCode::NONE);
+    }
+
+    write_status(an);
+    defmt::debug!("AL state 0x{:02x}", an);
+}
+
+/// Handle a sync manager (de)activation by the master (AL event bit 4):
+/// drop to INIT or PREOP when the mailbox or process data SMs no longer
+/// match, otherwise acknowledge the SM events
+pub(crate) fn sm_act_event() {
+    if unsafe { ESCvar.ALevent } & ESCREG_ALEVENT_SMCHANGE as u16 == 0 {
+        return;
+    }
+
+    let (ac, current, an) = requested_and_current();
+    let status = current;
+
+    // SM changes only matter with mailboxes running, past INIT and not in BOOT
+    if status & ESCREG_AL_ALLBUTINITMASK as u8 != 0
+        && status != ESCboot as u8
+        && unsafe { ESCvar.MBXrun } != 0
+    {
+        // Reading the activation registers acknowledges the event
+        let mbx = check_mbx(status);
+        let sm23 = check_sm23(status);
+
+        if an & ERROR != 0 && ac & ERROR == 0 {
+            // Stay in the error state
+        } else if mbx == INIT | ERROR {
+            if app_state() != 0 {
+                stop_output();
+                stop_input();
+            }
+            stop_mbx();
+            write_status_code(AlStatusCode::INVALID_MBX_CONFIG);
+            write_status(mbx);
+        } else if app_state() != 0 && sm23 == PREOP | ERROR {
+            stop_output();
+            stop_input();
+            write_status_code(sm23_status_code());
+            write_status(sm23);
+        }
+    } else {
+        for n in 0..8 {
+            esc::sm_ack(n);
+        }
+    }
+}
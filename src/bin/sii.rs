@@ -0,0 +1,86 @@
+//! SII EEPROM image and ESI file of the LAN9252 demo, built from the object
+//! dictionary and settings generated from `esi.json` (`SOES_ESI`):
+//!
+//! ```text
+//! cargo run --features std --bin sii -- src/soes-c/soes-esi
+//! ```
+//!
+//! writes `eeprom.bin`, `eeprom.hex` and `LAN9252_SPI_demo.xml` to the
+//! given directory (default: the current one). `--check <eeprom.bin>` lists
+//! the differences between an image and the demo instead.
+
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+use SOES_rs::esi;
+use SOES_rs::sii;
+
+#[allow(dead_code)]
+mod objectlist {
+    include!(concat!(env!("OUT_DIR"), "/objectlist.rs"));
+}
+
+include!(concat!(env!("OUT_DIR"), "/sii_config.rs"));
+
+// The stack logs through defmt, discard it on the host
+#[defmt::global_logger]
+struct NullLogger;
+
+unsafe impl defmt::Logger for NullLogger {
+    fn acquire() {}
+    unsafe fn flush() {}
+    unsafe fn release() {}
+    unsafe fn write(_bytes: &[u8]) {}
+}
+
+defmt::timestamp!("{=u8}", 0);
+
+fn check(file: &str) {
+    let data = fs::read(file).unwrap_or_else(|err| {
+        eprintln!("{file}: {err}");
+        process::exit(1);
+    });
+    let image = sii::parse(&data).unwrap_or_else(|err| {
+        eprintln!("{file}: invalid SII image: {err:?}");
+        process::exit(1);
+    });
+    let mismatches = image.check();
+    for mismatch in &mismatches {
+        println!("{mismatch}");
+    }
+    if !mismatches.is_empty() {
+        process::exit(1);
+    }
+    println!("{file}: matches the firmware");
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let [flag, file] = args.as_slice() {
+        if flag == "--check" {
+            return check(file);
+        }
+    }
+    let dir = PathBuf::from(args.first().map_or(".", String::as_str));
+    let config = sii_config();
+
+    let (image, xml) = match sii::image(&config).and_then(|image| Ok((image, esi::xml(&config)?))) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("SII image failed: {err:?}");
+            process::exit(1);
+        }
+    };
+    for (file, data) in [
+        ("eeprom.bin", image.clone()),
+        ("eeprom.hex", sii::intel_hex(&image).into_bytes()),
+        ("LAN9252_SPI_demo.xml", xml.into_bytes()),
+    ] {
+        let path = dir.join(file);
+        if let Err(err) = fs::write(&path, data) {
+            eprintln!("{}: {err}", path.display());
+            process::exit(1);
+        }
+        println!("{}", path.display());
+    }
+}
@@ -0,0 +1,120 @@
+//! Application hooks on SDO transfers
+
+use core::ptr::addr_of_mut;
+
+use super::SdoAbort;
+use crate::od::{DataType, Entry};
+
+/// Object accessed by an SDO transfer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct SdoAccess {
+    pub index: u16,
+    pub subindex: u8,
+    /// Complete access, from `subindex` (0 or 1) to the last entry
+    pub complete_access: bool,
+}
+
+/// Value of an SDO download, decoded from the entry data type
+#[derive(Clone, Copy, Debug, PartialEq, defmt::Format)]
+pub enum SdoValue<'a> {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    /// Also bit types (`Bit1`..`Bit8`)
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    /// Strings, bit arrays and complete access data, packed as in the SDO
+    /// frames
+    Bytes(&'a [u8]),
+}
+
+impl<'a> SdoValue<'a> {
+    /// Decode `data`, the little endian value of `entry`
+    pub fn new(entry: &Entry, data: &'a [u8]) -> Self {
+        fn bytes<const N: usize>(data: &[u8]) -> [u8; N] {
+            let mut bytes = [0; N];
+            let len = data.len().min(N);
+            bytes[..len].copy_from_slice(&data[..len]);
+            bytes
+        }
+
+        const BOOLEAN: u16 = DataType::Boolean as u16;
+        const INTEGER8: u16 = DataType::Integer8 as u16;
+        const INTEGER16: u16 = DataType::Integer16 as u16;
+        const INTEGER32: u16 = DataType::Integer32 as u16;
+        const INTEGER64: u16 = DataType::Integer64 as u16;
+        const UNSIGNED8: u16 = DataType::Unsigned8 as u16;
+        const UNSIGNED16: u16 = DataType::Unsigned16 as u16;
+        const UNSIGNED32: u16 = DataType::Unsigned32 as u16;
+        const UNSIGNED64: u16 = DataType::Unsigned64 as u16;
+        const REAL32: u16 = DataType::Real32 as u16;
+        const REAL64: u16 = DataType::Real64 as u16;
+        const BIT1: u16 = DataType::Bit1 as u16;
+        const BIT8: u16 = DataType::Bit8 as u16;
+
+        match entry.datatype() {
+            BOOLEAN => Self::Bool(bytes::<1>(data)[0] & 1 != 0),
+            INTEGER8 => Self::I8(i8::from_le_bytes(bytes(data))),
+            INTEGER16 => Self::I16(i16::from_le_bytes(bytes(data))),
+            INTEGER32 => Self::I32(i32::from_le_bytes(bytes(data))),
+            INTEGER64 => Self::I64(i64::from_le_bytes(bytes(data))),
+            UNSIGNED8 | BIT1..=BIT8 => Self::U8(bytes::<1>(data)[0]),
+            UNSIGNED16 => Self::U16(u16::from_le_bytes(bytes(data))),
+            UNSIGNED32 => Self::U32(u32::from_le_bytes(bytes(data))),
+            UNSIGNED64 => Self::U64(u64::from_le_bytes(bytes(data))),
+            REAL32 => Self::F32(f32::from_le_bytes(bytes(data))),
+            REAL64 => Self::F64(f64::from_le_bytes(bytes(data))),
+            _ => Self::Bytes(data),
+        }
+    }
+}
+
+/// Application hooks called by the CoE server around SDO transfers, see
+/// `EcatSlave::set_object_handler`. Returning an error aborts the transfer
+/// with that code.
+pub trait ObjectHandler {
+    /// Download received in full, before it is written to the dictionary
+    fn download_pre(&mut self, _access: &SdoAccess, _value: SdoValue) -> Result<(), SdoAbort> {
+        Ok(())
+    }
+
+    /// Download written to the dictionary
+    fn download_post(&mut self, _access: &SdoAccess) -> Result<(), SdoAbort> {
+        Ok(())
+    }
+
+    /// Upload requested, before the value is read, e.g. to refresh it
+    fn upload_pre(&mut self, _access: &SdoAccess) -> Result<(), SdoAbort> {
+        Ok(())
+    }
+
+    /// Upload data read, on the last segment of a segmented upload
+    fn upload_post(&mut self, _access: &SdoAccess) -> Result<(), SdoAbort> {
+        Ok(())
+    }
+}
+
+static mut HANDLER: Option<&'static mut dyn ObjectHandler> = None;
+
+/// Register the application object handler
+pub(crate) fn set(handler: &'static mut dyn ObjectHandler) {
+    // SAFETY: set before the mailboxes run
+    unsafe { *addr_of_mut!(HANDLER) = Some(handler) };
+}
+
+/// Run `f` on the registered handler, `Ok` without one
+pub(super) fn call(
+    f: impl FnOnce(&mut dyn ObjectHandler) -> Result<(), SdoAbort>,
+) -> Result<(), SdoAbort> {
+    // SAFETY: only used from the mailbox service
+    match unsafe { &mut *addr_of_mut!(HANDLER) } {
+        Some(handler) => f(&mut **handler),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,276 @@
+//! SDO Information service: OD lists, object and entry descriptions
+
+use core::ffi::CStr;
+
+use super::*;
+use crate::od::{self, Access, Object};
+
+const GET_OD_LIST_REQUEST: u8 = 0x01;
+const GET_OD_LIST_RESPONSE: u8 = COE_GETODLISTRESPONSE as u8;
+const GET_OD_REQUEST: u8 = 0x03;
+const GET_OD_RESPONSE: u8 = COE_GETODRESPONSE as u8;
+const GET_ED_REQUEST: u8 = 0x05;
+const GET_ED_RESPONSE: u8 = COE_ENTRYDESCRIPTIONRESPONSE as u8;
+const INFO_ERROR: u8 = COE_INFOERROR as u8;
+/// More fragments follow
+const INCOMPLETE: u8 = 0x80;
+
+/// Opcode, reserved, fragments left
+const INFO_HEADER_SIZE: usize = 4;
+
+/// Number of objects in each list
+const LIST_LENGTHS: u16 = 0;
+const LIST_ALL: u16 = 1;
+const LIST_RXPDO: u16 = 2;
+const LIST_TXPDO: u16 = 3;
+const LIST_BACKUP: u16 = 4;
+const LIST_SETTINGS: u16 = 5;
+
+const VALUEINFO_UNIT: u8 = COE_VALUEINFO_TYPE as u8;
+const VALUEINFO_DEFAULT: u8 = COE_VALUEINFO_DEFAULT as u8;
+const VALUEINFO_MINIMUM: u8 = COE_VALUEINFO_MINIMUM as u8;
+const VALUEINFO_MAXIMUM: u8 = COE_VALUEINFO_MAXIMUM as u8;
+
+/// OD list response in progress
+#[derive(Clone, Copy)]
+pub(super) struct OdList {
+    list: u16,
+    // Position in the dictionary of the next object to check
+    next: usize,
+    // Objects still to send
+    left: usize,
+    fragments_left: u16,
+    first: bool,
+}
+
+fn in_list(object: &Object, list: u16) -> bool {
+    let flag = match list {
+        LIST_ALL => return true,
+        LIST_RXPDO => Access(ATYPE_RXPDO as u16),
+        LIST_TXPDO => Access(ATYPE_TXPDO as u16),
+        LIST_BACKUP => Access::BACKUP,
+        LIST_SETTINGS => Access::SETTINGS,
+        _ => return false,
+    };
+    object
+        .entries()
+        .iter()
+        .any(|entry| entry.access().contains(flag))
+}
+
+fn list_length(list: u16) -> usize {
+    od::objects()
+        .iter()
+        .filter(|object| in_list(object, list))
+        .count()
+}
+
+/// Queue an SDO Information response, `fill` writes the data after the
+/// info header and returns its length
+fn send_info(
+    out: &mut MbxOut,
+    opcode: u8,
+    fragments_left: u16,
+    fill: impl FnOnce(&mut [u8]) -> usize,
+) {
+    send(out, SERVICE_SDO_INFO, |data| {
+        data[0] = opcode;
+        data[1] = 0;
+        data[2..4].copy_from_slice(&fragments_left.to_le_bytes());
+        INFO_HEADER_SIZE + fill(&mut data[INFO_HEADER_SIZE..])
+    });
+}
+
+/// Queue an SDO Information error
+fn info_error(out: &mut MbxOut, abort: SdoAbort) {
+    defmt::debug!("SDO Info error {}", abort);
+    send_info(out, INFO_ERROR, 0, |data| {
+        data[..4].copy_from_slice(&abort.code().to_le_bytes());
+        4
+    });
+}
+
+/// Copy `name` to `buf`, truncated to fit
+fn put_name(buf: &mut [u8], name: &CStr) -> usize {
+    let name = name.to_bytes();
+    let len = name.len().min(buf.len());
+    buf[..len].copy_from_slice(&name[..len]);
+    len
+}
+
+impl Coe {
+    /// SDO Information request
+    pub(super) fn info_request(
+        &mut self,
+        request: &[u8],
+        out: &mut MbxOut,
+    ) -> Result<(), MbxError> {
+        let payload = &request[2 + INFO_HEADER_SIZE..];
+        match request[2] & !INCOMPLETE {
+            GET_OD_LIST_REQUEST if payload.len() >= 2 => {
+                self.od_list_request(u16::from_le_bytes([payload[0], payload[1]]), out)
+            }
+            GET_OD_REQUEST if payload.len() >= 2 => {
+                od_description(u16::from_le_bytes([payload[0], payload[1]]), out)
+            }
+            GET_ED_REQUEST if payload.len() >= 4 => {
+                let index = u16::from_le_bytes([payload[0], payload[1]]);
+                entry_description(index, payload[2], payload[3], out)
+            }
+            GET_OD_LIST_REQUEST | GET_OD_REQUEST | GET_ED_REQUEST => {
+                return Err(MbxError::SIZE_TOO_SHORT)
+            }
+            _ => info_error(out, SdoAbort::UnknownCommand),
+        }
+        Ok(())
+    }
+
+    fn od_list_request(&mut self, list: u16, out: &mut MbxOut) {
+        if list == LIST_LENGTHS {
+            send_info(out, GET_OD_LIST_RESPONSE, 0, |data| {
+                data[..2].copy_from_slice(&LIST_LENGTHS.to_le_bytes());
+                for list in LIST_ALL..=LIST_SETTINGS {
+                    let n = 2 * list as usize;
+                    data[n..n + 2].copy_from_slice(&(list_length(list) as u16).to_le_bytes());
+                }
+                12
+            });
+            return;
+        }
+        if list > LIST_SETTINGS {
+            info_error(out, SdoAbort::UnsupportedAccess);
+            return;
+        }
+
+        // The first fragment starts with the list type
+        let length = list_length(list);
+        let first = (out.data_size() - 2 - INFO_HEADER_SIZE - 2) / 2;
+        let next = (out.data_size() - 2 - INFO_HEADER_SIZE) / 2;
+        let fragments = length.saturating_sub(first).div_ceil(next);
+        self.od_list = Some(OdList {
+            list,
+            next: 0,
+            left: length,
+            fragments_left: fragments as u16,
+            first: true,
+        });
+        self.od_list_fragment(out);
+    }
+
+    /// Send the next fragment of the OD list
+    pub(super) fn od_list_fragment(&mut self, out: &mut MbxOut) {
+        let Some(mut list) = self.od_list else {
+            return;
+        };
+        let opcode = match list.fragments_left {
+            0 => GET_OD_LIST_RESPONSE,
+            _ => GET_OD_LIST_RESPONSE | INCOMPLETE,
+        };
+        let objects = od::objects();
+
+        send_info(out, opcode, list.fragments_left, |data| {
+            let mut n = 0;
+            if list.first {
+                data[..2].copy_from_slice(&list.list.to_le_bytes());
+                n = 2;
+            }
+            while list.left > 0 && n + 2 <= data.len() {
+                let object = &objects[list.next];
+                list.next += 1;
+                if in_list(object, list.list) {
+                    data[n..n + 2].copy_from_slice(&object.index().to_le_bytes());
+                    n += 2;
+                    list.left -= 1;
+                }
+            }
+            n
+        });
+
+        list.first = false;
+        self.od_list = match list.fragments_left {
+            0 => None,
+            left => Some(OdList {
+                fragments_left: left - 1,
+                ..list
+            }),
+        };
+    }
+}
+
+/// Object description: data type, max subindex, object code and name
+fn od_description(index: u16, out: &mut MbxOut) {
+    let Some(object) = od::find(index) else {
+        info_error(out, SdoAbort::NoObject);
+        return;
+    };
+    let entries = object.entries();
+    // Arrays report the type of their elements
+    let datatype = match object.object_type() as u32 {
+        OTYPE_VAR => entries[0].datatype(),
+        OTYPE_ARRAY => entries.get(1).unwrap_or(&entries[0]).datatype(),
+        _ => 0,
+    };
+
+    send_info(out, GET_OD_RESPONSE, 0, |data| {
+        data[..2].copy_from_slice(&index.to_le_bytes());
+        data[2..4].copy_from_slice(&datatype.to_le_bytes());
+        data[4] = object.max_subindex();
+        data[5] = object.object_type() as u8;
+        6 + put_name(&mut data[6..], object.name())
+    });
+}
+
+/// Entry description: data type, bit length, access, the requested value
+/// info the entry has (unit, default, minimum, maximum) and name
+fn entry_description(index: u16, subindex: u8, valueinfo: u8, out: &mut MbxOut) {
+    let Some(object) = od::find(index) else {
+        info_error(out, SdoAbort::NoObject);
+        return;
+    };
+    let Some(entry) = object.entry(subindex) else {
+        info_error(out, SdoAbort::NoSubindex);
+        return;
+    };
+
+    // Values are sent in the entry data type
+    let size = entry.byte_len();
+    let requested =
+        |flag: u8, value: Option<u64>| value.filter(|_| valueinfo & flag != 0 && size <= 8);
+    let default = requested(
+        VALUEINFO_DEFAULT,
+        (!entry.is_string()).then(|| entry.default_u64()),
+    );
+    let min = requested(VALUEINFO_MINIMUM, entry.minimum());
+    let max = requested(VALUEINFO_MAXIMUM, entry.maximum());
+    let unit = entry
+        .unit_type()
+        .filter(|_| valueinfo & VALUEINFO_UNIT != 0);
+    let included = [
+        (VALUEINFO_UNIT, unit.is_some()),
+        (VALUEINFO_DEFAULT, default.is_some()),
+        (VALUEINFO_MINIMUM, min.is_some()),
+        (VALUEINFO_MAXIMUM, max.is_some()),
+    ]
+    .into_iter()
+    .filter(|(_, included)| *included)
+    .fold(0, |bits, (flag, _)| bits | flag);
+
+    send_info(out, GET_ED_RESPONSE, 0, |data| {
+        data[..2].copy_from_slice(&index.to_le_bytes());
+        data[2] = subindex;
+        data[3] = included;
+        data[4..6].copy_from_slice(&entry.datatype().to_le_bytes());
+        data[6..8].copy_from_slice(&entry.bit_length().to_le_bytes());
+        data[8..10].copy_from_slice(&entry.access().0.to_le_bytes());
+        let mut n = 10;
+        if let Some(unit) = unit {
+            data[n..n + 4].copy_from_slice(&unit.to_le_bytes());
+            n += 4;
+        }
+        for value in [default, min, max].into_iter().flatten() {
+            data[n..n + size].copy_from_slice(&value.to_le_bytes()[..size]);
+            n += size;
+        }
+        n + put_name(&mut data[n..], entry.name())
+    });
+}
@@ -0,0 +1,237 @@
+//! CAN application protocol over EtherCAT (port of `esc_coe.c`): SDO server
+//! and SDO Information on the Rust object dictionary, PDO mapping of the
+//! process data.
+
+mod handler;
+mod info;
+pub mod pdo;
+mod sdo;
+
+use core::ptr::addr_of_mut;
+
+use crate::bindings::*;
+use crate::mbx::{MbxError, MbxHeader, MbxOut, MbxProtocol, MbxType};
+use crate::od::Entry;
+
+pub use handler::{ObjectHandler, SdoAccess, SdoValue};
+pub use pdo::PdoMapping;
+
+/// SDO abort codes (ETG.1000.6)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum SdoAbort {
+    /// Toggle bit not changed
+    Toggle = ABORT_NOTOGGLE,
+    Timeout = ABORT_TRANSFER_TIMEOUT,
+    /// Client/server command specifier not valid or unknown
+    UnknownCommand = ABORT_UNKNOWN,
+    InvalidBlockSize = ABORT_INVALID_BLOCK_SIZE,
+    InvalidSequenceNumber = ABORT_INVALID_SEQUENCE_NUMBER,
+    BlockCrc = ABORT_BLOCK_CRC_ERROR,
+    OutOfMemory = ABORT_OUT_OF_MEMORY,
+    UnsupportedAccess = ABORT_UNSUPPORTED,
+    WriteOnly = ABORT_WRITEONLY,
+    ReadOnly = ABORT_READONLY,
+    /// Subindex 0 must be 0 for a complete access write
+    Subindex0NotZero = ABORT_SUBINDEX0_NOT_ZERO,
+    CompleteAccessNotSupported = ABORT_CA_NOT_SUPPORTED,
+    ExceedsMailboxSize = ABORT_EXCEEDS_MBOX_SIZE,
+    DownloadBlocked = ABORT_SDO_DOWNLOAD_BLOCKED,
+    NoObject = ABORT_NOOBJECT,
+    MappingObject = ABORT_MAPPING_OBJECT_ERROR,
+    MappingLength = ABORT_MAPPING_LENGTH_ERROR,
+    GeneralParameter = ABORT_GENERAL_PARAMETER_ERROR,
+    GeneralDevice = ABORT_GENERAL_DEVICE_ERROR,
+    Hardware = ABORT_HARDWARE_ERROR,
+    TypeMismatch = ABORT_TYPEMISMATCH,
+    DataTypeTooHigh = ABORT_DATATYPE_TOO_HIGH,
+    DataTypeTooLow = ABORT_DATATYPE_TOO_LOW,
+    NoSubindex = ABORT_NOSUBINDEX,
+    ValueRange = ABORT_VALUE_EXCEEDED,
+    ValueTooHigh = ABORT_VALUE_TOO_HIGH,
+    ValueTooLow = ABORT_VALUE_TOO_LOW,
+    ModuleListMismatch = ABORT_MODULE_LIST_MISMATCH,
+    MaxLessThanMin = ABORT_MAX_VAL_LESS_THAN_MIN_VAL,
+    ResourceNotAvailable = ABORT_RESOURCE_NOT_AVAILABLE,
+    General = ABORT_GENERALERROR,
+    DataStore = ABORT_DATA_STORE_ERROR,
+    DataStoreLocal = ABORT_DATA_STORE_LOCAL_ERROR,
+    /// Not allowed in the current AL state
+    NotInThisState = ABORT_NOTINTHISSTATE,
+    ObjectDictionary = ABORT_OBJECT_DICTIONARY_ERROR,
+    NoDataAvailable = ABORT_NO_DATA_AVAILABLE,
+}
+
+impl SdoAbort {
+    const ALL: [Self; 36] = [
+        Self::Toggle,
+        Self::Timeout,
+        Self::UnknownCommand,
+        Self::InvalidBlockSize,
+        Self::InvalidSequenceNumber,
+        Self::BlockCrc,
+        Self::OutOfMemory,
+        Self::UnsupportedAccess,
+        Self::WriteOnly,
+        Self::ReadOnly,
+        Self::Subindex0NotZero,
+        Self::CompleteAccessNotSupported,
+        Self::ExceedsMailboxSize,
+        Self::DownloadBlocked,
+        Self::NoObject,
+        Self::MappingObject,
+        Self::MappingLength,
+        Self::GeneralParameter,
+        Self::GeneralDevice,
+        Self::Hardware,
+        Self::TypeMismatch,
+        Self::DataTypeTooHigh,
+        Self::DataTypeTooLow,
+        Self::NoSubindex,
+        Self::ValueRange,
+        Self::ValueTooHigh,
+        Self::ValueTooLow,
+        Self::ModuleListMismatch,
+        Self::MaxLessThanMin,
+        Self::ResourceNotAvailable,
+        Self::General,
+        Self::DataStore,
+        Self::DataStoreLocal,
+        Self::NotInThisState,
+        Self::ObjectDictionary,
+        Self::NoDataAvailable,
+    ];
+
+    /// Abort code sent to the master
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|abort| abort.code() == code)
+    }
+}
+
+/// CoE header service (bits 12..15)
+const SERVICE_SDO_REQUEST: u8 = COE_SDOREQUEST as u8;
+const SERVICE_SDO_RESPONSE: u8 = COE_SDORESPONSE as u8;
+const SERVICE_SDO_INFO: u8 = COE_SDOINFORMATION as u8;
+
+/// CoE header, SDO command, index, subindex and size (or expedited data)
+const SDO_HEADER_SIZE: usize = COE_HEADERSIZE as usize;
+/// CoE header and SDO segment command
+const SEGMENT_HEADER_SIZE: usize = COE_SEGMENTHEADERSIZE as usize;
+
+/// Download and complete access buffer, an entry or an object packed as in
+/// its SDO frames
+const CA_BUFFER_SIZE: usize = PREALLOC_BUFFER_SIZE as usize;
+
+/// Data of a transfer: one entry, or the complete access buffer
+#[derive(Clone, Copy)]
+enum Data {
+    Entry(&'static Entry),
+    CompleteAccess,
+}
+
+/// Segmented transfer in progress
+#[derive(Clone, Copy)]
+enum Transfer {
+    Idle,
+    Upload(Segmented),
+    Download(Segmented),
+}
+
+#[derive(Clone, Copy)]
+struct Segmented {
+    data: Data,
+    index: u16,
+    subindex: u8,
+    complete_access: bool,
+    size: usize,
+    offset: usize,
+    /// Toggle bit expected in the next segment request
+    toggle: u8,
+}
+
+/// Built-in CoE server
+pub(crate) struct Coe {
+    transfer: Transfer,
+    ca_buffer: [u8; CA_BUFFER_SIZE],
+    od_list: Option<info::OdList>,
+}
+
+static mut COE: Coe = Coe {
+    transfer: Transfer::Idle,
+    ca_buffer: [0; CA_BUFFER_SIZE],
+    od_list: None,
+};
+
+pub(crate) use handler::set as set_object_handler;
+
+pub(crate) fn handler() -> &'static mut Coe {
+    // SAFETY: only used from the mailbox service
+    unsafe { &mut *addr_of_mut!(COE) }
+}
+
+/// Queue a CoE response: `fill` writes the payload after the CoE header
+/// and returns its length
+fn send(out: &mut MbxOut, service: u8, fill: impl FnOnce(&mut [u8]) -> usize) {
+    let result = out.send(MbxType::COE, |data| {
+        data[..2].copy_from_slice(&((service as u16) << 12).to_le_bytes());
+        2 + fill(&mut data[2..])
+    });
+    if result.is_err() {
+        defmt::warn!("CoE response dropped, no mailbox buffer");
+    }
+}
+
+/// Queue an SDO abort
+fn abort(out: &mut MbxOut, index: u16, subindex: u8, abort: SdoAbort) {
+    defmt::debug!("SDO abort {:04x}:{:02x} {}", index, subindex, abort);
+    send(out, SERVICE_SDO_REQUEST, |data| {
+        data[0] = COE_COMMAND_SDOABORT as u8;
+        data[1..3].copy_from_slice(&index.to_le_bytes());
+        data[3] = subindex;
+        data[4..8].copy_from_slice(&abort.code().to_le_bytes());
+        8
+    });
+}
+
+impl MbxProtocol for Coe {
+    fn mbx_type(&self) -> MbxType {
+        MbxType::COE
+    }
+
+    fn receive(
+        &mut self,
+        _header: &MbxHeader,
+        data: &[u8],
+        out: &mut MbxOut,
+    ) -> Result<(), MbxError> {
+        if data.len() < COE_MINIMUM_LENGTH as usize {
+            return Err(MbxError::INVALID_SIZE);
+        }
+        match (u16::from_le_bytes([data[0], data[1]]) >> 12) as u8 {
+            SERVICE_SDO_REQUEST => self.sdo_request(data, out)?,
+            SERVICE_SDO_INFO => self.info_request(data, out)?,
+            0 => return Err(MbxError::INVALID_HEADER),
+            _ => return Err(MbxError::SERVICE_NOT_SUPPORTED),
+        }
+        Ok(())
+    }
+
+    fn poll(&mut self, out: &mut MbxOut) {
+        if !out.pending() {
+            self.od_list_fragment(out);
+        }
+    }
+
+    fn busy(&self) -> bool {
+        self.od_list.is_some()
+    }
+
+    fn reset(&mut self) {
+        self.transfer = Transfer::Idle;
+        self.od_list = None;
+    }
+}
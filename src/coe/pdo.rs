@@ -0,0 +1,136 @@
+//! PDO mapping: process data layout from the 0x1C12/0x1C13 assignments,
+//! packing of the mapped entries into the SM2/SM3 buffers
+
+use crate::od::{self, Entry};
+
+/// Mapped entry and its bit offset in the process data, `None` for padding
+#[derive(Clone, Copy)]
+pub struct PdoMapping {
+    pub entry: Option<&'static Entry>,
+    pub offset: u16,
+}
+
+impl PdoMapping {
+    pub const EMPTY: Self = Self {
+        entry: None,
+        offset: 0,
+    };
+}
+
+/// Size in bytes of the PDOs assigned in `assign` (0x1C12 or 0x1C13), the
+/// mapped entries listed in `mappings`. Returns the size and the number of
+/// mappings, `None` when a mapped entry does not exist or `mappings` is
+/// too small. Without `mappings` only the size is computed.
+pub fn size_of_pdo(assign: u16, mappings: &mut [PdoMapping]) -> Option<(u16, usize)> {
+    let Some(assignment) = od::find(assign) else {
+        return Some((0, 0));
+    };
+    let mut bits: usize = 0;
+    let mut count = 0;
+
+    let assigned = assignment.entries()[0].value_u64() as usize;
+    for pdo in assignment.entries().iter().skip(1).take(assigned) {
+        // Unknown PDOs are skipped, as in SOES
+        let Some(pdo) = od::find(pdo.value_u64() as u16) else {
+            continue;
+        };
+        let mapped = pdo.entries()[0].value_u64() as usize;
+        for mapping in pdo.entries().iter().skip(1).take(mapped) {
+            let value = mapping.value_u64() as u32;
+            let (index, subindex) = ((value >> 16) as u16, (value >> 8) as u8);
+
+            if !mappings.is_empty() {
+                if count == mappings.len() {
+                    defmt::warn!("PDO {:04x}: too many mapped entries", assign);
+                    return None;
+                }
+                let entry = if index == 0 && subindex == 0 {
+                    None
+                } else {
+                    let entry = od::find(index).and_then(|object| object.entry(subindex));
+                    if entry.is_none() {
+                        defmt::warn!("PDO entry {:04x}:{:02x} does not exist", index, subindex);
+                        return None;
+                    }
+                    entry
+                };
+                mappings[count] = PdoMapping {
+                    entry,
+                    offset: bits as u16,
+                };
+                count += 1;
+            }
+            bits += (value & 0xFF) as usize;
+        }
+    }
+
+    Some((bits.div_ceil(8) as u16, count))
+}
+
+/// Write the mapped entries to the TxPDO buffer
+pub fn pack(buf: &mut [u8], mappings: &[PdoMapping]) {
+    for mapping in mappings {
+        if let Some(entry) = mapping.entry {
+            pack_entry(buf, entry, mapping.offset as usize);
+        }
+    }
+}
+
+/// Update the mapped entries from the RxPDO buffer
+pub fn unpack(buf: &[u8], mappings: &[PdoMapping]) {
+    for mapping in mappings {
+        if let Some(entry) = mapping.entry {
+            unpack_entry(buf, entry, mapping.offset as usize);
+        }
+    }
+}
+
+/// Write `entry` at bit `offset` of `buf`, nothing written past its end
+pub(super) fn pack_entry(buf: &mut [u8], entry: &Entry, offset: usize) {
+    let bits = entry.bit_length() as usize;
+    let start = offset / 8;
+    if bits > 64 || (offset & 7 == 0 && bits & 7 == 0) {
+        if let Some(data) = buf.get_mut(start..start + entry.byte_len()) {
+            entry.read_at(0, data);
+        }
+    } else {
+        set_bits(buf, offset, bits, entry.value_u64());
+    }
+}
+
+/// Update `entry` from bit `offset` of `buf`, left alone when it does not
+/// fit in `buf`
+pub(super) fn unpack_entry(buf: &[u8], entry: &Entry, offset: usize) {
+    let bits = entry.bit_length() as usize;
+    let start = offset / 8;
+    if bits > 64 || (offset & 7 == 0 && bits & 7 == 0) {
+        if let Some(data) = buf.get(start..start + entry.byte_len()) {
+            entry.write_at(0, data);
+        }
+    } else if offset + bits <= buf.len() * 8 {
+        let value = get_bits(buf, offset, bits);
+        entry.write_at(0, &value.to_le_bytes()[..entry.byte_len()]);
+    }
+}
+
+fn set_bits(buf: &mut [u8], offset: usize, bits: usize, value: u64) {
+    for bit in 0..bits {
+        let (byte, shift) = ((offset + bit) / 8, (offset + bit) % 8);
+        let Some(byte) = buf.get_mut(byte) else {
+            return;
+        };
+        if value >> bit & 1 != 0 {
+            *byte |= 1 << shift;
+        } else {
+            *byte &= !(1 << shift);
+        }
+    }
+}
+
+pub(super) fn get_bits(buf: &[u8], offset: usize, bits: usize) -> u64 {
+    (0..bits).fold(0, |value, bit| {
+        let (byte, shift) = ((offset + bit) / 8, (offset + bit) % 8);
+        let set = buf.get(byte).map_or(0, |byte| (byte >> shift) & 1);
+        value | (set as u64) << bit
+    })
+}
@@ -0,0 +1,568 @@
+//! SDO upload and download: expedited, normal, segmented and complete
+//! access transfers
+
+use super::handler::{self, SdoAccess, SdoValue};
+use super::pdo::{get_bits, pack_entry, unpack_entry};
+use super::*;
+use crate::al::{self, AlState};
+use crate::od::{self, DataType, Object};
+use crate::storage::{self, RESTORE_DEFAULTS_INDEX, STORE_PARAMETERS_INDEX};
+
+const UPLOAD_REQUEST: u8 = COE_COMMAND_UPLOADREQUEST as u8;
+const UPLOAD_RESPONSE: u8 = COE_COMMAND_UPLOADRESPONSE as u8;
+const UPLOAD_SEGMENT_REQUEST: u8 = COE_COMMAND_UPLOADSEGREQ as u8;
+const UPLOAD_SEGMENT: u8 = COE_COMMAND_UPLOADSEGMENT as u8;
+const DOWNLOAD_REQUEST: u8 = COE_COMMAND_DOWNLOADREQUEST as u8;
+const DOWNLOAD_RESPONSE: u8 = COE_COMMAND_DOWNLOADRESPONSE as u8;
+const DOWNLOAD_SEGMENT_REQUEST: u8 = COE_COMMAND_DOWNLOADSEGREQ as u8;
+const DOWNLOAD_SEGMENT_RESPONSE: u8 = COE_COMMAND_DOWNLOADSEGRESP as u8;
+const SDO_ABORT: u8 = COE_COMMAND_SDOABORT as u8;
+
+const SIZE_INDICATOR: u8 = COE_SIZE_INDICATOR as u8;
+const EXPEDITED: u8 = COE_EXPEDITED_INDICATOR as u8;
+const COMPLETE_ACCESS: u8 = COE_COMPLETEACCESS as u8;
+const TOGGLE: u8 = COE_TOGGLEBIT as u8;
+const LAST_SEGMENT: u8 = COE_COMMAND_LASTSEGMENTBIT as u8;
+
+/// Segment data below this size is padded to a 10 byte frame
+const MIN_SEGMENT_DATA: usize = 7;
+
+fn address(request: &[u8]) -> (u16, u8) {
+    (u16::from_le_bytes([request[3], request[4]]), request[5])
+}
+
+fn state() -> AlState {
+    al::state().unwrap_or(AlState::Init)
+}
+
+fn find_entry(index: u16, subindex: u8) -> Result<&'static Entry, SdoAbort> {
+    od::find(index)
+        .ok_or(SdoAbort::NoObject)?
+        .entry(subindex)
+        .ok_or(SdoAbort::NoSubindex)
+}
+
+/// Size and data of a download request: expedited in the size field, or
+/// following it
+fn download_data(request: &[u8]) -> (usize, &[u8]) {
+    let command = request[2];
+    if command & EXPEDITED != 0 {
+        let size = 4 - ((command >> 2) & 3) as usize;
+        (size, &request[6..6 + size])
+    } else {
+        let size = u32::from_le_bytes(request[6..10].try_into().unwrap()) as usize;
+        (size, &request[SDO_HEADER_SIZE..])
+    }
+}
+
+/// 0x1010/0x1011 entries, commands to the parameter storage without data
+fn is_storage_command(index: u16) -> bool {
+    matches!(index, STORE_PARAMETERS_INDEX | RESTORE_DEFAULTS_INDEX)
+}
+
+/// Entries of a complete access starting at `subindex` (0 or 1)
+fn complete_access_entries(object: &Object, subindex: u8) -> Result<&'static [Entry], SdoAbort> {
+    if subindex > 1 {
+        return Err(SdoAbort::UnsupportedAccess);
+    }
+    if object.object_type() == OTYPE_VAR as u16 {
+        return Err(SdoAbort::CompleteAccessNotSupported);
+    }
+    let entries = &object.entries()[subindex as usize..];
+    if entries.is_empty() {
+        return Err(SdoAbort::NoSubindex);
+    }
+    if entries.iter().any(Entry::is_string) {
+        return Err(SdoAbort::CompleteAccessNotSupported);
+    }
+    Ok(entries)
+}
+
+/// Complete access layout: `f` gets each entry with its bit offset, the
+/// total size in bytes is returned. Subindex 0 takes 16 bits, byte sized
+/// entries are byte aligned, bit entries are packed.
+fn complete_access_layout(
+    entries: &'static [Entry],
+    mut f: impl FnMut(&'static Entry, usize),
+) -> usize {
+    let mut bits: usize = 0;
+    for entry in entries {
+        let len = entry.bit_length() as usize;
+        if len & 7 == 0 {
+            bits = bits.next_multiple_of(8);
+        }
+        f(entry, bits);
+        bits += if entry.subindex() == 0 { 16 } else { len };
+    }
+    bits.div_ceil(8)
+}
+
+/// Numeric value compared with the entry limits
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Number {
+    Signed(i64),
+    Unsigned(u64),
+    Real(f64),
+}
+
+impl Number {
+    fn new(value: SdoValue) -> Option<Self> {
+        Some(match value {
+            SdoValue::Bool(value) => Self::Unsigned(value as u64),
+            SdoValue::I8(value) => Self::Signed(value as i64),
+            SdoValue::I16(value) => Self::Signed(value as i64),
+            SdoValue::I32(value) => Self::Signed(value as i64),
+            SdoValue::I64(value) => Self::Signed(value),
+            SdoValue::U8(value) => Self::Unsigned(value as u64),
+            SdoValue::U16(value) => Self::Unsigned(value as u64),
+            SdoValue::U32(value) => Self::Unsigned(value as u64),
+            SdoValue::U64(value) => Self::Unsigned(value),
+            SdoValue::F32(value) => Self::Real(value as f64),
+            SdoValue::F64(value) => Self::Real(value),
+            SdoValue::Bytes(_) => return None,
+        })
+    }
+
+    /// Limit of `entry` encoded in its data type, of the same kind
+    fn limit(self, entry: &Entry, limit: u64) -> Self {
+        match self {
+            Self::Signed(_) => Self::Signed(limit as i64),
+            Self::Unsigned(_) => Self::Unsigned(limit),
+            Self::Real(_) if entry.datatype() == DataType::Real32 as u16 => {
+                Self::Real(f32::from_bits(limit as u32) as f64)
+            }
+            Self::Real(_) => Self::Real(f64::from_bits(limit)),
+        }
+    }
+
+    fn allowed(self, value: i64) -> Self {
+        match self {
+            Self::Signed(_) => Self::Signed(value),
+            Self::Unsigned(_) => Self::Unsigned(value as u64),
+            Self::Real(_) => Self::Real(value as f64),
+        }
+    }
+}
+
+/// Check a downloaded value against the range and allowed values of
+/// `entry`
+fn check_limits(entry: &Entry, data: &[u8]) -> Result<(), SdoAbort> {
+    let Some(value) = Number::new(SdoValue::new(entry, data)) else {
+        return Ok(());
+    };
+    if entry
+        .minimum()
+        .is_some_and(|min| value < value.limit(entry, min))
+    {
+        return Err(SdoAbort::ValueTooLow);
+    }
+    if entry
+        .maximum()
+        .is_some_and(|max| value > value.limit(entry, max))
+    {
+        return Err(SdoAbort::ValueTooHigh);
+    }
+    let allowed = entry.allowed_values();
+    if !allowed.is_empty() && !allowed.iter().any(|v| value == value.allowed(*v)) {
+        return Err(SdoAbort::ValueRange);
+    }
+    Ok(())
+}
+
+/// Initiate response: command, index, subindex and size or expedited data
+fn initiate_header(data: &mut [u8], command: u8, index: u16, subindex: u8, value: [u8; 4]) {
+    data[0] = command;
+    data[1..3].copy_from_slice(&index.to_le_bytes());
+    data[3] = subindex;
+    data[4..8].copy_from_slice(&value);
+}
+
+impl Segmented {
+    fn access(&self) -> SdoAccess {
+        SdoAccess {
+            index: self.index,
+            subindex: self.subindex,
+            complete_access: self.complete_access,
+        }
+    }
+
+    fn next(self, offset: usize) -> Self {
+        Self {
+            offset,
+            toggle: self.toggle ^ TOGGLE,
+            ..self
+        }
+    }
+}
+
+impl Coe {
+    /// SDO request, answered with a response or an abort
+    pub(super) fn sdo_request(&mut self, request: &[u8], out: &mut MbxOut) -> Result<(), MbxError> {
+        if request.len() < SDO_HEADER_SIZE {
+            return Err(MbxError::INVALID_SIZE);
+        }
+        let command = request[2];
+        let (index, subindex) = match (command & 0xE0, self.transfer) {
+            (UPLOAD_SEGMENT_REQUEST, Transfer::Upload(transfer))
+            | (DOWNLOAD_SEGMENT_REQUEST, Transfer::Download(transfer)) => {
+                (transfer.index, transfer.subindex)
+            }
+            _ => address(request),
+        };
+
+        let result = match command & 0xE0 {
+            UPLOAD_REQUEST if command & COMPLETE_ACCESS != 0 => {
+                self.upload_complete(index, subindex, out)
+            }
+            UPLOAD_REQUEST => self.upload(index, subindex, out),
+            UPLOAD_SEGMENT_REQUEST => self.upload_segment(request, out),
+            DOWNLOAD_REQUEST if command & COMPLETE_ACCESS != 0 => {
+                self.download_complete(request, out)
+            }
+            DOWNLOAD_REQUEST => self.download(request, out),
+            DOWNLOAD_SEGMENT_REQUEST => self.download_segment(request, out),
+            SDO_ABORT => {
+                self.transfer = Transfer::Idle;
+                Ok(())
+            }
+            _ => Err(SdoAbort::UnknownCommand),
+        };
+        if let Err(code) = result {
+            self.transfer = Transfer::Idle;
+            abort(out, index, subindex, code);
+        }
+        Ok(())
+    }
+
+    fn read(&self, data: Data, offset: usize, buf: &mut [u8]) {
+        match data {
+            Data::Entry(entry) => {
+                entry.read_at(offset, buf);
+            }
+            Data::CompleteAccess => {
+                buf.copy_from_slice(&self.ca_buffer[offset..offset + buf.len()])
+            }
+        }
+    }
+
+    fn upload(&mut self, index: u16, subindex: u8, out: &mut MbxOut) -> Result<(), SdoAbort> {
+        let entry = find_entry(index, subindex)?;
+        if !entry.access().readable(state()) {
+            return Err(SdoAbort::WriteOnly);
+        }
+        let access = SdoAccess {
+            index,
+            subindex,
+            complete_access: false,
+        };
+        handler::call(|handler| handler.upload_pre(&access))?;
+        self.upload_initiate(Data::Entry(entry), access, entry.byte_len(), out)
+    }
+
+    /// Complete access upload of an object, from subindex 0 or 1
+    fn upload_complete(
+        &mut self,
+        index: u16,
+        subindex: u8,
+        out: &mut MbxOut,
+    ) -> Result<(), SdoAbort> {
+        let object = od::find(index).ok_or(SdoAbort::NoObject)?;
+        let entries = complete_access_entries(object, subindex)?;
+        let size = complete_access_layout(entries, |_, _| {});
+        if size > CA_BUFFER_SIZE {
+            return Err(SdoAbort::General);
+        }
+        let access = SdoAccess {
+            index,
+            subindex,
+            complete_access: true,
+        };
+        handler::call(|handler| handler.upload_pre(&access))?;
+
+        // Entries not readable in this state read as zeros
+        let state = state();
+        let buf = &mut self.ca_buffer[..size];
+        buf.fill(0);
+        complete_access_layout(entries, |entry, offset| {
+            if entry.access().readable(state) {
+                pack_entry(buf, entry, offset);
+            }
+        });
+        self.upload_initiate(Data::CompleteAccess, access, size, out)
+    }
+
+    /// Initiate upload response: expedited up to 4 bytes, else normal,
+    /// segmented when the data does not fit in one mailbox
+    fn upload_initiate(
+        &mut self,
+        data: Data,
+        access: SdoAccess,
+        size: usize,
+        out: &mut MbxOut,
+    ) -> Result<(), SdoAbort> {
+        let SdoAccess {
+            index, subindex, ..
+        } = access;
+        let mut command = UPLOAD_RESPONSE | SIZE_INDICATOR;
+        if access.complete_access {
+            command |= COMPLETE_ACCESS;
+        }
+
+        if (1..=4).contains(&size) {
+            let mut value = [0; 4];
+            self.read(data, 0, &mut value[..size]);
+            handler::call(|handler| handler.upload_post(&access))?;
+            command |= EXPEDITED | ((4 - size as u8) << 2);
+            send(out, SERVICE_SDO_RESPONSE, |buf| {
+                initiate_header(buf, command, index, subindex, value);
+                8
+            });
+            return Ok(());
+        }
+
+        let room = out.data_size() - SDO_HEADER_SIZE;
+        let length = size.min(room);
+        if size > room {
+            self.transfer = Transfer::Upload(Segmented {
+                data,
+                index,
+                subindex,
+                complete_access: access.complete_access,
+                size,
+                offset: length,
+                toggle: 0,
+            });
+        } else {
+            handler::call(|handler| handler.upload_post(&access))?;
+        }
+        send(out, SERVICE_SDO_RESPONSE, |buf| {
+            initiate_header(buf, command, index, subindex, (size as u32).to_le_bytes());
+            self.read(data, 0, &mut buf[8..8 + length]);
+            8 + length
+        });
+        Ok(())
+    }
+
+    fn upload_segment(&mut self, request: &[u8], out: &mut MbxOut) -> Result<(), SdoAbort> {
+        let Transfer::Upload(transfer) = self.transfer else {
+            return Err(SdoAbort::UnknownCommand);
+        };
+        if request[2] & TOGGLE != transfer.toggle {
+            return Err(SdoAbort::Toggle);
+        }
+
+        let room = out.data_size() - SEGMENT_HEADER_SIZE;
+        let remaining = transfer.size - transfer.offset;
+        let length = remaining.min(room);
+        let mut command = UPLOAD_SEGMENT | transfer.toggle;
+        if remaining > room {
+            self.transfer = Transfer::Upload(transfer.next(transfer.offset + length));
+        } else {
+            self.transfer = Transfer::Idle;
+            command |= LAST_SEGMENT;
+            if length < MIN_SEGMENT_DATA {
+                command |= ((MIN_SEGMENT_DATA - length) as u8) << 1;
+            }
+            handler::call(|handler| handler.upload_post(&transfer.access()))?;
+        }
+
+        send(out, SERVICE_SDO_RESPONSE, |buf| {
+            buf[0] = command;
+            self.read(transfer.data, transfer.offset, &mut buf[1..1 + length]);
+            buf[1 + length..1 + MIN_SEGMENT_DATA.max(length)].fill(0);
+            1 + MIN_SEGMENT_DATA.max(length)
+        });
+        Ok(())
+    }
+
+    fn download(&mut self, request: &[u8], out: &mut MbxOut) -> Result<(), SdoAbort> {
+        let (index, subindex) = address(request);
+        let entry = find_entry(index, subindex)?;
+        let access = entry.access();
+        if !access.writable(state()) {
+            return Err(match access.any_write() {
+                true => SdoAbort::NotInThisState,
+                false => SdoAbort::ReadOnly,
+            });
+        }
+
+        let (size, data) = download_data(request);
+        let len = entry.byte_len();
+        if size > len && entry.is_string() {
+            return Err(SdoAbort::DataTypeTooHigh);
+        } else if size != len && !entry.is_string() {
+            return Err(SdoAbort::TypeMismatch);
+        }
+        if entry.data().is_null() && !is_storage_command(index) {
+            return Err(SdoAbort::ReadOnly);
+        }
+        if size > CA_BUFFER_SIZE {
+            return Err(SdoAbort::General);
+        }
+
+        let access = SdoAccess {
+            index,
+            subindex,
+            complete_access: false,
+        };
+        self.download_initiate(Data::Entry(entry), access, size, data)?;
+        send(out, SERVICE_SDO_RESPONSE, |buf| {
+            initiate_header(buf, DOWNLOAD_RESPONSE, index, subindex, [0; 4]);
+            8
+        });
+        Ok(())
+    }
+
+    /// Complete access download of an object, from subindex 0 or 1.
+    /// Entries not writable in this state are left unchanged.
+    fn download_complete(&mut self, request: &[u8], out: &mut MbxOut) -> Result<(), SdoAbort> {
+        let (index, subindex) = address(request);
+        let object = od::find(index).ok_or(SdoAbort::NoObject)?;
+        let entries = complete_access_entries(object, subindex)?;
+        let (size, data) = download_data(request);
+        let total = complete_access_layout(entries, |_, _| {});
+        if total > CA_BUFFER_SIZE {
+            return Err(SdoAbort::General);
+        }
+        if size > total {
+            return Err(SdoAbort::TypeMismatch);
+        }
+        let access = SdoAccess {
+            index,
+            subindex,
+            complete_access: true,
+        };
+        self.download_initiate(Data::CompleteAccess, access, size, data)?;
+        send(out, SERVICE_SDO_RESPONSE, |buf| {
+            initiate_header(
+                buf,
+                DOWNLOAD_RESPONSE | COMPLETE_ACCESS,
+                index,
+                subindex,
+                [0; 4],
+            );
+            8
+        });
+        Ok(())
+    }
+
+    /// Buffer the data of an initiate request, segmented when it does not
+    /// hold all of it
+    fn download_initiate(
+        &mut self,
+        target: Data,
+        access: SdoAccess,
+        size: usize,
+        data: &[u8],
+    ) -> Result<(), SdoAbort> {
+        let length = size.min(data.len());
+        self.ca_buffer[..length].copy_from_slice(&data[..length]);
+        let transfer = Segmented {
+            data: target,
+            index: access.index,
+            subindex: access.subindex,
+            complete_access: access.complete_access,
+            size,
+            offset: length,
+            toggle: 0,
+        };
+        if length < size {
+            self.transfer = Transfer::Download(transfer);
+            Ok(())
+        } else {
+            self.download_done(transfer)
+        }
+    }
+
+    fn download_segment(&mut self, request: &[u8], out: &mut MbxOut) -> Result<(), SdoAbort> {
+        let Transfer::Download(transfer) = self.transfer else {
+            return Err(SdoAbort::UnknownCommand);
+        };
+        let command = request[2];
+        if command & TOGGLE != transfer.toggle {
+            return Err(SdoAbort::Toggle);
+        }
+
+        let mut length = request.len() - SEGMENT_HEADER_SIZE;
+        if length == MIN_SEGMENT_DATA {
+            length -= ((command >> 1) & 7) as usize;
+        }
+        let offset = transfer.offset + length;
+        if offset > transfer.size {
+            return Err(SdoAbort::DataTypeTooHigh);
+        }
+        self.ca_buffer[transfer.offset..offset]
+            .copy_from_slice(&request[SEGMENT_HEADER_SIZE..SEGMENT_HEADER_SIZE + length]);
+
+        if command & LAST_SEGMENT != 0 {
+            self.transfer = Transfer::Idle;
+            if offset < transfer.size {
+                return Err(SdoAbort::DataTypeTooLow);
+            }
+            self.download_done(transfer)?;
+        } else {
+            self.transfer = Transfer::Download(transfer.next(offset));
+        }
+
+        send(out, SERVICE_SDO_RESPONSE, |buf| {
+            buf[0] = DOWNLOAD_SEGMENT_RESPONSE | transfer.toggle;
+            buf[1..8].fill(0);
+            8
+        });
+        Ok(())
+    }
+
+    /// All data received: check it against the entry limits and with the
+    /// pre download handler, write it (clearing the rest of a shorter
+    /// string), run the 0x1010/0x1011 command or unpack the complete access,
+    /// then call the post download handler
+    fn download_done(&mut self, transfer: Segmented) -> Result<(), SdoAbort> {
+        let access = transfer.access();
+        let buf = &self.ca_buffer[..transfer.size];
+        match transfer.data {
+            Data::Entry(entry) => {
+                check_limits(entry, buf)?;
+                handler::call(|handler| handler.download_pre(&access, SdoValue::new(entry, buf)))?;
+                if is_storage_command(transfer.index) {
+                    let value = buf.get(..4).ok_or(SdoAbort::TypeMismatch)?;
+                    storage::command(
+                        transfer.index,
+                        u32::from_le_bytes(value.try_into().unwrap()),
+                    )?;
+                } else {
+                    entry.write_at(0, buf);
+                    for offset in transfer.size..entry.byte_len() {
+                        entry.write_at(offset, &[0]);
+                    }
+                }
+            }
+            Data::CompleteAccess => {
+                let object = od::find(transfer.index).ok_or(SdoAbort::NoObject)?;
+                let entries = complete_access_entries(object, transfer.subindex)?;
+                // Entries in the data and writable in this state
+                let state = state();
+                let written = |entry: &Entry, offset: usize| {
+                    let end = (offset + entry.bit_length() as usize).div_ceil(8);
+                    entry.subindex() != 0 && end <= buf.len() && entry.access().writable(state)
+                };
+
+                let mut result = Ok(());
+                complete_access_layout(entries, |entry, offset| {
+                    let bits = entry.bit_length() as usize;
+                    if result.is_ok() && bits <= 64 && written(entry, offset) {
+                        let value = get_bits(buf, offset, bits).to_le_bytes();
+                        result = check_limits(entry, &value[..entry.byte_len()]);
+                    }
+                });
+                result?;
+                handler::call(|handler| handler.download_pre(&access, SdoValue::Bytes(buf)))?;
+                complete_access_layout(entries, |entry, offset| {
+                    if written(entry, offset) {
+                        unpack_entry(buf, entry, offset);
+                    }
+                });
+            }
+        }
+        handler::call(|handler| handler.download_post(&access))
+    }
+}
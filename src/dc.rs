@@ -0,0 +1,278 @@
+//! Distributed Clocks: SYNC0/SYNC1 synchronised process data and the
+//! 0x1C32/0x1C33 sync manager parameter objects
+
+use crate::bindings::*;
+use crate::esc;
+use crate::od::{Access, DataType, Entry, Object};
+use crate::soes::ESCvar;
+
+// Sync mode (0x1C32:01 / 0x1C33:01)
+pub const SYNC_MODE_FREERUN: u16 = 0x00;
+pub const SYNC_MODE_SM: u16 = 0x01;
+pub const SYNC_MODE_DC_SYNC0: u16 = 0x02;
+pub const SYNC_MODE_DC_SYNC1: u16 = 0x03;
+
+/// Default minimum cycle time reported in 0x1C32:05 / 0x1C33:05 (ns)
+pub const DEFAULT_MIN_CYCLE_TIME_NS: u32 = 250_000;
+
+/// Default sync error counter limit (like 0x10F1:02)
+pub const DEFAULT_SYNC_ERROR_LIMIT: u16 = 4;
+
+/// Sync manager parameter object (0x1C32 for SM2 outputs, 0x1C33 for SM3
+/// inputs). Field order follows the subindexes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, defmt::Format)]
+pub struct SyncMgrParam {
+    /// :01 current sync mode (`SYNC_MODE_*`)
+    pub sync_mode: u16,
+    /// :02 cycle time (ns)
+    pub cycle_time: u32,
+    /// :03 shift time (ns)
+    pub shift_time: u32,
+    /// :04 supported sync modes (`SYNCTYPE_SUPPORT_*`)
+    pub sync_modes_supported: u16,
+    /// :05 minimum cycle time (ns)
+    pub min_cycle_time: u32,
+    /// :06 calc and copy time (ns)
+    pub calc_copy_time: u32,
+    /// :09 delay time (ns)
+    pub delay_time: u32,
+    /// :0A SYNC0 cycle time (ns)
+    pub sync0_cycle_time: u32,
+    /// :0B SM event missed counter
+    pub sm_event_missed: u16,
+    /// :0C cycle time too small counter
+    pub cycle_time_too_small: u16,
+    /// :20 sync error
+    pub sync_error: u8,
+}
+
+impl SyncMgrParam {
+    pub const fn new() -> Self {
+        Self {
+            sync_mode: SYNC_MODE_FREERUN,
+            cycle_time: 0,
+            shift_time: 0,
+            sync_modes_supported: (SYNCTYPE_SUPPORT_FREERUN
+                | SYNCTYPE_SUPPORT_SYNCHRON
+                | SYNCTYPE_SUPPORT_DCSYNC0
+                | SYNCTYPE_SUPPORT_DCSYNC1) as u16,
+            min_cycle_time: DEFAULT_MIN_CYCLE_TIME_NS,
+            calc_copy_time: 0,
+            delay_time: 0,
+            sync0_cycle_time: 0,
+            sm_event_missed: 0,
+            cycle_time_too_small: 0,
+            sync_error: 0,
+        }
+    }
+}
+
+impl Default for SyncMgrParam {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Storage of the 0x1C32/0x1C33 objects
+#[no_mangle]
+pub static mut SM2Param: SyncMgrParam = SyncMgrParam::new();
+
+#[no_mangle]
+pub static mut SM3Param: SyncMgrParam = SyncMgrParam::new();
+
+macro_rules! sync_mgr_param_entries {
+    ($param:ident) => {
+        [
+            Entry::max_subindex(0x20),
+            Entry::new(0x01, c"Sync mode", DataType::Unsigned16, Access::RW_PRE)
+                .bind(unsafe { core::ptr::addr_of_mut!($param.sync_mode) }),
+            Entry::new(0x02, c"Cycle time", DataType::Unsigned32, Access::RO)
+                .bind(unsafe { core::ptr::addr_of_mut!($param.cycle_time) }),
+            Entry::new(0x03, c"Shift time", DataType::Unsigned32, Access::RO)
+                .bind(unsafe { core::ptr::addr_of_mut!($param.shift_time) }),
+            Entry::new(
+                0x04,
+                c"Sync modes supported",
+                DataType::Unsigned16,
+                Access::RO,
+            )
+            .bind(unsafe { core::ptr::addr_of_mut!($param.sync_modes_supported) }),
+            Entry::new(
+                0x05,
+                c"Minimum cycle time",
+                DataType::Unsigned32,
+                Access::RO,
+            )
+            .bind(unsafe { core::ptr::addr_of_mut!($param.min_cycle_time) }),
+            Entry::new(
+                0x06,
+                c"Calc and copy time",
+                DataType::Unsigned32,
+                Access::RO,
+            )
+            .bind(unsafe { core::ptr::addr_of_mut!($param.calc_copy_time) }),
+            Entry::new(0x09, c"Delay time", DataType::Unsigned32, Access::RO)
+                .bind(unsafe { core::ptr::addr_of_mut!($param.delay_time) }),
+            Entry::new(0x0A, c"Sync0 cycle time", DataType::Unsigned32, Access::RO)
+                .bind(unsafe { core::ptr::addr_of_mut!($param.sync0_cycle_time) }),
+            Entry::new(0x0B, c"SM event missed", DataType::Unsigned16, Access::RO)
+                .bind(unsafe { core::ptr::addr_of_mut!($param.sm_event_missed) }),
+            Entry::new(
+                0x0C,
+                c"Cycle time too small",
+                DataType::Unsigned16,
+                Access::RO,
+            )
+            .bind(unsafe { core::ptr::addr_of_mut!($param.cycle_time_too_small) }),
+            Entry::new(0x20, c"Sync error", DataType::Boolean, Access::RO)
+                .bind(unsafe { core::ptr::addr_of_mut!($param.sync_error) }),
+        ]
+    };
+}
+
+static SM2_PARAM_ENTRIES: [Entry; 12] = sync_mgr_param_entries!(SM2Param);
+static SM3_PARAM_ENTRIES: [Entry; 12] = sync_mgr_param_entries!(SM3Param);
+
+/// 0x1C32 Sync Manager 2 (outputs) parameters, for the application dictionary
+pub const SM2_PARAMETERS: Object =
+    Object::record(0x1C32, c"Sync Manager 2 Parameters", &SM2_PARAM_ENTRIES);
+
+/// 0x1C33 Sync Manager 3 (inputs) parameters, for the application dictionary
+pub const SM3_PARAMETERS: Object =
+    Object::record(0x1C33, c"Sync Manager 3 Parameters", &SM3_PARAM_ENTRIES);
+
+// Sync error counter limit, ETG.1020 style: +3 per missed cycle, -1 per good one
+static mut SYNC_ERROR_LIMIT: u16 = DEFAULT_SYNC_ERROR_LIMIT;
+
+/// Set the minimum cycle time accepted for SYNC0 (ns)
+pub fn set_min_cycle_time(ns: u32) {
+    unsafe {
+        SM2Param.min_cycle_time = ns;
+        SM3Param.min_cycle_time = ns;
+    }
+}
+
+/// Set the sync error counter limit before the slave drops to SAFEOP
+pub fn set_sync_error_limit(limit: u16) {
+    unsafe {
+        SYNC_ERROR_LIMIT = limit;
+    }
+}
+
+/// True when the slave runs DC synchronised (SYNC0 drives process data)
+pub fn dc_active() -> bool {
+    unsafe { ESCvar.dcsync > 0 }
+}
+
+/// True when SYNC1 is used to latch the inputs
+pub fn sync1_active() -> bool {
+    unsafe { ESCvar.dcsync > 0 && SM3Param.sync_mode == SYNC_MODE_DC_SYNC1 }
+}
+
+/// Default `esc_check_dc_handler`: validate the SYNC unit setup done by the
+/// master and publish it in 0x1C32/0x1C33.
+///
+/// Called by the stack on the PREOP -> SAFEOP transition when the SYNC unit
+/// is activated. Returns an AL status code, 0 when the setup is accepted.
+///
+/// # Safety
+/// Only called by the stack, from the context running `EcatSlave`: it
+/// reads the SYNC registers through the bound driver and updates the
+/// 0x1C32/0x1C33 parameters, which must not be accessed meanwhile.
+pub unsafe extern "C" fn dc_check_handler() -> u16 {
+    let activation = esc::sync_activation() as u32;
+    if activation & ESCREG_SYNC_SYNC0_EN == 0 {
+        defmt::warn!("DC: SYNC unit active without SYNC0");
+        return ALERR_DCINVALIDSYNCCFG as u16;
+    }
+
+    let sync0_cycle = esc::sync0_cycle_time();
+    let min_cycle = SM2Param.min_cycle_time;
+    if sync0_cycle < min_cycle {
+        defmt::warn!(
+            "DC: SYNC0 cycle {} ns below minimum {} ns",
+            sync0_cycle,
+            min_cycle
+        );
+        SM2Param.cycle_time_too_small = SM2Param.cycle_time_too_small.wrapping_add(1);
+        return ALERR_DCSYNC0CYCLETIME as u16;
+    }
+
+    let (mode, shift) = if activation & ESCREG_SYNC_SYNC1_EN != 0 {
+        (SYNC_MODE_DC_SYNC1, esc::sync1_cycle_time())
+    } else {
+        (SYNC_MODE_DC_SYNC0, 0)
+    };
+
+    // A mode written by the master over SDO must match the SYNC unit setup
+    let requested = SM2Param.sync_mode;
+    if requested != SYNC_MODE_FREERUN && requested != mode {
+        defmt::warn!(
+            "DC: sync mode {} requested, SYNC unit set to {}",
+            requested,
+            mode
+        );
+        return ALERR_DCINVALIDSYNCCFG as u16;
+    }
+
+    for param in [
+        &mut *core::ptr::addr_of_mut!(SM2Param),
+        &mut *core::ptr::addr_of_mut!(SM3Param),
+    ] {
+        param.sync_mode = mode;
+        param.cycle_time = sync0_cycle;
+        param.sync0_cycle_time = sync0_cycle;
+        param.sm_event_missed = 0;
+        param.sync_error = 0;
+    }
+    SM3Param.shift_time = shift;
+
+    ESCvar.dcsync = 1;
+    ESCvar.synccounter = 0;
+    ESCvar.synccounterlimit = SYNC_ERROR_LIMIT;
+
+    defmt::info!("DC: mode {} with SYNC0 cycle {} ns", mode, sync0_cycle);
+    0
+}
+
+/// Account for one SYNC0 cycle. `sm2_seen` tells if the outputs were
+/// received since the previous SYNC0.
+///
+/// Returns `false` once the sync error counter exceeds its limit.
+pub(crate) fn sync0_cycle(sm2_seen: bool) -> bool {
+    unsafe {
+        if sm2_seen {
+            if ESCvar.synccounter > 0 {
+                ESCvar.synccounter -= 1;
+            }
+            return true;
+        }
+
+        SM2Param.sm_event_missed = SM2Param.sm_event_missed.wrapping_add(1);
+        ESCvar.synccounter = ESCvar.synccounter.saturating_add(3);
+        if ESCvar.synccounter as u16 > ESCvar.synccounterlimit {
+            SM2Param.sync_error = 1;
+            SM3Param.sync_error = 1;
+            return false;
+        }
+        true
+    }
+}
+
+/// Back to free run, used when the stack stops the process data
+pub(crate) fn reset_sync_mode() {
+    unsafe {
+        ESCvar.dcsync = 0;
+        ESCvar.synccounter = 0;
+        for param in [
+            &mut *core::ptr::addr_of_mut!(SM2Param),
+            &mut *core::ptr::addr_of_mut!(SM3Param),
+        ] {
+            param.sync_mode = SYNC_MODE_FREERUN;
+            param.cycle_time = 0;
+            param.shift_time = 0;
+            param.sync0_cycle_time = 0;
+        }
+    }
+}
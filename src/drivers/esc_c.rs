@@ -1,32 +1,151 @@
-use crate::esc_driver::EscDriver;
+use crate::bindings::{ESCREG_ALEVENT_DC_SYNC0, ESCREG_ALEVENT_DC_SYNC1, ESCREG_ALEVENT_SM2};
+use crate::dc;
+use crate::esc;
+use crate::esc_driver::{EscDriver, EscError};
 
-// static driver accessible aux bindings C
-static mut DRIVER: Option<&'static mut dyn EscDriver> = None;
+// Driver currently bound to the C callbacks, type-erased so any `EscDriver`
+// owned by an `EcatSlave` can be routed without a `'static` bound
+#[derive(Clone, Copy)]
+struct Binding {
+    driver: *mut (),
+    write: unsafe fn(*mut (), u16, &[u8]) -> Result<(), EscError>,
+    read: unsafe fn(*mut (), u16, &mut [u8]) -> Result<(), EscError>,
+}
+
+static mut BINDING: Option<Binding> = None;
+
+// First driver error seen by the C callbacks, until taken by the slave
+static mut DRIVER_ERROR: Option<EscError> = None;
+
+unsafe fn write_erased<D: EscDriver>(
+    driver: *mut (),
+    address: u16,
+    buf: &[u8],
+) -> Result<(), EscError> {
+    (*(driver as *mut D)).write(address, buf)
+}
+
+unsafe fn read_erased<D: EscDriver>(
+    driver: *mut (),
+    address: u16,
+    buf: &mut [u8],
+) -> Result<(), EscError> {
+    (*(driver as *mut D)).read(address, buf)
+}
 
-/// Set the global ESC driver
-pub fn set_driver(driver: &'static mut dyn EscDriver) {
-    unsafe {
-        DRIVER = Some(driver);
+/// Routes `ESC_read`/`ESC_write` to a driver until dropped, then restores
+/// the previous binding (bindings nest)
+pub(crate) struct DriverBinding {
+    previous: Option<Binding>,
+}
+
+/// Bind `driver` to the C callbacks for the lifetime of the returned guard.
+///
+/// # Safety
+/// `driver` must stay valid and must not be accessed other than through
+/// the C callbacks while the guard is alive.
+pub(crate) unsafe fn bind_driver<D: EscDriver>(driver: *mut D) -> DriverBinding {
+    let binding = Binding {
+        driver: driver as *mut (),
+        write: write_erased::<D>,
+        read: read_erased::<D>,
+    };
+    let previous = core::ptr::replace(core::ptr::addr_of_mut!(BINDING), Some(binding));
+    DriverBinding { previous }
+}
+
+impl Drop for DriverBinding {
+    fn drop(&mut self) {
+        unsafe {
+            *core::ptr::addr_of_mut!(BINDING) = self.previous;
+        }
     }
 }
 
-/// ESC C bindings will call this
+fn bound_driver() -> Option<Binding> {
+    unsafe { *core::ptr::addr_of!(BINDING) }
+}
+
+/// Take (and clear) the first error reported by the driver since the last call.
+///
+/// The C stack cannot receive a `Result` from `ESC_read`/`ESC_write`, so
+/// failures are latched here and handled by `EcatSlave`.
+pub fn take_driver_error() -> Option<EscError> {
+    unsafe { (*core::ptr::addr_of_mut!(DRIVER_ERROR)).take() }
+}
+
+fn latch_error(result: Result<(), EscError>) {
+    if let Err(err) = result {
+        unsafe {
+            let latched = &mut *core::ptr::addr_of_mut!(DRIVER_ERROR);
+            if latched.is_none() {
+                *latched = Some(err);
+            }
+        }
+    }
+}
+
+/// ESC C bindings will call this, routed to the driver of the running
+/// `EcatSlave`
+///
+/// # Safety
+/// `buf` must be valid for reads of `len` bytes and not written meanwhile.
 #[no_mangle]
-pub extern "C" fn ESC_write(address: u16, buf: *const u8, len: usize) {
-    unsafe {
-        if let Some(driver) = DRIVER.as_mut() {
+pub unsafe extern "C" fn ESC_write(address: u16, buf: *const u8, len: usize) {
+    match bound_driver() {
+        Some(binding) => {
             let slice = core::slice::from_raw_parts(buf, len);
-            driver.write(address, slice);
+            latch_error((binding.write)(binding.driver, address, slice));
         }
+        None => defmt::warn!("ESC_write 0x{:04x} without a driver", address),
     }
 }
 
+/// Read counterpart of [`ESC_write`]
+///
+/// # Safety
+/// `buf` must be valid for writes of `len` bytes and not accessed
+/// otherwise meanwhile.
 #[no_mangle]
-pub extern "C" fn ESC_read(address: u16, buf: *mut u8, len: usize) {
-    unsafe {
-        if let Some(driver) = DRIVER.as_mut() {
+pub unsafe extern "C" fn ESC_read(address: u16, buf: *mut u8, len: usize) {
+    match bound_driver() {
+        Some(binding) => {
             let slice = core::slice::from_raw_parts_mut(buf, len);
-            driver.read(address, slice);
+            latch_error((binding.read)(binding.driver, address, slice));
         }
+        None => defmt::warn!("ESC_read 0x{:04x} without a driver", address),
+    }
+}
+
+/// Default `esc_hw_interrupt_enable` hook: unmask `mask` in the AL event mask
+///
+/// When DC synchronised, outputs are taken at SYNC0 so SM2 stays masked,
+/// and SYNC1 is added if it latches the inputs.
+///
+/// # Safety
+/// Only called by the stack, from the context running `EcatSlave`: the AL
+/// event mask is read and written back through the bound driver, so no
+/// other ESC access (e.g. from an interrupt handler) may run meanwhile.
+pub unsafe extern "C" fn esc_irq_enable(mut mask: u32) {
+    if dc::dc_active() {
+        mask &= !ESCREG_ALEVENT_SM2;
+        if dc::sync1_active() {
+            mask |= ESCREG_ALEVENT_DC_SYNC1;
+        }
+    }
+    esc::al_event_mask_write(esc::al_event_mask_read() | mask);
+}
+
+/// Default `esc_hw_interrupt_disable` hook: mask `mask` in the AL event mask
+/// (SYNC1 goes with SYNC0) and leave DC mode
+///
+/// # Safety
+/// Same as [`esc_irq_enable`]; the DC sync mode is also reset, which must
+/// not race with the SYNC0/SYNC1 handling of the slave.
+pub unsafe extern "C" fn esc_irq_disable(mut mask: u32) {
+    if mask & ESCREG_ALEVENT_DC_SYNC0 != 0 {
+        mask |= ESCREG_ALEVENT_DC_SYNC1;
+        dc::reset_sync_mode();
     }
+    esc::al_event_mask_write(esc::al_event_mask_read() & !mask);
 }
@@ -0,0 +1,403 @@
+use crate::bindings::ESCREG_ALEVENT;
+use crate::soes;
+
+use crate::drivers::lan9252_cst::*;
+use crate::esc_driver::{EscDriver, EscError};
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+/// Maximum number of register polls for each LAN9252 busy-wait before
+/// giving up with [`EscError::Timeout`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lan9252Timeouts {
+    /// CSR command BUSY bit (`read_csr` / `write_csr`)
+    pub csr: u32,
+    /// PRAM command BUSY / AVAIL bits (`read_pram` / `write_pram`)
+    pub pram: u32,
+    /// Reset bit after a soft reset (`init`)
+    pub reset: u32,
+}
+
+impl Default for Lan9252Timeouts {
+    fn default() -> Self {
+        Self {
+            csr: 1_000,
+            pram: 1_000,
+            reset: 10_000,
+        }
+    }
+}
+
+/// LAN9252 identification read from `ESC_ID_REV_REG`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub struct Lan9252ChipId {
+    /// Chip ID (0x9252)
+    pub id: u16,
+    /// Chip revision
+    pub revision: u16,
+}
+
+impl Lan9252ChipId {
+    pub fn from_register(value: u32) -> Self {
+        Self {
+            id: (value >> 16) as u16,
+            revision: (value & 0xFFFF) as u16,
+        }
+    }
+
+    /// Same check as `ESC_IsLAN9252` in the C HAL
+    pub fn is_lan9252(&self) -> bool {
+        self.id == LAN9252_ID_REV && (self.revision & 0xFF) >= 1
+    }
+}
+
+/// LAN9252 driver over any embedded-hal 1.0 blocking SPI device.
+///
+/// Chip select is handled by the `SpiDevice` implementation (e.g.
+/// `embedded_hal_bus::spi::ExclusiveDevice` or `linux-embedded-hal`'s spidev).
+pub struct Lan9252<SPI> {
+    spi: SPI,
+    timeouts: Lan9252Timeouts,
+}
+
+impl<SPI: SpiDevice> Lan9252<SPI> {
+    pub fn new(spi: SPI) -> Self {
+        Self {
+            spi,
+            timeouts: Lan9252Timeouts::default(),
+        }
+    }
+
+    /// Set the poll limits of the busy-wait loops
+    pub fn set_timeouts(&mut self, timeouts: Lan9252Timeouts) {
+        self.timeouts = timeouts;
+    }
+
+    /// Give back the underlying SPI device
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Pulse the RST# pin (like `rst_low`/`rst_high` in `hal/stm32-lan9252/rst.c`).
+    ///
+    /// Only needed on boards wiring RST# to the MCU, follow with `init`.
+    pub fn hard_reset<RST: OutputPin, D: DelayNs>(
+        &mut self,
+        rst: &mut RST,
+        delay: &mut D,
+    ) -> Result<(), EscError> {
+        rst.set_low().map_err(|_| EscError::Bus)?;
+        delay.delay_us(LAN9252_RST_PULSE_US);
+        rst.set_high().map_err(|_| EscError::Bus)
+    }
+
+    /// Read the chip ID and revision
+    pub fn chip_id(&mut self) -> Result<Lan9252ChipId, EscError> {
+        let value = self.read_32(ESC_ID_REV_REG)?;
+        Ok(Lan9252ChipId::from_register(value))
+    }
+
+    /// Full bring-up sequence (like `ESC_init` in `hal/stm32-lan9252/esc_hw.c`):
+    /// digital reset, byte order test, ready flag and chip identification
+    pub fn bring_up(&mut self) -> Result<Lan9252ChipId, EscError> {
+        self.soft_reset()?;
+
+        // The byte test register only reads correctly once the SPI/PDI is up
+        self.wait_until(LAN9252_BYTE_TEST, self.timeouts.reset, |v| {
+            v == ESC_TEST_VALUE
+        })
+        .map_err(|err| match err {
+            EscError::Timeout => EscError::NotResponding,
+            err => err,
+        })?;
+
+        // Wait for the device to be ready
+        self.wait_until(ESC_HW_CFG_REG, self.timeouts.reset, |v| v & ESC_READY != 0)?;
+
+        let chip = self.chip_id()?;
+        if !chip.is_lan9252() {
+            defmt::error!(
+                "LAN9252: unexpected chip ID 0x{:04X} rev {}",
+                chip.id,
+                chip.revision
+            );
+            return Err(EscError::NotResponding);
+        }
+
+        defmt::info!("LAN9252 detected (rev {})", chip.revision);
+        Ok(chip)
+    }
+
+    /// Reset the device through `ESC_RESET_CTRL_REG` and wait for completion
+    fn soft_reset(&mut self) -> Result<(), EscError> {
+        self.write_32(ESC_RESET_CTRL_REG, ESC_DIGITAL_RST)?;
+        self.wait_until(ESC_RESET_CTRL_REG, self.timeouts.reset, |v| {
+            v & (ESC_DIGITAL_RST | ESC_RESET_CTRL_RST) == 0
+        })?;
+        Ok(())
+    }
+
+    fn write_32(&mut self, address: u16, val: u32) -> Result<(), EscError> {
+        let data = [
+            ESC_CMD_SERIAL_WRITE,
+            ((address >> 8) & 0xFF) as u8,
+            (address & 0xFF) as u8,
+            (val & 0xFF) as u8,
+            ((val >> 8) & 0xFF) as u8,
+            ((val >> 16) & 0xFF) as u8,
+            ((val >> 24) & 0xFF) as u8,
+        ];
+
+        self.spi.write(&data).map_err(|_| EscError::Bus)
+    }
+
+    fn read_32(&mut self, address: u16) -> Result<u32, EscError> {
+        let tx = [
+            ESC_CMD_FAST_READ,
+            ((address >> 8) & 0xFF) as u8,
+            (address & 0xFF) as u8,
+            ESC_CMD_FAST_READ_DUMMY,
+        ];
+        let mut rx = [0u8; 4];
+
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&tx),
+                Operation::Transfer(&mut rx, &[0xFF; 4]),
+            ])
+            .map_err(|_| EscError::Bus)?;
+
+        Ok(u32::from_le_bytes(rx))
+    }
+
+    /// Poll `address` until `done(value)` holds, at most `max_polls` times
+    fn wait_until(
+        &mut self,
+        address: u16,
+        max_polls: u32,
+        done: impl Fn(u32) -> bool,
+    ) -> Result<u32, EscError> {
+        for _ in 0..max_polls {
+            let value = self.read_32(address)?;
+            if done(value) {
+                return Ok(value);
+            }
+        }
+        defmt::warn!("LAN9252: timeout polling register 0x{:04X}", address);
+        Err(EscError::Timeout)
+    }
+
+    /// CSR Read helper (like `ESC_read_csr` in C)
+    fn read_csr(&mut self, address: u16, buf: &mut [u8]) -> Result<(), EscError> {
+        let len = buf.len() as u32;
+
+        // Issue CSR read command
+        let value = ESC_CSR_CMD_READ | ESC_CSR_CMD_SIZE(len) | (address as u32);
+        self.write_32(ESC_CSR_CMD_REG, value)?;
+
+        // Wait until not busy
+        self.wait_until(ESC_CSR_CMD_REG, self.timeouts.csr, |v| {
+            v & ESC_CSR_CMD_BUSY == 0
+        })?;
+
+        // Read data
+        let value = self.read_32(ESC_CSR_DATA_REG)?;
+        buf.copy_from_slice(&value.to_le_bytes()[..buf.len()]);
+        Ok(())
+    }
+
+    /// CSR Write helper (like `ESC_write_csr` in C)
+    fn write_csr(&mut self, address: u16, buf: &[u8]) -> Result<(), EscError> {
+        // Pack buf into u32 (little endian)
+        let mut bytes = [0u8; 4];
+        bytes[..buf.len()].copy_from_slice(buf);
+
+        // Write data
+        self.write_32(ESC_CSR_DATA_REG, u32::from_le_bytes(bytes))?;
+
+        // Issue CSR write command
+        let value = ESC_CSR_CMD_WRITE | ESC_CSR_CMD_SIZE(buf.len() as u32) | (address as u32);
+        self.write_32(ESC_CSR_CMD_REG, value)?;
+
+        // Wait until not busy
+        self.wait_until(ESC_CSR_CMD_REG, self.timeouts.csr, |v| {
+            v & ESC_CSR_CMD_BUSY == 0
+        })?;
+        Ok(())
+    }
+
+    fn update_AlEvent(&mut self) -> Result<(), EscError> {
+        let mut buf = [0u8; 2];
+        self.read_csr(ESCREG_ALEVENT as u16, &mut buf)?;
+
+        unsafe {
+            soes::ESCvar.ALevent = u16::from_le_bytes(buf);
+        }
+        Ok(())
+    }
+
+    /// ESC write process data RAM function
+    fn write_pram(&mut self, address: u16, buf: &[u8]) -> Result<(), EscError> {
+        let len = buf.len();
+
+        // Abort any ongoing PRAM write
+        self.write_32(ESC_PRAM_WR_CMD_REG, ESC_PRAM_CMD_ABORT)?;
+        self.wait_until(ESC_PRAM_WR_CMD_REG, self.timeouts.pram, |v| {
+            v & ESC_PRAM_CMD_BUSY == 0
+        })?;
+
+        // Set address and length
+        let value = ESC_PRAM_SIZE(len as u32) | ESC_PRAM_ADDR(address as u32);
+        self.write_32(ESC_PRAM_WR_ADDR_LEN_REG, value)?;
+
+        // Start PRAM write and wait for FIFO to be ready
+        self.write_32(ESC_PRAM_WR_CMD_REG, ESC_PRAM_CMD_BUSY)?;
+        self.wait_until(ESC_PRAM_WR_CMD_REG, self.timeouts.pram, |v| {
+            v & ESC_PRAM_CMD_AVAIL != 0
+        })?;
+
+        // First 32-bit value, shifted to the address byte lane
+        let first_byte_position = (address & 0x03) as usize;
+        let first_len = (4 - first_byte_position).min(len);
+        let mut first = [0u8; 4];
+        first[first_byte_position..first_byte_position + first_len]
+            .copy_from_slice(&buf[..first_len]);
+        self.write_32(ESC_PRAM_WR_FIFO_REG, u32::from_le_bytes(first))?;
+
+        let rest = &buf[first_len..];
+        if rest.is_empty() {
+            return Ok(());
+        }
+
+        // Incrementing write of the remaining bytes, padded to whole DWORDs
+        let cmd = [
+            ESC_CMD_SERIAL_WRITE,
+            ((ESC_PRAM_WR_FIFO_REG >> 8) & 0xFF) as u8,
+            (ESC_PRAM_WR_FIFO_REG & 0xFF) as u8,
+        ];
+        let padding = [0u8; 3];
+        let pad_len = (4 - rest.len() % 4) % 4;
+
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&cmd),
+                Operation::Write(rest),
+                Operation::Write(&padding[..pad_len]),
+            ])
+            .map_err(|_| EscError::Bus)
+    }
+
+    /// ESC read process data RAM function
+    fn read_pram(&mut self, address: u16, buf: &mut [u8]) -> Result<(), EscError> {
+        let len = buf.len();
+
+        // Abort any ongoing PRAM read
+        self.write_32(ESC_PRAM_RD_CMD_REG, ESC_PRAM_CMD_ABORT)?;
+        self.wait_until(ESC_PRAM_RD_CMD_REG, self.timeouts.pram, |v| {
+            v & ESC_PRAM_CMD_BUSY == 0
+        })?;
+
+        // Set address and length
+        let value = ESC_PRAM_SIZE(len as u32) | ESC_PRAM_ADDR(address as u32);
+        self.write_32(ESC_PRAM_RD_ADDR_LEN_REG, value)?;
+
+        // Start PRAM read and wait for FIFO to be ready
+        self.write_32(ESC_PRAM_RD_CMD_REG, ESC_PRAM_CMD_BUSY)?;
+        self.wait_until(ESC_PRAM_RD_CMD_REG, self.timeouts.pram, |v| {
+            v & ESC_PRAM_CMD_AVAIL != 0
+        })?;
+
+        // First 32-bit value, shifted from the address byte lane
+        let value = self.read_32(ESC_PRAM_RD_FIFO_REG)?;
+        let first_byte_position = (address & 0x03) as usize;
+        let first_len = (4 - first_byte_position).min(len);
+        buf[..first_len].copy_from_slice(
+            &value.to_le_bytes()[first_byte_position..first_byte_position + first_len],
+        );
+
+        let rest = &mut buf[first_len..];
+        if rest.is_empty() {
+            return Ok(());
+        }
+
+        // FIFO read of the remaining bytes, drained as whole DWORDs
+        let cmd = [
+            ESC_CMD_FAST_READ,
+            ((ESC_PRAM_RD_FIFO_REG >> 8) & 0xFF) as u8,
+            (ESC_PRAM_RD_FIFO_REG & 0xFF) as u8,
+            ESC_CMD_FAST_READ_DUMMY,
+        ];
+        let mut padding = [0u8; 3];
+        let pad_len = (4 - rest.len() % 4) % 4;
+
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&cmd),
+                Operation::Read(rest),
+                Operation::Read(&mut padding[..pad_len]),
+            ])
+            .map_err(|_| EscError::Bus)
+    }
+}
+
+impl<SPI: SpiDevice> EscDriver for Lan9252<SPI> {
+    fn init(&mut self) -> Result<(), EscError> {
+        self.bring_up()?;
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), EscError> {
+        self.soft_reset()
+    }
+
+    /// Enable the IRQ pin (active low, push-pull) for EtherCAT AL event
+    /// requests. Which events assert it is selected by the AL event mask.
+    fn enable_irq(&mut self) -> Result<(), EscError> {
+        self.write_32(
+            LAN9252_IRQ_CFG,
+            LAN9252_IRQ_CFG_IRQ_EN | LAN9252_IRQ_CFG_IRQ_BUF_TYPE,
+        )?;
+        self.write_32(LAN9252_INT_EN, LAN9252_INT_ECAT)
+    }
+
+    fn disable_irq(&mut self) -> Result<(), EscError> {
+        self.write_32(LAN9252_INT_EN, 0)?;
+        self.write_32(LAN9252_IRQ_CFG, 0)
+    }
+
+    /// Write to ESC memory (CSR or PRAM depending on address)
+    fn write(&mut self, mut address: u16, mut buf: &[u8]) -> Result<(), EscError> {
+        if address >= 0x1000 {
+            return self.write_pram(address, buf);
+        }
+
+        while !buf.is_empty() {
+            let size = csr_access_size(address, buf.len());
+            self.write_csr(address, &buf[..size])?;
+
+            buf = &buf[size..];
+            address += size as u16;
+        }
+        /* To mimic the ET1100 always providing AlEvent on every read or write */
+        self.update_AlEvent()
+    }
+
+    /// Read from ESC memory (CSR or PRAM depending on address)
+    fn read(&mut self, mut address: u16, mut buf: &mut [u8]) -> Result<(), EscError> {
+        if address >= 0x1000 {
+            return self.read_pram(address, buf);
+        }
+
+        while !buf.is_empty() {
+            let size = csr_access_size(address, buf.len());
+            self.read_csr(address, &mut buf[..size])?;
+
+            buf = &mut buf[size..];
+            address += size as u16;
+        }
+        /* To mimic the ET1100 always providing AlEvent on every read or write */
+        self.update_AlEvent()
+    }
+}
@@ -0,0 +1,411 @@
+use crate::bindings::ESCREG_ALEVENT;
+use crate::soes;
+
+use crate::drivers::lan9252::Lan9252ChipId;
+use crate::drivers::lan9252_cst::*;
+use crate::esc_driver::{AsyncEscDriver, EscError};
+
+use embassy_time::{Duration, Instant, Timer};
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::spi::{Operation, SpiDevice};
+
+/// Default delay between two polls of a BUSY/AVAIL bit
+pub const LAN9252_DEFAULT_POLL_INTERVAL: Duration = Duration::from_micros(5);
+
+/// Maximum time spent in each LAN9252 busy-wait before giving up with
+/// [`EscError::Timeout`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lan9252AsyncTimeouts {
+    /// CSR command BUSY bit (`read_csr` / `write_csr`)
+    pub csr: Duration,
+    /// PRAM command BUSY / AVAIL bits (`read_pram` / `write_pram`)
+    pub pram: Duration,
+    /// Reset bit after a soft reset (`init`)
+    pub reset: Duration,
+}
+
+impl Default for Lan9252AsyncTimeouts {
+    fn default() -> Self {
+        Self {
+            csr: Duration::from_millis(5),
+            pram: Duration::from_millis(5),
+            reset: Duration::from_millis(100),
+        }
+    }
+}
+
+/// LAN9252 driver over an async SPI device (e.g. Embassy DMA SPI).
+///
+/// Every busy-wait awaits `poll_interval` between two register reads, so the
+/// executor keeps running other tasks during long PRAM transfers.
+pub struct Lan9252Async<SPI> {
+    spi: SPI,
+    poll_interval: Duration,
+    timeouts: Lan9252AsyncTimeouts,
+}
+
+impl<SPI: SpiDevice> Lan9252Async<SPI> {
+    pub fn new(spi: SPI) -> Self {
+        Self {
+            spi,
+            poll_interval: LAN9252_DEFAULT_POLL_INTERVAL,
+            timeouts: Lan9252AsyncTimeouts::default(),
+        }
+    }
+
+    /// Set the maximum duration of the busy-wait loops
+    pub fn set_timeouts(&mut self, timeouts: Lan9252AsyncTimeouts) {
+        self.timeouts = timeouts;
+    }
+
+    /// Set the delay awaited between two polls of a BUSY/AVAIL bit
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
+    /// Give back the underlying SPI device
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Pulse the RST# pin (like `rst_low`/`rst_high` in `hal/stm32-lan9252/rst.c`).
+    ///
+    /// Only needed on boards wiring RST# to the MCU, follow with `init`.
+    pub async fn hard_reset<RST: OutputPin>(&mut self, rst: &mut RST) -> Result<(), EscError> {
+        rst.set_low().map_err(|_| EscError::Bus)?;
+        Timer::after_micros(LAN9252_RST_PULSE_US as u64).await;
+        rst.set_high().map_err(|_| EscError::Bus)
+    }
+
+    /// Read the chip ID and revision
+    pub async fn chip_id(&mut self) -> Result<Lan9252ChipId, EscError> {
+        let value = self.read_32(ESC_ID_REV_REG).await?;
+        Ok(Lan9252ChipId::from_register(value))
+    }
+
+    /// Full bring-up sequence (like `ESC_init` in `hal/stm32-lan9252/esc_hw.c`):
+    /// digital reset, byte order test, ready flag and chip identification
+    pub async fn bring_up(&mut self) -> Result<Lan9252ChipId, EscError> {
+        self.soft_reset().await?;
+
+        // The byte test register only reads correctly once the SPI/PDI is up
+        self.wait_until(LAN9252_BYTE_TEST, self.timeouts.reset, |v| {
+            v == ESC_TEST_VALUE
+        })
+        .await
+        .map_err(|err| match err {
+            EscError::Timeout => EscError::NotResponding,
+            err => err,
+        })?;
+
+        // Wait for the device to be ready
+        self.wait_until(ESC_HW_CFG_REG, self.timeouts.reset, |v| v & ESC_READY != 0)
+            .await?;
+
+        let chip = self.chip_id().await?;
+        if !chip.is_lan9252() {
+            defmt::error!(
+                "LAN9252: unexpected chip ID 0x{:04X} rev {}",
+                chip.id,
+                chip.revision
+            );
+            return Err(EscError::NotResponding);
+        }
+
+        defmt::info!("LAN9252 detected (rev {})", chip.revision);
+        Ok(chip)
+    }
+
+    /// Reset the device through `ESC_RESET_CTRL_REG` and wait for completion
+    async fn soft_reset(&mut self) -> Result<(), EscError> {
+        self.write_32(ESC_RESET_CTRL_REG, ESC_DIGITAL_RST).await?;
+        self.wait_until(ESC_RESET_CTRL_REG, self.timeouts.reset, |v| {
+            v & (ESC_DIGITAL_RST | ESC_RESET_CTRL_RST) == 0
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn write_32(&mut self, address: u16, val: u32) -> Result<(), EscError> {
+        let data = [
+            ESC_CMD_SERIAL_WRITE,
+            ((address >> 8) & 0xFF) as u8,
+            (address & 0xFF) as u8,
+            (val & 0xFF) as u8,
+            ((val >> 8) & 0xFF) as u8,
+            ((val >> 16) & 0xFF) as u8,
+            ((val >> 24) & 0xFF) as u8,
+        ];
+
+        self.spi.write(&data).await.map_err(|_| EscError::Bus)
+    }
+
+    async fn read_32(&mut self, address: u16) -> Result<u32, EscError> {
+        let tx = [
+            ESC_CMD_FAST_READ,
+            ((address >> 8) & 0xFF) as u8,
+            (address & 0xFF) as u8,
+            ESC_CMD_FAST_READ_DUMMY,
+        ];
+        let mut rx = [0u8; 4];
+
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&tx),
+                Operation::Transfer(&mut rx, &[0xFF; 4]),
+            ])
+            .await
+            .map_err(|_| EscError::Bus)?;
+
+        Ok(u32::from_le_bytes(rx))
+    }
+
+    /// Poll `address` until `done(value)` holds, yielding between reads,
+    /// for at most `timeout`
+    async fn wait_until(
+        &mut self,
+        address: u16,
+        timeout: Duration,
+        done: impl Fn(u32) -> bool,
+    ) -> Result<u32, EscError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let value = self.read_32(address).await?;
+            if done(value) {
+                return Ok(value);
+            }
+            if Instant::now() >= deadline {
+                defmt::warn!("LAN9252: timeout polling register 0x{:04X}", address);
+                return Err(EscError::Timeout);
+            }
+            Timer::after(self.poll_interval).await;
+        }
+    }
+
+    /// CSR Read helper (like `ESC_read_csr` in C)
+    async fn read_csr(&mut self, address: u16, buf: &mut [u8]) -> Result<(), EscError> {
+        let len = buf.len() as u32;
+
+        // Issue CSR read command
+        let value = ESC_CSR_CMD_READ | ESC_CSR_CMD_SIZE(len) | (address as u32);
+        self.write_32(ESC_CSR_CMD_REG, value).await?;
+
+        // Wait until not busy
+        self.wait_until(ESC_CSR_CMD_REG, self.timeouts.csr, |v| {
+            v & ESC_CSR_CMD_BUSY == 0
+        })
+        .await?;
+
+        // Read data
+        let value = self.read_32(ESC_CSR_DATA_REG).await?;
+        buf.copy_from_slice(&value.to_le_bytes()[..buf.len()]);
+        Ok(())
+    }
+
+    /// CSR Write helper (like `ESC_write_csr` in C)
+    async fn write_csr(&mut self, address: u16, buf: &[u8]) -> Result<(), EscError> {
+        // Pack buf into u32 (little endian)
+        let mut bytes = [0u8; 4];
+        bytes[..buf.len()].copy_from_slice(buf);
+
+        // Write data
+        self.write_32(ESC_CSR_DATA_REG, u32::from_le_bytes(bytes))
+            .await?;
+
+        // Issue CSR write command
+        let value = ESC_CSR_CMD_WRITE | ESC_CSR_CMD_SIZE(buf.len() as u32) | (address as u32);
+        self.write_32(ESC_CSR_CMD_REG, value).await?;
+
+        // Wait until not busy
+        self.wait_until(ESC_CSR_CMD_REG, self.timeouts.csr, |v| {
+            v & ESC_CSR_CMD_BUSY == 0
+        })
+        .await?;
+        Ok(())
+    }
+
+    async fn update_AlEvent(&mut self) -> Result<(), EscError> {
+        let mut buf = [0u8; 2];
+        self.read_csr(ESCREG_ALEVENT as u16, &mut buf).await?;
+
+        unsafe {
+            soes::ESCvar.ALevent = u16::from_le_bytes(buf);
+        }
+        Ok(())
+    }
+
+    /// ESC write process data RAM function
+    async fn write_pram(&mut self, address: u16, buf: &[u8]) -> Result<(), EscError> {
+        let len = buf.len();
+
+        // Abort any ongoing PRAM write
+        self.write_32(ESC_PRAM_WR_CMD_REG, ESC_PRAM_CMD_ABORT)
+            .await?;
+        self.wait_until(ESC_PRAM_WR_CMD_REG, self.timeouts.pram, |v| {
+            v & ESC_PRAM_CMD_BUSY == 0
+        })
+        .await?;
+
+        // Set address and length
+        let value = ESC_PRAM_SIZE(len as u32) | ESC_PRAM_ADDR(address as u32);
+        self.write_32(ESC_PRAM_WR_ADDR_LEN_REG, value).await?;
+
+        // Start PRAM write and wait for FIFO to be ready
+        self.write_32(ESC_PRAM_WR_CMD_REG, ESC_PRAM_CMD_BUSY)
+            .await?;
+        self.wait_until(ESC_PRAM_WR_CMD_REG, self.timeouts.pram, |v| {
+            v & ESC_PRAM_CMD_AVAIL != 0
+        })
+        .await?;
+
+        // First 32-bit value, shifted to the address byte lane
+        let first_byte_position = (address & 0x03) as usize;
+        let first_len = (4 - first_byte_position).min(len);
+        let mut first = [0u8; 4];
+        first[first_byte_position..first_byte_position + first_len]
+            .copy_from_slice(&buf[..first_len]);
+        self.write_32(ESC_PRAM_WR_FIFO_REG, u32::from_le_bytes(first))
+            .await?;
+
+        let rest = &buf[first_len..];
+        if rest.is_empty() {
+            return Ok(());
+        }
+
+        // Incrementing write of the remaining bytes, padded to whole DWORDs
+        let cmd = [
+            ESC_CMD_SERIAL_WRITE,
+            ((ESC_PRAM_WR_FIFO_REG >> 8) & 0xFF) as u8,
+            (ESC_PRAM_WR_FIFO_REG & 0xFF) as u8,
+        ];
+        let padding = [0u8; 3];
+        let pad_len = (4 - rest.len() % 4) % 4;
+
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&cmd),
+                Operation::Write(rest),
+                Operation::Write(&padding[..pad_len]),
+            ])
+            .await
+            .map_err(|_| EscError::Bus)
+    }
+
+    /// ESC read process data RAM function
+    async fn read_pram(&mut self, address: u16, buf: &mut [u8]) -> Result<(), EscError> {
+        let len = buf.len();
+
+        // Abort any ongoing PRAM read
+        self.write_32(ESC_PRAM_RD_CMD_REG, ESC_PRAM_CMD_ABORT)
+            .await?;
+        self.wait_until(ESC_PRAM_RD_CMD_REG, self.timeouts.pram, |v| {
+            v & ESC_PRAM_CMD_BUSY == 0
+        })
+        .await?;
+
+        // Set address and length
+        let value = ESC_PRAM_SIZE(len as u32) | ESC_PRAM_ADDR(address as u32);
+        self.write_32(ESC_PRAM_RD_ADDR_LEN_REG, value).await?;
+
+        // Start PRAM read and wait for FIFO to be ready
+        self.write_32(ESC_PRAM_RD_CMD_REG, ESC_PRAM_CMD_BUSY)
+            .await?;
+        self.wait_until(ESC_PRAM_RD_CMD_REG, self.timeouts.pram, |v| {
+            v & ESC_PRAM_CMD_AVAIL != 0
+        })
+        .await?;
+
+        // First 32-bit value, shifted from the address byte lane
+        let value = self.read_32(ESC_PRAM_RD_FIFO_REG).await?;
+        let first_byte_position = (address & 0x03) as usize;
+        let first_len = (4 - first_byte_position).min(len);
+        buf[..first_len].copy_from_slice(
+            &value.to_le_bytes()[first_byte_position..first_byte_position + first_len],
+        );
+
+        let rest = &mut buf[first_len..];
+        if rest.is_empty() {
+            return Ok(());
+        }
+
+        // FIFO read of the remaining bytes, drained as whole DWORDs
+        let cmd = [
+            ESC_CMD_FAST_READ,
+            ((ESC_PRAM_RD_FIFO_REG >> 8) & 0xFF) as u8,
+            (ESC_PRAM_RD_FIFO_REG & 0xFF) as u8,
+            ESC_CMD_FAST_READ_DUMMY,
+        ];
+        let mut padding = [0u8; 3];
+        let pad_len = (4 - rest.len() % 4) % 4;
+
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&cmd),
+                Operation::Read(rest),
+                Operation::Read(&mut padding[..pad_len]),
+            ])
+            .await
+            .map_err(|_| EscError::Bus)
+    }
+}
+
+impl<SPI: SpiDevice> AsyncEscDriver for Lan9252Async<SPI> {
+    async fn init(&mut self) -> Result<(), EscError> {
+        self.bring_up().await?;
+        Ok(())
+    }
+
+    async fn reset(&mut self) -> Result<(), EscError> {
+        self.soft_reset().await
+    }
+
+    /// Enable the IRQ pin (active low, push-pull) for EtherCAT AL event
+    /// requests. Which events assert it is selected by the AL event mask.
+    async fn enable_irq(&mut self) -> Result<(), EscError> {
+        self.write_32(
+            LAN9252_IRQ_CFG,
+            LAN9252_IRQ_CFG_IRQ_EN | LAN9252_IRQ_CFG_IRQ_BUF_TYPE,
+        )
+        .await?;
+        self.write_32(LAN9252_INT_EN, LAN9252_INT_ECAT).await
+    }
+
+    async fn disable_irq(&mut self) -> Result<(), EscError> {
+        self.write_32(LAN9252_INT_EN, 0).await?;
+        self.write_32(LAN9252_IRQ_CFG, 0).await
+    }
+
+    /// Write to ESC memory (CSR or PRAM depending on address)
+    async fn write(&mut self, mut address: u16, mut buf: &[u8]) -> Result<(), EscError> {
+        if address >= 0x1000 {
+            return self.write_pram(address, buf).await;
+        }
+
+        while !buf.is_empty() {
+            let size = csr_access_size(address, buf.len());
+            self.write_csr(address, &buf[..size]).await?;
+
+            buf = &buf[size..];
+            address += size as u16;
+        }
+        /* To mimic the ET1100 always providing AlEvent on every read or write */
+        self.update_AlEvent().await
+    }
+
+    /// Read from ESC memory (CSR or PRAM depending on address)
+    async fn read(&mut self, mut address: u16, mut buf: &mut [u8]) -> Result<(), EscError> {
+        if address >= 0x1000 {
+            return self.read_pram(address, buf).await;
+        }
+
+        while !buf.is_empty() {
+            let size = csr_access_size(address, buf.len());
+            self.read_csr(address, &mut buf[..size]).await?;
+
+            buf = &mut buf[size..];
+            address += size as u16;
+        }
+        /* To mimic the ET1100 always providing AlEvent on every read or write */
+        self.update_AlEvent().await
+    }
+}
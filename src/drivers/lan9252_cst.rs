@@ -56,12 +56,51 @@ pub const fn ESC_CSR_CMD_SIZE(x: u32) -> u32 {
     x << 16
 }
 
+/// Number of bytes a single CSR access may cover at `address`
+/// (LAN9252 datasheet, Table 12-14 "EtherCAT CSR Address VS size")
+#[inline(always)]
+pub const fn csr_access_size(address: u16, len: usize) -> usize {
+    // Max 4 bytes at a time
+    let size = if len > 4 { 4 } else { len };
+
+    if address & 0x01 != 0 {
+        // Odd address: byte access only
+        1
+    } else if address & 0x02 != 0 {
+        // 1xb address: word access if size is even
+        if size & 0x01 != 0 {
+            1
+        } else {
+            2
+        }
+    } else if size == 3 {
+        // Size 3 is not valid
+        1
+    } else {
+        size
+    }
+}
+
 // Reset control
 pub const ESC_RESET_CTRL_REG: u16 = 0x1F8;
 pub const ESC_RESET_CTRL_RST: u32 = BIT(6);
+pub const ESC_DIGITAL_RST: u32 = BIT(0);
+
+// Chip identification
+pub const ESC_ID_REV_REG: u16 = 0x050;
+pub const LAN9252_ID_REV: u16 = 0x9252;
+
+// Byte order test and hardware ready
+pub const ESC_TEST_VALUE: u32 = 0x87654321;
+pub const ESC_HW_CFG_REG: u16 = 0x074;
+pub const ESC_READY: u32 = BIT(27);
+
+// Minimum RST# low time (LAN9252 datasheet, trstia)
+pub const LAN9252_RST_PULSE_US: u32 = 200;
 
 // Sync status
 pub const ESCREG_SYNC0_STATUS: u16 = 0x098E;
+pub const ESCREG_SYNC1_STATUS: u16 = 0x098F;
 
 // LAN9252 direct registers
 pub const LAN9252_IRQ_CFG: u16 = 0x54;
@@ -69,12 +108,20 @@ pub const LAN9252_INT_EN: u16 = 0x5C;
 pub const LAN9252_INT_STS: u16 = 0x58;
 pub const LAN9252_BYTE_TEST: u16 = 0x64;
 
+// IRQ_CFG bits (IRQ pin: enable, polarity, buffer type)
+pub const LAN9252_IRQ_CFG_IRQ_EN: u32 = BIT(8);
+pub const LAN9252_IRQ_CFG_IRQ_POL: u32 = BIT(4); // 1 = active high
+pub const LAN9252_IRQ_CFG_IRQ_BUF_TYPE: u32 = BIT(0); // 1 = push-pull
+
+// INT_EN / INT_STS bits
+pub const LAN9252_INT_ECAT: u32 = BIT(0); // EtherCAT (AL event request) interrupt
+
 // ALEVENT_MASK
 pub const ALEVENT_MASK: u32 =
     ESCREG_ALEVENT_CONTROL | ESCREG_ALEVENT_SMCHANGE | ESCREG_ALEVENT_SM0 | ESCREG_ALEVENT_SM1;
 
-// ⚠️
+// AL event request bits (0x0220), same values as esc.h
 pub const ESCREG_ALEVENT_CONTROL: u32 = BIT(0);
-pub const ESCREG_ALEVENT_SMCHANGE: u32 = BIT(1);
-pub const ESCREG_ALEVENT_SM0: u32 = BIT(2);
-pub const ESCREG_ALEVENT_SM1: u32 = BIT(3);
+pub const ESCREG_ALEVENT_SMCHANGE: u32 = BIT(4);
+pub const ESCREG_ALEVENT_SM0: u32 = BIT(8);
+pub const ESCREG_ALEVENT_SM1: u32 = BIT(9);
@@ -1,8 +1,14 @@
 pub mod esc_c;
+pub mod lan9252;
+pub mod lan9252_async;
 pub mod lan9252_cst;
+#[cfg(feature = "embassy-stm32")]
 pub mod lan9252_embassy;
 
+pub use lan9252::*;
+pub use lan9252_async::*;
 pub use lan9252_cst::*;
+#[cfg(feature = "embassy-stm32")]
 pub use lan9252_embassy::*;
 
-pub use esc_c::set_driver;
+pub use esc_c::take_driver_error;
@@ -0,0 +1,251 @@
+//! SII EEPROM emulation (port of `esc_eep.c`): serves the EEPROM commands
+//! of an ESC configured for PDI emulated EEPROM (e.g. LAN9252 in MCU EEPROM
+//! mode) from an [`Eeprom`] backend registered with `EcatSlave::set_eeprom`.
+//!
+//! The master reads 8 (or 4) bytes and writes 2 bytes at a word address;
+//! a reload reads like a read at the address set by the ESC.
+
+use core::ptr::addr_of_mut;
+
+use embedded_storage::nor_flash::NorFlash;
+
+use crate::bindings::*;
+use crate::esc;
+use crate::soes::ESCvar;
+use crate::storage::{aligned, StorageError, CHUNK_SIZE};
+
+/// EEPROM control/status register (0x502) fields
+const EIGHT_BYTE_READ: u16 = 0x0040;
+const COMMAND_SHIFT: u16 = 8;
+const COMMAND_MASK: u16 = 0x0700;
+const CHECKSUM_ERROR: u16 = 0x0800;
+const LOADING: u16 = 0x1000;
+const ACK_ERROR: u16 = 0x2000;
+const WRITE_ERROR: u16 = 0x4000;
+const BUSY: u16 = 0x8000;
+
+const CMD_IDLE: u16 = 0;
+const CMD_READ: u16 = 1;
+const CMD_WRITE: u16 = 2;
+const CMD_RELOAD: u16 = 3;
+
+/// EEPROM address register (word address)
+const ADDRESS: u16 = ESCREG_EECONTSTAT as u16 + 2;
+const DATA: u16 = ESCREG_EEDATA as u16;
+
+/// Bytes per read command, 4 when the ESC does not report 8 byte reads
+const READ_SIZE: usize = 8;
+const WRITE_SIZE: usize = 2;
+
+/// Content of the emulated EEPROM, byte addressed
+pub trait Eeprom {
+    /// Read `buf` at `address`
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), StorageError>;
+
+    /// Write `data` at `address`
+    fn write(&mut self, address: u32, data: &[u8]) -> Result<(), StorageError>;
+
+    /// Persist the writes, called before the next read or reload and on AL
+    /// control events once the master wrote the EEPROM
+    fn flush(&mut self) -> Result<(), StorageError> {
+        Ok(())
+    }
+}
+
+/// Copy `image` at `address` to `buf`, erased value past the end
+fn read_image(image: &[u8], address: u32, buf: &mut [u8]) -> Result<(), StorageError> {
+    let address = address as usize;
+    if address >= image.len() {
+        return Err(StorageError::Capacity);
+    }
+    let len = buf.len().min(image.len() - address);
+    buf[..len].copy_from_slice(&image[address..address + len]);
+    buf[len..].fill(0xFF);
+    Ok(())
+}
+
+fn write_image(image: &mut [u8], address: u32, data: &[u8]) -> Result<(), StorageError> {
+    let address = address as usize;
+    let target = image
+        .get_mut(address..address + data.len())
+        .ok_or(StorageError::Capacity)?;
+    target.copy_from_slice(data);
+    Ok(())
+}
+
+/// EEPROM in RAM, lost at power off: an SII image built in the firmware
+pub struct RamEeprom {
+    image: &'static mut [u8],
+}
+
+impl RamEeprom {
+    pub fn new(image: &'static mut [u8]) -> Self {
+        Self { image }
+    }
+}
+
+impl Eeprom for RamEeprom {
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), StorageError> {
+        read_image(self.image, address, buf)
+    }
+
+    fn write(&mut self, address: u32, data: &[u8]) -> Result<(), StorageError> {
+        write_image(self.image, address, data)
+    }
+}
+
+/// EEPROM in a NOR flash area at `offset`, aligned to the erase size and
+/// served from the RAM copy `image`. Writes are saved on [`Eeprom::flush`].
+pub struct FlashEeprom<F: NorFlash> {
+    flash: F,
+    offset: u32,
+    image: &'static mut [u8],
+    dirty: bool,
+}
+
+impl<F: NorFlash> FlashEeprom<F> {
+    /// Load the EEPROM from flash. An erased area keeps `image`, the
+    /// default content.
+    pub fn new(mut flash: F, offset: u32, image: &'static mut [u8]) -> Result<Self, StorageError> {
+        let mut chunk = [0; CHUNK_SIZE];
+        assert!(aligned(offset as usize, F::ERASE_SIZE) && aligned(chunk.len(), F::READ_SIZE));
+        assert!(aligned(image.len(), F::WRITE_SIZE) && aligned(image.len(), F::READ_SIZE));
+        if offset as usize + image.len() > flash.capacity() {
+            return Err(StorageError::Capacity);
+        }
+
+        // Check the area is erased a chunk at a time, then load it
+        let mut erased = true;
+        for position in (0..image.len()).step_by(chunk.len()) {
+            let len = (image.len() - position).min(chunk.len());
+            flash
+                .read(offset + position as u32, &mut chunk[..len])
+                .map_err(|_| StorageError::Flash)?;
+            erased &= chunk[..len].iter().all(|byte| *byte == 0xFF);
+        }
+        if !erased {
+            flash.read(offset, image).map_err(|_| StorageError::Flash)?;
+        }
+        Ok(Self {
+            flash,
+            offset,
+            image,
+            dirty: false,
+        })
+    }
+}
+
+impl<F: NorFlash> Eeprom for FlashEeprom<F> {
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), StorageError> {
+        read_image(self.image, address, buf)
+    }
+
+    fn write(&mut self, address: u32, data: &[u8]) -> Result<(), StorageError> {
+        write_image(self.image, address, data)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), StorageError> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let end = self.image.len().next_multiple_of(F::ERASE_SIZE) as u32;
+        self.flash
+            .erase(self.offset, self.offset + end)
+            .map_err(|_| StorageError::Flash)?;
+        self.flash
+            .write(self.offset, self.image)
+            .map_err(|_| StorageError::Flash)?;
+        self.dirty = false;
+        defmt::info!("EEPROM saved");
+        Ok(())
+    }
+}
+
+static mut EEPROM: Option<&'static mut dyn Eeprom> = None;
+
+fn eeprom() -> Option<&'static mut dyn Eeprom> {
+    // SAFETY: set before the stack runs, then used from the slave polling
+    unsafe { (*addr_of_mut!(EEPROM)).as_deref_mut() }
+}
+
+/// Register the emulated EEPROM
+pub(crate) fn set(eeprom: &'static mut dyn Eeprom) {
+    unsafe { *addr_of_mut!(EEPROM) = Some(eeprom) };
+}
+
+/// AL events served by the emulation, for the interrupt mask
+pub(crate) fn events() -> u32 {
+    match eeprom() {
+        Some(_) => ESCREG_ALEVENT_EEP,
+        None => 0,
+    }
+}
+
+/// True when an EEPROM is registered
+pub(crate) fn enabled() -> bool {
+    eeprom().is_some()
+}
+
+fn flush(eeprom: &mut dyn Eeprom) {
+    if let Err(err) = eeprom.flush() {
+        defmt::error!("EEPROM save failed: {}", err);
+    }
+}
+
+/// Serve a pending EEPROM command (AL event 0x20), acknowledged by writing
+/// back the command with its error bits
+pub(crate) fn process() {
+    let Some(eeprom) = eeprom() else {
+        return;
+    };
+    let alevent = unsafe { ESCvar.ALevent } as u32;
+    if alevent & ESCREG_ALEVENT_CONTROL != 0 {
+        flush(eeprom);
+    }
+    if alevent & ESCREG_ALEVENT_EEP == 0 {
+        return;
+    }
+
+    let mut status = esc::read_u16(ESCREG_EECONTSTAT as u16);
+    if status & BUSY == 0 {
+        return;
+    }
+    let address = esc::read_u32(ADDRESS).saturating_mul(2);
+    status &= !(CHECKSUM_ERROR | LOADING | ACK_ERROR | WRITE_ERROR);
+
+    let result = match (status & COMMAND_MASK) >> COMMAND_SHIFT {
+        CMD_IDLE => Ok(()),
+        CMD_READ | CMD_RELOAD => {
+            flush(eeprom);
+            let mut data = [0; READ_SIZE];
+            let len = match status & EIGHT_BYTE_READ {
+                0 => 4,
+                _ => READ_SIZE,
+            };
+            eeprom
+                .read(address, &mut data[..len])
+                .map(|()| esc::write(DATA, &data[..len]))
+        }
+        CMD_WRITE => {
+            let mut data = [0; WRITE_SIZE];
+            esc::read(DATA, &mut data);
+            eeprom.write(address, &data)
+        }
+        _ => {
+            status |= ACK_ERROR;
+            Ok(())
+        }
+    };
+    if let Err(err) = result {
+        defmt::debug!(
+            "EEPROM command 0x{:04x} at {} failed: {}",
+            status,
+            address,
+            err
+        );
+        status |= ACK_ERROR;
+    }
+    esc::write_u16(ESCREG_EECONTSTAT as u16, status);
+}
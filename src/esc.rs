@@ -0,0 +1,234 @@
+//! ESC register and sync manager access (port of the non state machine
+//! part of `esc.c`)
+
+use core::ptr::{addr_of, addr_of_mut};
+
+use crate::al::AlStatusCode;
+use crate::bindings::*;
+use crate::drivers::esc_c::{ESC_read, ESC_write};
+use crate::soes::ESCvar;
+
+/// Read `buf.len()` bytes at `address` through the bound driver
+pub(crate) fn read(address: u16, buf: &mut [u8]) {
+    // SAFETY: the pointer and length come from the borrowed slice
+    unsafe { ESC_read(address, buf.as_mut_ptr(), buf.len()) };
+}
+
+/// Write `buf` at `address` through the bound driver
+pub(crate) fn write(address: u16, buf: &[u8]) {
+    // SAFETY: the pointer and length come from the borrowed slice
+    unsafe { ESC_write(address, buf.as_ptr(), buf.len()) };
+}
+
+pub(crate) fn read_u8(address: u16) -> u8 {
+    let mut buf = [0u8; 1];
+    read(address, &mut buf);
+    buf[0]
+}
+
+pub(crate) fn read_u16(address: u16) -> u16 {
+    let mut buf = [0u8; 2];
+    read(address, &mut buf);
+    u16::from_le_bytes(buf)
+}
+
+pub(crate) fn read_u32(address: u16) -> u32 {
+    let mut buf = [0u8; 4];
+    read(address, &mut buf);
+    u32::from_le_bytes(buf)
+}
+
+pub(crate) fn write_u8(address: u16, value: u8) {
+    write(address, &[value]);
+}
+
+pub(crate) fn write_u16(address: u16, value: u16) {
+    write(address, &value.to_le_bytes());
+}
+
+pub(crate) fn write_u32(address: u16, value: u32) {
+    write(address, &value.to_le_bytes());
+}
+
+/// Read the AL event request register (0x220)
+pub fn al_event_read() -> u32 {
+    read_u32(ESCREG_ALEVENT as u16)
+}
+
+/// Write the AL event mask register (0x204)
+pub fn al_event_mask_write(mask: u32) {
+    write_u32(ESCREG_ALEVENTMASK as u16, mask);
+}
+
+/// Read the AL event mask register (0x204)
+pub fn al_event_mask_read() -> u32 {
+    read_u32(ESCREG_ALEVENTMASK as u16)
+}
+
+/// Watchdog status (0x440), bit 0 cleared when the PD watchdog expired
+pub fn wd_status() -> u8 {
+    read_u16(ESCREG_WDSTATUS as u16) as u8
+}
+
+/// SYNC out unit activation (0x981)
+pub fn sync_activation() -> u8 {
+    read_u8(ESCREG_SYNC_ACT as u16)
+}
+
+/// SYNC0 cycle time (0x9A0), ns
+pub fn sync0_cycle_time() -> u32 {
+    read_u32(ESCREG_SYNC0_CYCLE_TIME as u16)
+}
+
+/// SYNC1 cycle time (0x9A4), ns
+pub fn sync1_cycle_time() -> u32 {
+    read_u32(ESCREG_SYNC1_CYCLE_TIME as u16)
+}
+
+/// Copy of the cached sync manager `n` state
+pub(crate) fn sm(n: u8) -> _ESCsm {
+    unsafe { *addr_of!(ESCvar.SM[n as usize]) }
+}
+
+/// Sync manager `n` as the register layout (status, PDI control bytes)
+fn sm_regs(n: u8) -> *mut _ESCsm2 {
+    unsafe { addr_of_mut!(ESCvar.SM[n as usize]) as *mut _ESCsm2 }
+}
+
+/// Read the whole sync manager `n` configuration into `ESCvar.SM[n]`
+pub(crate) fn sm_read(n: u8) {
+    let mut buf = [0u8; core::mem::size_of::<_ESCsm>()];
+    read(ESCREG_SM0 as u16 + ((n as u16) << 3), &mut buf);
+    unsafe {
+        *addr_of_mut!(ESCvar.SM[n as usize]) = core::ptr::read_unaligned(buf.as_ptr() as *const _);
+    }
+}
+
+/// Acknowledge a sync manager activation event by reading the SM activate
+/// register (0x806 + n * 8)
+pub(crate) fn sm_ack(n: u8) {
+    read_u8(ESCREG_SM0ACTIVATE as u16 + ((n as u16) << 3));
+}
+
+/// Read the SM status register (0x805 + n * 8) into `ESCvar.SM[n]`. The
+/// activate register is left alone: reading it clears the SM change event,
+/// handled by `al::sm_act_event` which refreshes the repeat toggle.
+pub(crate) fn sm_status(n: u8) {
+    let status = read_u8(ESCREG_SM0STATUS as u16 + ((n as u16) << 3));
+    unsafe { (*sm_regs(n)).Status = status };
+}
+
+/// Write the PDI control byte of `ESCvar.SM[n]` (0x807 + n * 8)
+pub(crate) fn sm_write_pdi(n: u8) {
+    let pdi = unsafe { (*sm_regs(n)).ActPDI };
+    write_u8(ESCREG_SM0PDI as u16 + ((n as u16) << 3), pdi);
+}
+
+/// Activate sync manager `n` from the PDI side
+pub(crate) fn sm_enable(n: u8) {
+    unsafe { (*sm_regs(n)).ActPDI &= !(ESCREG_SMENABLE_BIT as u8) };
+    sm_write_pdi(n);
+}
+
+/// Deactivate sync manager `n` from the PDI side
+pub(crate) fn sm_disable(n: u8) {
+    unsafe { (*sm_regs(n)).ActPDI |= ESCREG_SMENABLE_BIT as u8 };
+    sm_write_pdi(n);
+}
+
+/// Acknowledge a repeat request on sync manager `n` with the ECAT side
+/// toggle
+pub(crate) fn sm_ack_repeat(n: u8, toggle: u8) {
+    unsafe { (*addr_of_mut!(ESCvar.SM[n as usize])).set_PDIrep(toggle) };
+    sm_write_pdi(n);
+}
+
+/// Clear the cached mailbox full flag of `ESCvar.SM[n]` once read
+pub(crate) fn sm_clear_mbx_full(n: u8) {
+    unsafe { (*addr_of_mut!(ESCvar.SM[n as usize])).set_MBXstat(0) };
+}
+
+/// Physical start address, length and control byte of `ESCvar.SM[n]`
+pub(crate) fn sm_config(n: u8) -> (u16, u16, u8) {
+    let sm = unsafe { *sm_regs(n) };
+    (u16::from_le(sm.PSA), u16::from_le(sm.Length), sm.Command)
+}
+
+/// ECAT side activation byte of `ESCvar.SM[n]`
+pub(crate) fn sm_activation(n: u8) -> u8 {
+    unsafe { (*sm_regs(n)).ActESC }
+}
+
+/// Read the configured station address (0x010)
+pub(crate) fn read_address() {
+    let address = read_u16(ESCREG_ADDRESS as u16);
+    unsafe { ESCvar.address = address };
+}
+
+/// Validate the DC setup when the SYNC unit is activated, through the
+/// `esc_check_dc_handler` hook
+pub(crate) fn check_dc() -> AlStatusCode {
+    let activation = sync_activation() as u32;
+    if activation & (ESCREG_SYNC_ACT_ACTIVATED | ESCREG_SYNC_AUTO_ACTIVATED) == 0 {
+        unsafe {
+            ESCvar.dcsync = 0;
+            ESCvar.synccounter = 0;
+        }
+        return AlStatusCode::NONE;
+    }
+
+    match unsafe { ESCvar.esc_check_dc_handler } {
+        Some(handler) => AlStatusCode(unsafe { handler() }),
+        None => AlStatusCode::DC_INVALID_SYNC_CONFIG,
+    }
+}
+
+/// Copy the application configuration to the stack
+pub(crate) fn config(cfg: &esc_cfg_t) {
+    unsafe {
+        ESCvar.use_interrupt = cfg.use_interrupt;
+        ESCvar.watchdogcnt = cfg.watchdog_cnt;
+
+        ESCvar.mb[0] = sm_cfg_t {
+            cfg_sma: MBX0_sma as u16,
+            cfg_sml: MBX0_sml as u16,
+            cfg_sme: MBX0_sme as u16,
+            cfg_smc: MBX0_smc as u8,
+            cfg_smact: 0,
+        };
+        ESCvar.mb[1] = sm_cfg_t {
+            cfg_sma: MBX1_sma as u16,
+            cfg_sml: MBX1_sml as u16,
+            cfg_sme: MBX1_sme as u16,
+            cfg_smc: MBX1_smc as u8,
+            cfg_smact: 0,
+        };
+        ESCvar.mbboot[0] = sm_cfg_t {
+            cfg_sma: MBX0_sma_b as u16,
+            cfg_sml: MBX0_sml_b as u16,
+            cfg_sme: MBX0_sme_b as u16,
+            cfg_smc: MBX0_smc_b as u8,
+            cfg_smact: 0,
+        };
+        ESCvar.mbboot[1] = sm_cfg_t {
+            cfg_sma: MBX1_sma_b as u16,
+            cfg_sml: MBX1_sml_b as u16,
+            cfg_sme: MBX1_sme_b as u16,
+            cfg_smc: MBX1_smc_b as u8,
+            cfg_smact: 0,
+        };
+
+        ESCvar.skip_default_initialization = cfg.skip_default_initialization;
+        ESCvar.set_defaults_hook = cfg.set_defaults_hook;
+        ESCvar.pre_state_change_hook = cfg.pre_state_change_hook;
+        ESCvar.post_state_change_hook = cfg.post_state_change_hook;
+        ESCvar.application_hook = cfg.application_hook;
+        ESCvar.safeoutput_override = cfg.safeoutput_override;
+        ESCvar.rxpdo_override = cfg.rxpdo_override;
+        ESCvar.txpdo_override = cfg.txpdo_override;
+        ESCvar.esc_hw_interrupt_enable = cfg.esc_hw_interrupt_enable;
+        ESCvar.esc_hw_interrupt_disable = cfg.esc_hw_interrupt_disable;
+        ESCvar.esc_hw_eep_handler = cfg.esc_hw_eep_handler;
+        ESCvar.esc_check_dc_handler = cfg.esc_check_dc_handler;
+    }
+}
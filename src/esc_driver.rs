@@ -1,8 +1,50 @@
+/// Errors reported by an ESC driver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, defmt::Format)]
+pub enum EscError {
+    /// The underlying bus (SPI...) reported a transfer error
+    Bus,
+    /// The ESC did not clear a BUSY bit / set a ready bit in time
+    Timeout,
+    /// The ESC does not answer as expected (byte test, chip ID...)
+    NotResponding,
+}
+
 pub trait EscDriver {
-    fn init(&mut self);
-    fn reset(&mut self);
-    fn write(&mut self, address: u16, buf: &[u8]);
-    fn read(&mut self, address: u16, buf: &mut [u8]);
+    fn init(&mut self) -> Result<(), EscError>;
+    fn reset(&mut self) -> Result<(), EscError>;
+    fn write(&mut self, address: u16, buf: &[u8]) -> Result<(), EscError>;
+    fn read(&mut self, address: u16, buf: &mut [u8]) -> Result<(), EscError>;
+
+    /// Route the ESC AL event requests to the interrupt line, if the
+    /// interface has one. Polling-only drivers keep the default no-op.
+    fn enable_irq(&mut self) -> Result<(), EscError> {
+        Ok(())
+    }
+
+    /// Stop driving the interrupt line
+    fn disable_irq(&mut self) -> Result<(), EscError> {
+        Ok(())
+    }
+}
+
+/// Async counterpart of [`EscDriver`], for drivers that can await the bus
+/// (e.g. DMA SPI) instead of spinning while the ESC is busy.
+#[allow(async_fn_in_trait)]
+pub trait AsyncEscDriver {
+    async fn init(&mut self) -> Result<(), EscError>;
+    async fn reset(&mut self) -> Result<(), EscError>;
+    async fn write(&mut self, address: u16, buf: &[u8]) -> Result<(), EscError>;
+    async fn read(&mut self, address: u16, buf: &mut [u8]) -> Result<(), EscError>;
+
+    /// See [`EscDriver::enable_irq`]
+    async fn enable_irq(&mut self) -> Result<(), EscError> {
+        Ok(())
+    }
+
+    /// See [`EscDriver::disable_irq`]
+    async fn disable_irq(&mut self) -> Result<(), EscError> {
+        Ok(())
+    }
 }
 
 //TODO: create a trait for initialized driver and use it for the slave obj
@@ -0,0 +1,581 @@
+//! ESI file generator (EtherCAT Slave Information XML, ETG.2000), host side
+//! with the `std` feature: identity, mailbox and CoE details, sync managers,
+//! PDOs, DC sync modes and the whole object dictionary with its data types, from the
+//! object dictionary and the [`SiiConfig`] of the SII image.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::bindings::*;
+use crate::od::{self, Access, Entry, Object};
+use crate::sii::{self, SiiConfig, SiiError};
+
+/// Language of the names (English)
+const LCID: &str = "1033";
+
+/// XML text with the markup characters escaped
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn hex(value: impl Into<u64>) -> String {
+    format!("#x{:X}", value.into())
+}
+
+/// Indented XML document
+#[derive(Default)]
+struct Xml {
+    text: String,
+    depth: usize,
+}
+
+impl Xml {
+    fn tag(&mut self, tag: &str, attributes: &[(&str, String)]) {
+        for _ in 0..self.depth {
+            self.text.push_str("  ");
+        }
+        self.text.push('<');
+        self.text.push_str(tag);
+        for (name, value) in attributes {
+            let _ = write!(self.text, " {name}=\"{}\"", escape(value));
+        }
+    }
+
+    fn open(&mut self, tag: &str, attributes: &[(&str, String)]) {
+        self.tag(tag, attributes);
+        self.text.push_str(">\n");
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        for _ in 0..self.depth {
+            self.text.push_str("  ");
+        }
+        let _ = writeln!(self.text, "</{tag}>");
+    }
+
+    /// Element holding `text`
+    fn element(&mut self, tag: &str, attributes: &[(&str, String)], text: &str) {
+        self.tag(tag, attributes);
+        let _ = writeln!(self.text, ">{}</{tag}>", escape(text));
+    }
+
+    fn empty(&mut self, tag: &str, attributes: &[(&str, String)]) {
+        self.tag(tag, attributes);
+        self.text.push_str(" />\n");
+    }
+}
+
+/// Base type name of a data type code, `None` for strings
+fn base_type(datatype: u16) -> Option<&'static str> {
+    Some(match datatype as u32 {
+        DTYPE_BOOLEAN => "BOOL",
+        DTYPE_INTEGER8 => "SINT",
+        DTYPE_INTEGER16 => "INT",
+        DTYPE_INTEGER32 => "DINT",
+        DTYPE_INTEGER64 => "LINT",
+        DTYPE_UNSIGNED8 => "USINT",
+        DTYPE_UNSIGNED16 => "UINT",
+        DTYPE_UNSIGNED32 => "UDINT",
+        DTYPE_UNSIGNED64 => "ULINT",
+        DTYPE_REAL32 => "REAL",
+        DTYPE_REAL64 => "LREAL",
+        DTYPE_BITARR8 => "BITARR8",
+        DTYPE_BITARR16 => "BITARR16",
+        DTYPE_BITARR32 => "BITARR32",
+        DTYPE_BIT1 => "BIT1",
+        DTYPE_BIT2 => "BIT2",
+        DTYPE_BIT3 => "BIT3",
+        DTYPE_BIT4 => "BIT4",
+        DTYPE_BIT5 => "BIT5",
+        DTYPE_BIT6 => "BIT6",
+        DTYPE_BIT7 => "BIT7",
+        DTYPE_BIT8 => "BIT8",
+        _ => return None,
+    })
+}
+
+/// Simple data type, declared once in the DataTypes list
+#[derive(Clone, Copy)]
+enum Simple {
+    Base(u16),
+    String(usize),
+    Bytes(usize),
+}
+
+/// Type name of an entry and its declaration
+fn simple_type(entry: &Entry) -> (String, Simple) {
+    let len = entry.byte_len();
+    match base_type(entry.datatype()) {
+        Some(name) if !entry.is_string() => (name.into(), Simple::Base(entry.bit_length())),
+        _ if entry.datatype() == od::DataType::VisibleString as u16 => {
+            (format!("STRING({len})"), Simple::String(len))
+        }
+        // Octet and unicode strings
+        _ => (
+            format!("ARRAY [0..{}] OF BYTE", len.saturating_sub(1)),
+            Simple::Bytes(len),
+        ),
+    }
+}
+
+/// Type name of an entry, recording the simple types used
+fn type_name(entry: &Entry, types: &mut BTreeMap<String, Simple>) -> String {
+    let (name, simple) = simple_type(entry);
+    if let Simple::Bytes(_) = simple {
+        types.insert("BYTE".into(), Simple::Base(8));
+    }
+    types.insert(name.clone(), simple);
+    name
+}
+
+/// Value of `raw` in the entry data type: signed or unsigned decimal, hex
+/// for reals and bit arrays
+fn number(entry: &Entry, raw: u64) -> String {
+    let bits = entry.bit_length().clamp(1, 64) as u32;
+    match entry.datatype() as u32 {
+        DTYPE_INTEGER8 | DTYPE_INTEGER16 | DTYPE_INTEGER24 | DTYPE_INTEGER32 | DTYPE_INTEGER64 => {
+            let shift = 64 - bits;
+            (((raw << shift) as i64) >> shift).to_string()
+        }
+        DTYPE_REAL32 | DTYPE_REAL64 | DTYPE_BITARR8 | DTYPE_BITARR16 | DTYPE_BITARR32 => {
+            format!("#x{raw:X}")
+        }
+        _ => raw.to_string(),
+    }
+}
+
+/// Readable/writable states of `access`, e.g. "PreOP_SafeOP"
+fn restrictions(access: u16, states: [u32; 3]) -> String {
+    ["PreOP", "SafeOP", "OP"]
+        .iter()
+        .zip(states)
+        .filter(|(_, state)| access as u32 & state != 0)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn flags(xml: &mut Xml, entry: &Entry) {
+    let access = entry.access();
+    let read = access.0 & Access::RO.0;
+    let write = access.0 & Access::WO.0;
+    let mut attributes = Vec::new();
+    if read != 0 && read != Access::RO.0 {
+        let states = [ATYPE_Rpre, ATYPE_Rsafe, ATYPE_Rop];
+        attributes.push(("ReadRestrictions", restrictions(read, states)));
+    }
+    if write != 0 && write != Access::WO.0 {
+        let states = [ATYPE_Wpre, ATYPE_Wsafe, ATYPE_Wop];
+        attributes.push(("WriteRestrictions", restrictions(write, states)));
+    }
+    let text = match (read != 0, write != 0) {
+        (true, true) => "rw",
+        (false, true) => "wo",
+        _ => "ro",
+    };
+
+    xml.open("Flags", &[]);
+    xml.element("Access", &attributes, text);
+    let mapping = match (
+        access.contains(Access(ATYPE_TXPDO as u16)),
+        access.contains(Access(ATYPE_RXPDO as u16)),
+    ) {
+        (true, true) => "TR",
+        (true, false) => "T",
+        (false, true) => "R",
+        (false, false) => "",
+    };
+    if !mapping.is_empty() {
+        xml.element("PdoMapping", &[], mapping);
+    }
+    if access.contains(Access::BACKUP) {
+        xml.element("Backup", &[], "1");
+    }
+    if access.contains(Access::SETTINGS) {
+        xml.element("Setting", &[], "1");
+    }
+    xml.close("Flags");
+}
+
+/// Limits and default value of an entry, in hex for PDO mappings and
+/// assignments
+fn info(xml: &mut Xml, entry: &Entry, hex: bool) {
+    if entry.is_string() {
+        let text = sii::text(entry);
+        if entry.datatype() == od::DataType::VisibleString as u16 && !text.is_empty() {
+            xml.element("DefaultString", &[], &text);
+        }
+        return;
+    }
+    if let Some(min) = entry.minimum() {
+        xml.element("MinValue", &[], &number(entry, min));
+    }
+    if let Some(max) = entry.maximum() {
+        xml.element("MaxValue", &[], &number(entry, max));
+    }
+    let default = match hex {
+        true => self::hex(entry.default_u64()),
+        false => number(entry, entry.default_u64()),
+    };
+    xml.element("DefaultValue", &[], &default);
+}
+
+fn sub_item(
+    xml: &mut Xml,
+    subindex: Option<u8>,
+    name: &str,
+    datatype: &str,
+    bits: usize,
+    offset: usize,
+    entry: &Entry,
+) {
+    xml.open("SubItem", &[]);
+    if let Some(subindex) = subindex {
+        xml.element("SubIdx", &[], &subindex.to_string());
+    }
+    xml.element("Name", &[], name);
+    xml.element("Type", &[], datatype);
+    xml.element("BitSize", &[], &bits.to_string());
+    xml.element("BitOffs", &[], &offset.to_string());
+    flags(xml, entry);
+    xml.close("SubItem");
+}
+
+fn entry_name(entry: &Entry) -> &'static str {
+    entry.name().to_str().unwrap_or_default()
+}
+
+fn object_name(object: &Object) -> &'static str {
+    object.name().to_str().unwrap_or_default()
+}
+
+/// Data type of a record or array object, named `DTxxxx`; returns its name
+/// and size in whole bytes. Subindex 0 takes 16 bits, as in the files of the
+/// SOES ESI tool.
+fn compound_type(
+    xml: &mut Xml,
+    object: &Object,
+    types: &mut BTreeMap<String, Simple>,
+) -> (String, usize) {
+    let name = format!("DT{:04X}", object.index());
+    let entries = object.entries();
+    let max = &entries[0];
+    let max_type = type_name(max, types);
+
+    if object.object_type() == OTYPE_ARRAY as u16 && entries.len() > 1 {
+        let element = &entries[1];
+        let bits = element.bit_length() as usize;
+        let count = entries.len() - 1;
+        let array = format!("{name}ARR");
+        xml.open("DataType", &[]);
+        xml.element("Name", &[], &array);
+        xml.element("BaseType", &[], &type_name(element, types));
+        xml.element("BitSize", &[], &(bits * count).to_string());
+        xml.open("ArrayInfo", &[]);
+        xml.element("LBound", &[], "1");
+        xml.element("Elements", &[], &count.to_string());
+        xml.close("ArrayInfo");
+        xml.close("DataType");
+
+        let size = (16 + bits * count).next_multiple_of(8);
+        xml.open("DataType", &[]);
+        xml.element("Name", &[], &name);
+        xml.element("BitSize", &[], &size.to_string());
+        sub_item(xml, Some(0), entry_name(max), &max_type, 8, 0, max);
+        sub_item(xml, None, "Elements", &array, bits * count, 16, element);
+        xml.close("DataType");
+        return (name, size);
+    }
+
+    let bits: usize = entries[1..]
+        .iter()
+        .map(|entry| entry.bit_length() as usize)
+        .sum();
+    let size = (16 + bits).next_multiple_of(8);
+    xml.open("DataType", &[]);
+    xml.element("Name", &[], &name);
+    xml.element("BitSize", &[], &size.to_string());
+    sub_item(xml, Some(0), entry_name(max), &max_type, 8, 0, max);
+    let mut offset = 16;
+    for entry in &entries[1..] {
+        let bits = entry.bit_length() as usize;
+        let datatype = type_name(entry, types);
+        sub_item(
+            xml,
+            Some(entry.subindex()),
+            entry_name(entry),
+            &datatype,
+            bits,
+            offset,
+            entry,
+        );
+        offset += bits;
+    }
+    xml.close("DataType");
+    (name, size)
+}
+
+/// Object list and the data types it uses
+fn dictionary(xml: &mut Xml) {
+    let mut types = BTreeMap::new();
+    let mut datatypes = Xml {
+        depth: xml.depth + 2,
+        ..Xml::default()
+    };
+    let mut objects = Xml {
+        depth: xml.depth + 2,
+        ..Xml::default()
+    };
+
+    for object in od::objects() {
+        let entries = object.entries();
+        let (datatype, size) = match object.object_type() as u32 {
+            OTYPE_VAR => (
+                type_name(&entries[0], &mut types),
+                entries[0].bit_length() as usize,
+            ),
+            _ => compound_type(&mut datatypes, object, &mut types),
+        };
+
+        objects.open("Object", &[]);
+        objects.element("Index", &[], &format!("#x{:04X}", object.index()));
+        objects.element("Name", &[], object_name(object));
+        objects.element("Type", &[], &datatype);
+        objects.element("BitSize", &[], &size.to_string());
+        objects.open("Info", &[]);
+        let hex = matches!(object.index(), 0x1600..=0x1BFF | 0x1C10..=0x1C2F);
+        if object.object_type() == OTYPE_VAR as u16 {
+            info(&mut objects, &entries[0], hex);
+        } else {
+            for entry in entries {
+                objects.open("SubItem", &[]);
+                objects.element("Name", &[], entry_name(entry));
+                objects.open("Info", &[]);
+                info(&mut objects, entry, hex && entry.subindex() > 0);
+                objects.close("Info");
+                objects.close("SubItem");
+            }
+        }
+        objects.close("Info");
+        if object.object_type() == OTYPE_VAR as u16 {
+            flags(&mut objects, &entries[0]);
+        }
+        objects.close("Object");
+    }
+
+    for (name, simple) in &types {
+        datatypes.open("DataType", &[]);
+        datatypes.element("Name", &[], name);
+        match *simple {
+            Simple::Base(bits) => datatypes.element("BitSize", &[], &bits.to_string()),
+            Simple::String(len) => datatypes.element("BitSize", &[], &(len * 8).to_string()),
+            Simple::Bytes(len) => {
+                datatypes.element("BaseType", &[], "BYTE");
+                datatypes.element("BitSize", &[], &(len * 8).to_string());
+                datatypes.open("ArrayInfo", &[]);
+                datatypes.element("LBound", &[], "0");
+                datatypes.element("Elements", &[], &len.to_string());
+                datatypes.close("ArrayInfo");
+            }
+        }
+        datatypes.close("DataType");
+    }
+
+    xml.open("Dictionary", &[]);
+    xml.open("DataTypes", &[]);
+    xml.text.push_str(&datatypes.text);
+    xml.close("DataTypes");
+    xml.open("Objects", &[]);
+    xml.text.push_str(&objects.text);
+    xml.close("Objects");
+    xml.close("Dictionary");
+}
+
+/// RxPdo or TxPdo elements of the PDOs assigned in `assign` to `sm`
+fn pdos(xml: &mut Xml, tag: &str, assign: u16, sm: u8) -> Result<(), SiiError> {
+    for pdo in sii::assigned_pdos(assign) {
+        // Mappings the master cannot change
+        let fixed = !pdo.entries()[0].access().any_write();
+        xml.open(
+            tag,
+            &[
+                ("Fixed", fixed.to_string()),
+                ("Mandatory", "true".into()),
+                ("Sm", sm.to_string()),
+            ],
+        );
+        xml.element("Index", &[], &format!("#x{:04X}", pdo.index()));
+        xml.element("Name", &[], object_name(pdo));
+        for (index, subindex, bits) in sii::mappings(pdo) {
+            xml.open("Entry", &[]);
+            xml.element("Index", &[], &format!("#x{index:04X}"));
+            xml.element("SubIndex", &[], &subindex.to_string());
+            xml.element("BitLen", &[], &bits.to_string());
+            // Padding has no name nor data type
+            if (index, subindex) != (0, 0) {
+                let entry = od::find(index)
+                    .and_then(|object| object.entry(subindex))
+                    .ok_or(SiiError::Mapping(index))?;
+                xml.element("Name", &[], entry_name(entry));
+                xml.element("DataType", &[], &simple_type(entry).0);
+            }
+            xml.close("Entry");
+        }
+        xml.close(tag);
+    }
+    Ok(())
+}
+
+/// Physical layer of the used ports: Y (MII) or K (E-Bus)
+fn physics(ports: u16) -> String {
+    let physics: String = (0..4)
+        .map(|port| match (ports >> (port * 4)) & 0xF {
+            1 => 'Y',
+            3 => 'K',
+            _ => ' ',
+        })
+        .collect();
+    physics.trim_end().into()
+}
+
+/// ESI file of the device described by the object dictionary and `config`
+pub fn xml(config: &SiiConfig) -> Result<String, SiiError> {
+    let mut xml = Xml::default();
+    xml.text
+        .push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.open("EtherCATInfo", &[]);
+    xml.open("Vendor", &[]);
+    xml.element("Id", &[], &hex(sii::identity(1)));
+    xml.element("Name", &[("LcId", LCID.into())], &config.vendor_name);
+    xml.close("Vendor");
+
+    xml.open("Descriptions", &[]);
+    xml.open("Groups", &[]);
+    xml.open("Group", &[]);
+    xml.element("Type", &[], &config.group);
+    xml.element("Name", &[("LcId", LCID.into())], &config.group_name);
+    xml.close("Group");
+    xml.close("Groups");
+
+    xml.open("Devices", &[]);
+    xml.open("Device", &[("Physics", physics(config.physical_ports))]);
+    xml.element(
+        "Type",
+        &[
+            ("ProductCode", hex(sii::identity(2))),
+            ("RevisionNo", hex(sii::identity(3))),
+        ],
+        &config.device_type,
+    );
+    xml.element("Name", &[("LcId", LCID.into())], &sii::device_name());
+    xml.element("GroupType", &[], &config.group);
+
+    // 0x1000 device type: profile number and additional information
+    let device_type = od::find(0x1000)
+        .and_then(|object| object.entry(0))
+        .map_or(0, Entry::default_value);
+    xml.open("Profile", &[]);
+    xml.element("ProfileNo", &[], &(device_type & 0xFFFF).to_string());
+    xml.element("AddInfo", &[], &(device_type >> 16).to_string());
+    dictionary(&mut xml);
+    xml.close("Profile");
+
+    let rxpdo = !sii::assigned_pdos(RX_PDO_OBJIDX as u16).is_empty();
+    let txpdo = !sii::assigned_pdos(TX_PDO_OBJIDX as u16).is_empty();
+    if rxpdo {
+        xml.element("Fmmu", &[], "Outputs");
+    }
+    if txpdo {
+        xml.element("Fmmu", &[], "Inputs");
+    }
+    xml.element("Fmmu", &[], "MBoxState");
+
+    let size = |assign| u32::from(sii::pdo_size(assign));
+    for (start, length, control, name) in [
+        (MBX0_sma, MBX0_sml, MBX0_smc, "MBoxOut"),
+        (MBX1_sma, MBX1_sml, MBX1_smc, "MBoxIn"),
+        (SM2_sma, size(RX_PDO_OBJIDX as u16), SM2_smc, "Outputs"),
+        (SM3_sma, size(TX_PDO_OBJIDX as u16), SM3_smc, "Inputs"),
+    ] {
+        let mut attributes = Vec::new();
+        if length != 0 {
+            attributes.push(("DefaultSize", length.to_string()));
+        }
+        attributes.push(("StartAddress", format!("#x{start:04X}")));
+        attributes.push(("ControlByte", format!("#x{control:02X}")));
+        attributes.push(("Enable", u8::from(length != 0).to_string()));
+        xml.element("Sm", &attributes, name);
+    }
+    pdos(&mut xml, "RxPdo", RX_PDO_OBJIDX as u16, 2)?;
+    pdos(&mut xml, "TxPdo", TX_PDO_OBJIDX as u16, 3)?;
+
+    xml.open("Mailbox", &[("DataLinkLayer", "true".into())]);
+    if config.mailbox_protocols & sii::MBX_EOE != 0 {
+        xml.empty("EoE", &[]);
+    }
+    if config.mailbox_protocols & sii::MBX_COE != 0 {
+        let detail = |bit: u8| (config.coe_details & bit != 0).to_string();
+        xml.empty(
+            "CoE",
+            &[
+                ("SdoInfo", detail(sii::COE_SDO_INFO)),
+                ("PdoAssign", detail(sii::COE_PDO_ASSIGN)),
+                ("PdoConfig", detail(sii::COE_PDO_CONFIG)),
+                ("PdoUpload", detail(sii::COE_UPLOAD_AT_STARTUP)),
+                ("CompleteAccess", detail(sii::COE_COMPLETE_ACCESS)),
+            ],
+        );
+    }
+    if config.mailbox_protocols & sii::MBX_FOE != 0 {
+        xml.empty("FoE", &[]);
+    }
+    xml.close("Mailbox");
+
+    // Sync modes of 0x1C32 / 0x1C33 and their cyclic unit (0x980) and
+    // SYNC0/SYNC1 (0x981) activation
+    xml.open("Dc", &[]);
+    for (name, desc, assign_activate) in [
+        ("FreeRun", "FreeRun/SM-Synchron", 0x0u32),
+        ("DC-SYNC0", "DC-Synchron (SYNC0)", 0x300),
+        ("DC-SYNC1", "DC-Synchron (SYNC0 + SYNC1)", 0x700),
+    ] {
+        xml.open("OpMode", &[]);
+        xml.element("Name", &[], name);
+        xml.element("Desc", &[], desc);
+        xml.element("AssignActivate", &[], &hex(assign_activate));
+        xml.close("OpMode");
+    }
+    xml.close("Dc");
+
+    // Config area words 0 to 6, without the checksum
+    let mut config_data = String::new();
+    for word in config.pdi.iter().chain(&[config.station_alias, 0, 0]) {
+        for byte in word.to_le_bytes() {
+            let _ = write!(config_data, "{byte:02X}");
+        }
+    }
+    xml.open("Eeprom", &[]);
+    xml.element("ByteSize", &[], &config.eeprom_size.to_string());
+    xml.element("ConfigData", &[], &config_data);
+    xml.close("Eeprom");
+
+    xml.close("Device");
+    xml.close("Devices");
+    xml.close("Descriptions");
+    xml.close("EtherCATInfo");
+    Ok(xml.text)
+}
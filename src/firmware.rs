@@ -0,0 +1,187 @@
+//! Firmware update over FoE in BOOT state: [`FirmwareUpdate`] is an FoE
+//! file streaming the image into the DFU partition of a bootloader,
+//! checking it once received and marking it for swap when the master takes
+//! the slave from BOOT back to INIT.
+//!
+//! The file sent is the firmware followed by its CRC-32 (IEEE 802.3, little
+//! endian).
+
+use embedded_storage::nor_flash::NorFlash;
+
+use crate::foe::{FoeError, FoeFile, FoeMode, FoeWrite};
+use crate::storage::{aligned, crc32, StorageError, CHUNK_SIZE};
+
+const CRC_SIZE: usize = 4;
+
+/// Bootloader side of the update, implemented by the application: e.g. a
+/// wrapper calling embassy-boot's `BlockingFirmwareState::mark_updated`
+pub trait Bootloader {
+    /// Swap in the firmware of `size` bytes at the start of the DFU
+    /// partition at the next boot. A signature can be checked first and the
+    /// device reset after.
+    fn mark_updated(&mut self, size: u32) -> Result<(), StorageError>;
+}
+
+/// FoE file, written only in BOOT state, receiving the firmware into the
+/// DFU partition `dfu`
+pub struct FirmwareUpdate<F: NorFlash, B: Bootloader> {
+    name: &'static str,
+    password: u32,
+    dfu: F,
+    bootloader: B,
+    // Bytes erased from the start of the partition
+    erased: usize,
+    // Bytes written, full chunks
+    written: usize,
+    chunk: [u8; CHUNK_SIZE],
+    used: usize,
+    // Size of the firmware received and checked, marked when BOOT is left
+    ready: Option<u32>,
+}
+
+impl<F: NorFlash, B: Bootloader> FirmwareUpdate<F, B> {
+    pub fn new(name: &'static str, dfu: F, bootloader: B) -> Self {
+        assert!(aligned(CHUNK_SIZE, F::WRITE_SIZE) && aligned(CHUNK_SIZE, F::READ_SIZE));
+        assert!(aligned(F::ERASE_SIZE, CHUNK_SIZE));
+        Self {
+            name,
+            password: 0,
+            dfu,
+            bootloader,
+            erased: 0,
+            written: 0,
+            chunk: [0xFF; CHUNK_SIZE],
+            used: 0,
+            ready: None,
+        }
+    }
+
+    /// Password of the FoE write request
+    pub fn password(mut self, password: u32) -> Self {
+        self.password = password;
+        self
+    }
+
+    /// Write the chunk, padded with the erased value
+    fn flush(&mut self) -> Result<(), FoeError> {
+        let len = self.used.next_multiple_of(F::WRITE_SIZE);
+        self.chunk[self.used..].fill(0xFF);
+        self.dfu
+            .write(self.written as u32, &self.chunk[..len])
+            .map_err(|_| FoeError::ProgramError)?;
+        self.written += self.used;
+        self.used = 0;
+        Ok(())
+    }
+
+    /// Read back the received file and check the firmware CRC
+    fn verify(&mut self) -> Result<u32, FoeError> {
+        let total = self.written;
+        let size = total.checked_sub(CRC_SIZE).ok_or(FoeError::Checksum)?;
+        let mut crc = 0;
+        let mut trailer = [0; CRC_SIZE];
+        for position in (0..total).step_by(CHUNK_SIZE) {
+            let len = (total - position).min(CHUNK_SIZE);
+            self.dfu
+                .read(
+                    position as u32,
+                    &mut self.chunk[..len.next_multiple_of(F::READ_SIZE)],
+                )
+                .map_err(|_| FoeError::ProgramError)?;
+            let data = size.saturating_sub(position).min(len);
+            crc = crc32(crc, &self.chunk[..data]);
+            for (n, byte) in self.chunk[data..len].iter().enumerate() {
+                trailer[position + data + n - size] = *byte;
+            }
+        }
+        if crc != u32::from_le_bytes(trailer) {
+            defmt::warn!("Firmware CRC mismatch");
+            return Err(FoeError::Checksum);
+        }
+        Ok(size as u32)
+    }
+}
+
+impl<F: NorFlash, B: Bootloader> FoeFile for FirmwareUpdate<F, B> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn password(&self) -> u32 {
+        self.password
+    }
+
+    fn boot_only(&self) -> bool {
+        true
+    }
+
+    fn open(&mut self, mode: FoeMode) -> Result<(), FoeError> {
+        if mode == FoeMode::Read {
+            return Err(FoeError::Access);
+        }
+        self.erased = 0;
+        self.written = 0;
+        self.used = 0;
+        self.ready = None;
+        Ok(())
+    }
+
+    fn write(&mut self, offset: u32, data: &[u8]) -> Result<FoeWrite, FoeError> {
+        let end = offset as usize + data.len();
+        if offset as usize != self.written + self.used {
+            return Err(FoeError::ProgramError);
+        }
+        if end > self.dfu.capacity() {
+            return Err(FoeError::DiskFull);
+        }
+
+        // Erase a block at a time, the master sends the data again
+        if end.next_multiple_of(CHUNK_SIZE) > self.erased {
+            let block = self.erased as u32;
+            self.dfu
+                .erase(block, block + F::ERASE_SIZE as u32)
+                .map_err(|_| FoeError::ProgramError)?;
+            self.erased += F::ERASE_SIZE;
+            let blocks = |bytes: usize| (bytes / F::ERASE_SIZE).min(u16::MAX as usize) as u16;
+            return Ok(FoeWrite::Busy {
+                done: blocks(self.erased),
+                entire: blocks(self.dfu.capacity()),
+            });
+        }
+
+        let mut data = data;
+        while !data.is_empty() {
+            let n = data.len().min(CHUNK_SIZE - self.used);
+            self.chunk[self.used..self.used + n].copy_from_slice(&data[..n]);
+            self.used += n;
+            data = &data[n..];
+            if self.used == CHUNK_SIZE {
+                self.flush()?;
+            }
+        }
+        Ok(FoeWrite::Done)
+    }
+
+    fn close(&mut self, complete: bool) -> Result<(), FoeError> {
+        if !complete {
+            defmt::warn!("Firmware update aborted");
+            return Ok(());
+        }
+        if self.used > 0 {
+            self.flush()?;
+        }
+        let size = self.verify()?;
+        defmt::info!("Firmware received ({} bytes)", size);
+        self.ready = Some(size);
+        Ok(())
+    }
+
+    fn boot_exit(&mut self) {
+        if let Some(size) = self.ready.take() {
+            match self.bootloader.mark_updated(size) {
+                Ok(()) => defmt::info!("Firmware marked for update"),
+                Err(err) => defmt::error!("Firmware update mark failed: {}", err),
+            }
+        }
+    }
+}
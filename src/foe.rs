@@ -0,0 +1,441 @@
+//! File over EtherCAT (port of `esc_foe.c`): read and write requests served
+//! by the application files registered with `EcatSlave::add_foe_file`.
+//!
+//! A write is acknowledged packet by packet and ends with a packet shorter
+//! than the mailbox allows; a read is sent the same way and ends once the
+//! master acknowledges the short packet. A file not ready for data answers
+//! [`FoeWrite::Busy`] and the master sends the packet again.
+
+use core::ptr::addr_of_mut;
+
+use crate::al::{self, AlState};
+use crate::bindings::*;
+use crate::mbx::{MbxError, MbxHeader, MbxOut, MbxProtocol, MbxType, MAX_MBX_SIZE};
+
+/// Number of files that can be registered
+pub const MAX_FOE_FILES: usize = 4;
+
+/// Opcode, reserved, password / packet number / error code
+const FOE_HEADER_SIZE: usize = 6;
+
+const OP_READ: u8 = FOE_OP_RRQ as u8;
+const OP_WRITE: u8 = FOE_OP_WRQ as u8;
+const OP_DATA: u8 = FOE_OP_DATA as u8;
+const OP_ACK: u8 = FOE_OP_ACK as u8;
+const OP_ERROR: u8 = FOE_OP_ERR as u8;
+const OP_BUSY: u8 = FOE_OP_BUSY as u8;
+
+/// FoE error codes (ETG.1000.6)
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum FoeError {
+    NotDefined = FOE_ERR_NOTDEFINED,
+    NotFound = FOE_ERR_NOTFOUND,
+    Access = FOE_ERR_ACCESS,
+    DiskFull = FOE_ERR_DISKFULL,
+    /// Request not valid in the transfer state
+    Illegal = FOE_ERR_ILLEGAL,
+    PacketNumber = FOE_ERR_PACKETNO,
+    Exists = FOE_ERR_EXISTS,
+    NoUser = FOE_ERR_NOUSER,
+    BootstrapOnly = FOE_ERR_BOOTSTRAPONLY,
+    NotInBootstrap = FOE_ERR_NOTINBOOTSTRAP,
+    /// Wrong password
+    NoRights = FOE_ERR_NORIGHTS,
+    ProgramError = FOE_ERR_PROGERROR,
+    Checksum = FOE_ERR_CHECKSUM,
+}
+
+impl FoeError {
+    /// Error code sent to the master
+    pub const fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// Error text sent with the code
+    pub const fn text(self) -> &'static str {
+        match self {
+            Self::NotDefined => "Not defined",
+            Self::NotFound => "Not found",
+            Self::Access => "Access denied",
+            Self::DiskFull => "Disk full",
+            Self::Illegal => "Illegal",
+            Self::PacketNumber => "Packet number wrong",
+            Self::Exists => "Already exists",
+            Self::NoUser => "No user",
+            Self::BootstrapOnly => "Bootstrap only",
+            Self::NotInBootstrap => "Not in bootstrap",
+            Self::NoRights => "No rights",
+            Self::ProgramError => "Program error",
+            Self::Checksum => "Checksum error",
+        }
+    }
+}
+
+/// Transfer direction, seen from the master
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum FoeMode {
+    /// The master reads the file
+    Read,
+    /// The master writes the file
+    Write,
+}
+
+/// Outcome of [`FoeFile::write`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum FoeWrite {
+    /// Data taken
+    Done,
+    /// Data not taken, e.g. while erasing: the master is told `done` of
+    /// `entire` and sends the packet again
+    Busy { done: u16, entire: u16 },
+}
+
+/// File served over FoE. A request is matched on [`Self::name`] and
+/// [`Self::password`] before the file is opened.
+pub trait FoeFile {
+    /// Name requested by the master
+    fn name(&self) -> &str;
+
+    /// Password of the requests, 0 when not used
+    fn password(&self) -> u32 {
+        0
+    }
+
+    /// Written only in BOOT state, e.g. firmware files
+    fn boot_only(&self) -> bool {
+        false
+    }
+
+    /// Transfer started
+    fn open(&mut self, _mode: FoeMode) -> Result<(), FoeError> {
+        Ok(())
+    }
+
+    /// Read the file at `offset` into `buf`, return the number of bytes
+    /// read, less than `buf` at the end of the file
+    fn read(&mut self, _offset: u32, _buf: &mut [u8]) -> Result<usize, FoeError> {
+        Err(FoeError::Access)
+    }
+
+    /// Write `data` at `offset`
+    fn write(&mut self, _offset: u32, _data: &[u8]) -> Result<FoeWrite, FoeError> {
+        Err(FoeError::Access)
+    }
+
+    /// Transfer ended, `complete` false when it was aborted. An error
+    /// fails a write instead of acknowledging its last packet.
+    fn close(&mut self, _complete: bool) -> Result<(), FoeError> {
+        Ok(())
+    }
+
+    /// BOOT state left for INIT, e.g. to hand a firmware to the bootloader
+    fn boot_exit(&mut self) {}
+}
+
+/// Transfer in progress
+#[derive(Clone, Copy)]
+enum State {
+    Idle,
+    /// Waiting for the data packet following `packet`
+    Write {
+        file: usize,
+        packet: u32,
+        offset: u32,
+    },
+    /// Waiting for the ack of `packet`, `len` bytes at `offset`
+    Read {
+        file: usize,
+        packet: u32,
+        offset: u32,
+        len: usize,
+    },
+}
+
+/// Built-in FoE server
+pub(crate) struct Foe {
+    files: [Option<&'static mut dyn FoeFile>; MAX_FOE_FILES],
+    state: State,
+    /// Data packet read from the file
+    buf: [u8; MAX_MBX_SIZE],
+}
+
+static mut FOE: Foe = Foe {
+    files: [const { None }; MAX_FOE_FILES],
+    state: State::Idle,
+    buf: [0; MAX_MBX_SIZE],
+};
+
+fn foe() -> &'static mut Foe {
+    // SAFETY: only used from the mailbox service
+    unsafe { &mut *addr_of_mut!(FOE) }
+}
+
+/// FoE server, once a file is registered
+pub(crate) fn handler() -> Option<&'static mut Foe> {
+    let foe = foe();
+    foe.files.iter().any(Option::is_some).then_some(foe)
+}
+
+/// Register a file
+pub(crate) fn add_file(file: &'static mut dyn FoeFile) -> Result<(), MbxError> {
+    let slot = foe()
+        .files
+        .iter_mut()
+        .find(|slot| slot.is_none())
+        .ok_or(MbxError::NO_MORE_MEMORY)?;
+    *slot = Some(file);
+    Ok(())
+}
+
+/// Tell the files BOOT was left
+pub(crate) fn boot_exit() {
+    for file in foe().files.iter_mut().flatten() {
+        file.boot_exit();
+    }
+}
+
+/// Queue an FoE frame: `fill` writes the data after the header and returns
+/// its length
+fn send(out: &mut MbxOut, opcode: u8, value: u32, fill: impl FnOnce(&mut [u8]) -> usize) {
+    let result = out.send(MbxType::FOE, |data| {
+        data[0] = opcode;
+        data[1] = 0;
+        data[2..FOE_HEADER_SIZE].copy_from_slice(&value.to_le_bytes());
+        FOE_HEADER_SIZE + fill(&mut data[FOE_HEADER_SIZE..])
+    });
+    if result.is_err() {
+        defmt::warn!("FoE response dropped, no mailbox buffer");
+    }
+}
+
+fn send_ack(out: &mut MbxOut, packet: u32) {
+    send(out, OP_ACK, packet, |_| 0);
+}
+
+/// Largest data packet with the active mailbox configuration
+fn packet_size(out: &MbxOut) -> usize {
+    out.data_size() - FOE_HEADER_SIZE
+}
+
+impl Foe {
+    fn file(&mut self, file: usize) -> &mut dyn FoeFile {
+        &mut **self.files[file].as_mut().unwrap()
+    }
+
+    /// End the transfer in progress, if any
+    fn close(&mut self, complete: bool) -> Result<(), FoeError> {
+        let file = match self.state {
+            State::Idle => return Ok(()),
+            State::Write { file, .. } | State::Read { file, .. } => file,
+        };
+        self.state = State::Idle;
+        self.file(file).close(complete)
+    }
+
+    /// Abort the transfer and send `error` to the master
+    fn abort(&mut self, out: &mut MbxOut, error: FoeError) {
+        defmt::debug!("FoE error {}", error);
+        let _ = self.close(false);
+        send(out, OP_ERROR, error.code(), |data| {
+            let text = error.text().as_bytes();
+            let len = text.len().min(data.len());
+            data[..len].copy_from_slice(&text[..len]);
+            len
+        });
+    }
+
+    /// Read or write request: find the file and open it
+    fn open(&mut self, mode: FoeMode, password: u32, name: &[u8]) -> Result<usize, FoeError> {
+        if !matches!(self.state, State::Idle) {
+            return Err(FoeError::Illegal);
+        }
+        // Some masters send the name NUL terminated
+        let len = name.iter().rposition(|c| *c != 0).map_or(0, |n| n + 1);
+        let name = &name[..len];
+        let n = self
+            .files
+            .iter()
+            .position(|file| matches!(file, Some(file) if file.name().as_bytes() == name))
+            .ok_or(FoeError::NotFound)?;
+
+        let file = self.file(n);
+        if file.password() != password {
+            return Err(FoeError::NoRights);
+        }
+        if mode == FoeMode::Write && file.boot_only() && al::state() != Some(AlState::Boot) {
+            return Err(FoeError::NotInBootstrap);
+        }
+        file.open(mode)?;
+        Ok(n)
+    }
+
+    /// Send the data packet at `offset`
+    fn send_data(
+        &mut self,
+        out: &mut MbxOut,
+        file: usize,
+        packet: u32,
+        offset: u32,
+    ) -> Result<(), FoeError> {
+        // Read first, a failed read is answered with an error only
+        let size = packet_size(out);
+        let Self { files, buf, .. } = self;
+        let len = files[file]
+            .as_mut()
+            .unwrap()
+            .read(offset, &mut buf[..size])?;
+        let len = len.min(size);
+        send(out, OP_DATA, packet, |data| {
+            data[..len].copy_from_slice(&self.buf[..len]);
+            len
+        });
+        self.state = State::Read {
+            file,
+            packet,
+            offset,
+            len,
+        };
+        Ok(())
+    }
+
+    fn read_request(&mut self, out: &mut MbxOut, password: u32, name: &[u8]) {
+        let result = self
+            .open(FoeMode::Read, password, name)
+            .and_then(|file| self.send_data(out, file, 1, 0));
+        if let Err(error) = result {
+            self.abort(out, error);
+        }
+    }
+
+    fn write_request(&mut self, out: &mut MbxOut, password: u32, name: &[u8]) {
+        match self.open(FoeMode::Write, password, name) {
+            Ok(file) => {
+                self.state = State::Write {
+                    file,
+                    packet: 0,
+                    offset: 0,
+                };
+                send_ack(out, 0);
+            }
+            Err(error) => self.abort(out, error),
+        }
+    }
+
+    /// Data packet of a write
+    fn data(&mut self, out: &mut MbxOut, number: u32, data: &[u8]) -> Result<(), FoeError> {
+        let State::Write {
+            file,
+            packet,
+            offset,
+        } = self.state
+        else {
+            return Err(FoeError::Illegal);
+        };
+        if number != packet.wrapping_add(1) {
+            return Err(FoeError::PacketNumber);
+        }
+
+        match self.file(file).write(offset, data)? {
+            FoeWrite::Busy { done, entire } => {
+                // Done and entire take the place of the packet number
+                send(out, OP_BUSY, done as u32 | (entire as u32) << 16, |_| 0);
+                return Ok(());
+            }
+            FoeWrite::Done => {}
+        }
+
+        self.state = State::Write {
+            file,
+            packet: number,
+            offset: offset + data.len() as u32,
+        };
+        // A short packet ends the file
+        if data.len() < packet_size(out) {
+            self.close(true)?;
+        }
+        send_ack(out, number);
+        Ok(())
+    }
+
+    /// Ack of a read packet: send the next one, or end after the short one
+    fn ack(&mut self, out: &mut MbxOut, number: u32) -> Result<(), FoeError> {
+        let State::Read {
+            file,
+            packet,
+            offset,
+            len,
+        } = self.state
+        else {
+            return Err(FoeError::Illegal);
+        };
+        if number != packet {
+            return Err(FoeError::PacketNumber);
+        }
+        if len < packet_size(out) {
+            return self.close(true);
+        }
+        self.send_data(out, file, packet + 1, offset + len as u32)
+    }
+
+    /// Busy from the master during a read: send the last packet again
+    fn busy(&mut self, out: &mut MbxOut) -> Result<(), FoeError> {
+        let State::Read {
+            file,
+            packet,
+            offset,
+            ..
+        } = self.state
+        else {
+            return Err(FoeError::Illegal);
+        };
+        self.send_data(out, file, packet, offset)
+    }
+}
+
+impl MbxProtocol for Foe {
+    fn mbx_type(&self) -> MbxType {
+        MbxType::FOE
+    }
+
+    fn receive(
+        &mut self,
+        _header: &MbxHeader,
+        data: &[u8],
+        out: &mut MbxOut,
+    ) -> Result<(), MbxError> {
+        if data.len() < FOE_HEADER_SIZE {
+            return Err(MbxError::SIZE_TOO_SHORT);
+        }
+        let value = u32::from_le_bytes(data[2..FOE_HEADER_SIZE].try_into().unwrap());
+        let payload = &data[FOE_HEADER_SIZE..];
+
+        let result = match data[0] {
+            OP_READ => {
+                self.read_request(out, value, payload);
+                Ok(())
+            }
+            OP_WRITE => {
+                self.write_request(out, value, payload);
+                Ok(())
+            }
+            OP_DATA => self.data(out, value, payload),
+            OP_ACK => self.ack(out, value),
+            OP_BUSY => self.busy(out),
+            OP_ERROR => {
+                defmt::debug!("FoE aborted by the master: {:x}", value);
+                let _ = self.close(false);
+                Ok(())
+            }
+            _ => Err(FoeError::NotDefined),
+        };
+        if let Err(error) = result {
+            self.abort(out, error);
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        let _ = self.close(false);
+    }
+}
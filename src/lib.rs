@@ -4,13 +4,30 @@
 #[cfg(feature = "std")]
 extern crate std;
 
-pub mod bindings;
+/// SOES C types and constants, generated by build.rs
+pub mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
 
 pub mod soes;
 
 pub use soes::*;
+pub mod al;
+pub mod coe;
+pub mod dc;
 pub mod drivers;
+pub mod eeprom;
+pub mod esc;
 pub mod esc_driver;
+#[cfg(feature = "std")]
+pub mod esi;
+pub mod firmware;
+pub mod foe;
+pub mod mbx;
+pub mod od;
+#[cfg(feature = "std")]
+pub mod sii;
+pub mod storage;
 
 /// Simple Wrapper for tests (??)
 pub fn soes_version() -> u32 {
@@ -0,0 +1,486 @@
+//! Mailbox layer (port of `ESC_mbxprocess`): SM0/SM1 buffer handling,
+//! repeat requests and dispatch of received mailboxes to protocol handlers.
+//!
+//! CoE and FoE are served by the built-in [`crate::coe`] and [`crate::foe`]
+//! servers, other protocols
+//! plug in with [`MbxProtocol`] and [`register`].
+
+use core::ptr::addr_of_mut;
+
+use crate::bindings::*;
+use crate::coe;
+use crate::esc;
+use crate::foe;
+use crate::soes::{max, ESCvar};
+
+/// Mailbox header: length, address, channel/priority, type/counter
+pub const MBX_HEADER_SIZE: usize = 6;
+
+/// Largest mailbox, standard or bootstrap configuration
+pub const MAX_MBX_SIZE: usize = max(MBXSIZE as usize, MBXSIZEBOOT as usize);
+
+/// Number of response buffers
+pub const MBX_BUFFERS: usize = MBXBUFFERS as usize;
+
+/// Number of protocol handlers that can be registered
+pub const MAX_MBX_PROTOCOLS: usize = 4;
+
+/// Mailbox protocol type (header byte 5, low nibble)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct MbxType(pub u8);
+
+impl MbxType {
+    pub const ERR: Self = Self(MBXERR as u8);
+    pub const AOE: Self = Self(MBXAOE as u8);
+    pub const EOE: Self = Self(MBXEOE as u8);
+    pub const COE: Self = Self(MBXCOE as u8);
+    pub const FOE: Self = Self(MBXFOE as u8);
+    pub const SOE: Self = Self(0x05);
+    pub const VOE: Self = Self(0x0F);
+}
+
+/// Mailbox error reply detail (`MBXERR_*`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct MbxError(pub u16);
+
+impl MbxError {
+    pub const SYNTAX: Self = Self(MBXERR_SYNTAX as u16);
+    pub const UNSUPPORTED_PROTOCOL: Self = Self(MBXERR_UNSUPPORTEDPROTOCOL as u16);
+    pub const INVALID_CHANNEL: Self = Self(MBXERR_INVALIDCHANNEL as u16);
+    pub const SERVICE_NOT_SUPPORTED: Self = Self(MBXERR_SERVICENOTSUPPORTED as u16);
+    pub const INVALID_HEADER: Self = Self(MBXERR_INVALIDHEADER as u16);
+    pub const SIZE_TOO_SHORT: Self = Self(MBXERR_SIZETOOSHORT as u16);
+    pub const NO_MORE_MEMORY: Self = Self(MBXERR_NOMOREMEMORY as u16);
+    pub const INVALID_SIZE: Self = Self(MBXERR_INVALIDSIZE as u16);
+}
+
+/// Mailbox header
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct MbxHeader {
+    /// Length of the data following the header
+    pub length: u16,
+    pub address: u16,
+    pub channel: u8,
+    pub priority: u8,
+    pub mbx_type: MbxType,
+    /// Mailbox counter, 1..=7 (0 when not used by the master)
+    pub counter: u8,
+}
+
+impl MbxHeader {
+    /// Header of a response to the master, counter set when queued
+    pub const fn response(mbx_type: MbxType, length: u16) -> Self {
+        Self {
+            length,
+            address: 0,
+            channel: 0,
+            priority: 0,
+            mbx_type,
+            counter: 0,
+        }
+    }
+
+    pub const fn from_bytes(bytes: &[u8; MBX_HEADER_SIZE]) -> Self {
+        Self {
+            length: u16::from_le_bytes([bytes[0], bytes[1]]),
+            address: u16::from_le_bytes([bytes[2], bytes[3]]),
+            channel: bytes[4] & 0x3F,
+            priority: bytes[4] >> 6,
+            mbx_type: MbxType(bytes[5] & 0x0F),
+            counter: (bytes[5] >> 4) & 0x07,
+        }
+    }
+
+    pub const fn to_bytes(&self) -> [u8; MBX_HEADER_SIZE] {
+        let length = self.length.to_le_bytes();
+        let address = self.address.to_le_bytes();
+        [
+            length[0],
+            length[1],
+            address[0],
+            address[1],
+            (self.channel & 0x3F) | (self.priority << 6),
+            (self.mbx_type.0 & 0x0F) | ((self.counter & 0x07) << 4),
+        ]
+    }
+}
+
+/// Mailbox protocol handler (CoE, FoE, EoE or a vendor protocol)
+pub trait MbxProtocol {
+    /// Mailbox type served by the handler
+    fn mbx_type(&self) -> MbxType;
+
+    /// Handle a received mailbox, `data` being the bytes after the header.
+    /// Responses are queued in `out`, an error is answered with a mailbox
+    /// error reply.
+    fn receive(
+        &mut self,
+        header: &MbxHeader,
+        data: &[u8],
+        out: &mut MbxOut,
+    ) -> Result<(), MbxError>;
+
+    /// Called on every mailbox service, to continue multi-frame responses
+    fn poll(&mut self, _out: &mut MbxOut) {}
+
+    /// True while a transfer is in progress: the next request is left in
+    /// the receive mailbox until it is done
+    fn busy(&self) -> bool {
+        false
+    }
+
+    /// The mailboxes were stopped, drop any ongoing transfer
+    fn reset(&mut self) {}
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Buffer {
+    Free,
+    Queued,
+    /// Written to the send mailbox, not read by the master yet
+    Posted,
+    /// Last response read by the master, kept for a repeat request
+    Backup,
+}
+
+/// Response buffers, owned by the mailbox and lent to the protocol handlers
+pub struct MbxOut {
+    buffers: [[u8; MAX_MBX_SIZE]; MBX_BUFFERS],
+    state: [Buffer; MBX_BUFFERS],
+    // FIFO of queued buffers
+    queue: [usize; MBX_BUFFERS],
+    queued: usize,
+    posted: Option<usize>,
+    backup: Option<usize>,
+    // The posted buffer was taken back for a repeat, write it again once
+    // the backup is read
+    repost: bool,
+    counter: u8,
+    size: usize,
+}
+
+impl MbxOut {
+    const fn new() -> Self {
+        Self {
+            buffers: [[0; MAX_MBX_SIZE]; MBX_BUFFERS],
+            state: [Buffer::Free; MBX_BUFFERS],
+            queue: [0; MBX_BUFFERS],
+            queued: 0,
+            posted: None,
+            backup: None,
+            repost: false,
+            counter: 0,
+            size: MBXSIZE as usize,
+        }
+    }
+
+    /// Number of free response buffers
+    pub fn free(&self) -> usize {
+        self.state
+            .iter()
+            .filter(|state| **state == Buffer::Free)
+            .count()
+    }
+
+    /// Largest response payload with the active mailbox configuration
+    pub fn data_size(&self) -> usize {
+        self.size - MBX_HEADER_SIZE
+    }
+
+    /// True while a response is queued or not read by the master yet
+    pub fn pending(&self) -> bool {
+        self.queued > 0 || self.posted.is_some()
+    }
+
+    /// Queue a response to the master: `fill` writes the payload and
+    /// returns its length
+    pub fn send(
+        &mut self,
+        mbx_type: MbxType,
+        fill: impl FnOnce(&mut [u8]) -> usize,
+    ) -> Result<(), MbxError> {
+        let n = self
+            .state
+            .iter()
+            .position(|state| *state == Buffer::Free)
+            .ok_or(MbxError::NO_MORE_MEMORY)?;
+
+        let (header, data) = self.buffers[n][..self.size].split_at_mut(MBX_HEADER_SIZE);
+        let length = fill(data).min(data.len());
+        self.counter = self.counter % 7 + 1;
+        let response = MbxHeader {
+            counter: self.counter,
+            ..MbxHeader::response(mbx_type, length as u16)
+        };
+        header.copy_from_slice(&response.to_bytes());
+
+        self.state[n] = Buffer::Queued;
+        self.queue[self.queued] = n;
+        self.queued += 1;
+        Ok(())
+    }
+
+    /// Queue a mailbox error reply
+    pub fn error(&mut self, error: MbxError) -> Result<(), MbxError> {
+        self.send(MbxType::ERR, |data| {
+            data[..2].copy_from_slice(&0x01u16.to_le_bytes()); // type: error
+            data[2..4].copy_from_slice(&error.0.to_le_bytes());
+            4
+        })
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        if self.queued == 0 {
+            return None;
+        }
+        let n = self.queue[0];
+        self.queue.copy_within(1.., 0);
+        self.queued -= 1;
+        Some(n)
+    }
+
+    /// Write buffer `n` to the send mailbox (SM1)
+    fn write(&self, n: usize) {
+        let mb1 = unsafe { *ESCvar.activemb1 };
+        let sml = mb1.cfg_sml as usize;
+        let buf = &self.buffers[n];
+
+        let header = MbxHeader::from_bytes(buf[..MBX_HEADER_SIZE].try_into().unwrap());
+        let length = (header.length as usize).min(sml - MBX_HEADER_SIZE);
+        esc::write(mb1.cfg_sma, &buf[..MBX_HEADER_SIZE + length]);
+        // Writing the last byte hands the mailbox to the master
+        if MBX_HEADER_SIZE + length < sml {
+            esc::write_u8(mb1.cfg_sme, 0);
+        }
+    }
+}
+
+struct Mailbox {
+    rx: [u8; MAX_MBX_SIZE],
+    out: MbxOut,
+    // Send mailbox empty (read by the master)
+    sm1_free: bool,
+    // Repeat request toggle last acknowledged
+    toggle: u8,
+    // Counter of the last received mailbox
+    counter: u8,
+}
+
+impl Mailbox {
+    const fn new() -> Self {
+        Self {
+            rx: [0; MAX_MBX_SIZE],
+            out: MbxOut::new(),
+            sm1_free: true,
+            toggle: 0,
+            counter: 0,
+        }
+    }
+
+    /// The master read the send mailbox: the posted response becomes the
+    /// backup for repeat requests
+    fn read_acked(&mut self) {
+        let mb1 = unsafe { *ESCvar.activemb1 };
+        esc::write_u8(mb1.cfg_sma, 0);
+        self.sm1_free = true;
+
+        if let Some(backup) = self.out.backup.take() {
+            self.out.state[backup] = Buffer::Free;
+        }
+        if let Some(posted) = self.out.posted.take() {
+            if self.out.repost {
+                self.out.repost = false;
+                self.out.write(posted);
+                self.sm1_free = false;
+            }
+            self.out.state[posted] = Buffer::Backup;
+            self.out.backup = Some(posted);
+        }
+    }
+
+    /// The master lost the last response: send the backup again and take
+    /// back the posted one
+    fn repeat(&mut self, toggle: u8) {
+        if let Some(backup) = self.out.backup {
+            if self.out.posted.is_some() {
+                // Reset the send mailbox
+                esc::sm_disable(1);
+                self.out.repost = true;
+                esc::sm_enable(1);
+            }
+            self.out.write(backup);
+            self.sm1_free = false;
+        }
+        self.toggle = toggle;
+        esc::sm_ack_repeat(1, toggle);
+    }
+
+    /// Post the next queued response when the send mailbox is empty
+    fn post(&mut self) {
+        if self.out.posted.is_some() || !(self.sm1_free || esc::sm(1).MBXstat() == 0) {
+            return;
+        }
+        if let Some(n) = self.out.pop() {
+            self.out.write(n);
+            self.sm1_free = false;
+            esc::sm_status(1);
+            self.out.state[n] = Buffer::Posted;
+            self.out.posted = Some(n);
+        }
+    }
+
+    /// Read the receive mailbox (SM0) and hand it to its protocol
+    fn receive(&mut self) {
+        let mb0 = unsafe { *ESCvar.activemb0 };
+        let sml = mb0.cfg_sml as usize;
+        let Self { rx, out, .. } = self;
+
+        esc::read(mb0.cfg_sma, &mut rx[..MBX_HEADER_SIZE]);
+        let header = MbxHeader::from_bytes(rx[..MBX_HEADER_SIZE].try_into().unwrap());
+        let length = (header.length as usize)
+            .min(sml - MBX_HEADER_SIZE)
+            .min(MAX_MBX_SIZE - MBX_HEADER_SIZE);
+        esc::read(
+            mb0.cfg_sma + MBX_HEADER_SIZE as u16,
+            &mut rx[MBX_HEADER_SIZE..MBX_HEADER_SIZE + length],
+        );
+        // Reading the last byte releases the mailbox
+        if MBX_HEADER_SIZE + length < sml {
+            esc::read_u8(mb0.cfg_sme);
+        }
+        esc::sm_clear_mbx_full(0);
+
+        // A repeated counter is a mailbox already handled
+        let repeated = header.counter != 0 && header.counter == self.counter;
+        self.counter = header.counter;
+
+        if header.length == 0 || header.mbx_type == MbxType::ERR {
+            let _ = out.error(MbxError::INVALID_HEADER);
+            return;
+        }
+        if repeated {
+            return;
+        }
+
+        let data = &rx[MBX_HEADER_SIZE..MBX_HEADER_SIZE + length];
+        let result = match protocol(header.mbx_type) {
+            Some(protocol) => protocol.receive(&header, data, out),
+            None => Err(MbxError::UNSUPPORTED_PROTOCOL),
+        };
+        if let Err(error) = result {
+            defmt::debug!("Mailbox error {} for type {}", error.0, header.mbx_type.0);
+            let _ = out.error(error);
+        }
+    }
+}
+
+static mut MAILBOX: Mailbox = Mailbox::new();
+
+static mut PROTOCOLS: [Option<&'static mut dyn MbxProtocol>; MAX_MBX_PROTOCOLS] =
+    [const { None }; MAX_MBX_PROTOCOLS];
+
+fn mailbox() -> &'static mut Mailbox {
+    // SAFETY: only used from the (non reentrant) slave stack calls
+    unsafe { &mut *addr_of_mut!(MAILBOX) }
+}
+
+fn protocols() -> &'static mut [Option<&'static mut dyn MbxProtocol>; MAX_MBX_PROTOCOLS] {
+    // SAFETY: as `mailbox`
+    unsafe { &mut *addr_of_mut!(PROTOCOLS) }
+}
+
+/// Register a protocol handler. It takes precedence over a built-in handler
+/// of the same type (CoE, FoE).
+pub fn register(protocol: &'static mut dyn MbxProtocol) -> Result<(), MbxError> {
+    let slot = protocols()
+        .iter_mut()
+        .find(|slot| slot.is_none())
+        .ok_or(MbxError::NO_MORE_MEMORY)?;
+    *slot = Some(protocol);
+    Ok(())
+}
+
+/// Handler of `mbx_type`, registered ones first
+fn protocol(mbx_type: MbxType) -> Option<&'static mut dyn MbxProtocol> {
+    let registered = protocols()
+        .iter_mut()
+        .flatten()
+        .find(|protocol| protocol.mbx_type() == mbx_type);
+    match registered {
+        Some(protocol) => Some(&mut **protocol),
+        None if mbx_type == MbxType::COE => Some(coe::handler()),
+        None if mbx_type == MbxType::FOE => foe::handler().map(|foe| foe as _),
+        None => None,
+    }
+}
+
+fn for_each_protocol(mut f: impl FnMut(&mut dyn MbxProtocol)) {
+    for protocol in protocols().iter_mut().flatten() {
+        f(&mut **protocol);
+    }
+    f(coe::handler());
+    if let Some(foe) = foe::handler() {
+        f(foe);
+    }
+}
+
+fn busy() -> bool {
+    let mut busy = false;
+    for_each_protocol(|protocol| busy |= protocol.busy());
+    busy
+}
+
+/// Mailboxes started: take the active mailbox size and the repeat toggle
+pub(crate) fn start() {
+    let mbx = mailbox();
+    mbx.out.size = unsafe { ESCvar.activembxsize }.min(MAX_MBX_SIZE);
+    mbx.toggle = esc::sm(1).ECrep();
+}
+
+/// Mailboxes stopped: drop the buffers and any ongoing transfer
+pub(crate) fn reset() {
+    let mbx = mailbox();
+    mbx.out.state = [Buffer::Free; MBX_BUFFERS];
+    mbx.out.queued = 0;
+    mbx.out.posted = None;
+    mbx.out.backup = None;
+    mbx.out.repost = false;
+    mbx.out.counter = 0;
+    mbx.sm1_free = true;
+    mbx.toggle = 0;
+    mbx.counter = 0;
+    for_each_protocol(|protocol| protocol.reset());
+}
+
+/// Mailbox service: acknowledge reads and repeat requests, receive and
+/// dispatch requests and post the queued responses
+pub(crate) fn process() {
+    if unsafe { ESCvar.MBXrun } == 0 {
+        return;
+    }
+    let mbx = mailbox();
+    let alevent = unsafe { ESCvar.ALevent } as u32;
+
+    // SM0/1 access
+    if alevent & (ESCREG_ALEVENT_SM0 | ESCREG_ALEVENT_SM1) != 0 {
+        esc::sm_status(0);
+        esc::sm_status(1);
+    }
+
+    if mbx.out.posted.is_some() && alevent & ESCREG_ALEVENT_SM1 != 0 {
+        mbx.read_acked();
+    } else {
+        let toggle = esc::sm(1).ECrep();
+        if toggle != mbx.toggle {
+            mbx.repeat(toggle);
+            return;
+        }
+        // Read the next request once the last response is out
+        if esc::sm(0).MBXstat() != 0 && !mbx.out.pending() && !busy() {
+            mbx.receive();
+        }
+    }
+
+    for_each_protocol(|protocol| protocol.poll(&mut mbx.out));
+    mbx.post();
+}
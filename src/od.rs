@@ -0,0 +1,693 @@
+//! Object dictionary declared from Rust and served by the CoE layer.
+//!
+//! Objects are built with const fns into `_objectlist`/`_objd` based tables
+//! published as `SDOobjects`, see [`object_dictionary!`].
+
+use crate::al::AlState;
+use crate::bindings::*;
+use crate::ESCvar;
+use core::ffi::{c_void, CStr};
+use core::ptr::{self, addr_of};
+
+/// CoE data types (`DTYPE_*`)
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum DataType {
+    Boolean = DTYPE_BOOLEAN as u16,
+    Integer8 = DTYPE_INTEGER8 as u16,
+    Integer16 = DTYPE_INTEGER16 as u16,
+    Integer32 = DTYPE_INTEGER32 as u16,
+    Integer64 = DTYPE_INTEGER64 as u16,
+    Unsigned8 = DTYPE_UNSIGNED8 as u16,
+    Unsigned16 = DTYPE_UNSIGNED16 as u16,
+    Unsigned32 = DTYPE_UNSIGNED32 as u16,
+    Unsigned64 = DTYPE_UNSIGNED64 as u16,
+    Real32 = DTYPE_REAL32 as u16,
+    Real64 = DTYPE_REAL64 as u16,
+    VisibleString = DTYPE_VISIBLE_STRING as u16,
+    OctetString = DTYPE_OCTET_STRING as u16,
+    UnicodeString = DTYPE_UNICODE_STRING as u16,
+    BitArr8 = DTYPE_BITARR8 as u16,
+    BitArr16 = DTYPE_BITARR16 as u16,
+    BitArr32 = DTYPE_BITARR32 as u16,
+    Bit1 = DTYPE_BIT1 as u16,
+    Bit2 = DTYPE_BIT2 as u16,
+    Bit3 = DTYPE_BIT3 as u16,
+    Bit4 = DTYPE_BIT4 as u16,
+    Bit5 = DTYPE_BIT5 as u16,
+    Bit6 = DTYPE_BIT6 as u16,
+    Bit7 = DTYPE_BIT7 as u16,
+    Bit8 = DTYPE_BIT8 as u16,
+}
+
+impl DataType {
+    /// Bit length of the type, 0 for strings (set per entry)
+    pub const fn bitlen(self) -> u16 {
+        match self {
+            DataType::Boolean | DataType::Bit1 => 1,
+            DataType::Bit2 => 2,
+            DataType::Bit3 => 3,
+            DataType::Bit4 => 4,
+            DataType::Bit5 => 5,
+            DataType::Bit6 => 6,
+            DataType::Bit7 => 7,
+            DataType::Integer8 | DataType::Unsigned8 | DataType::BitArr8 | DataType::Bit8 => 8,
+            DataType::Integer16 | DataType::Unsigned16 | DataType::BitArr16 => 16,
+            DataType::Integer32 | DataType::Unsigned32 | DataType::Real32 | DataType::BitArr32 => {
+                32
+            }
+            DataType::Integer64 | DataType::Unsigned64 | DataType::Real64 => 64,
+            DataType::VisibleString | DataType::OctetString | DataType::UnicodeString => 0,
+        }
+    }
+}
+
+/// Access rights of an entry (`ATYPE_*`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub struct Access(pub u16);
+
+impl Access {
+    pub const RO: Self = Self(ATYPE_RO as u16);
+    pub const WO: Self = Self(ATYPE_WO as u16);
+    pub const RW: Self = Self(ATYPE_RW as u16);
+    /// Read always, write in PREOP only
+    pub const RW_PRE: Self = Self(ATYPE_RWpre as u16);
+    pub const R_PRE: Self = Self(ATYPE_Rpre as u16);
+    pub const R_SAFE: Self = Self(ATYPE_Rsafe as u16);
+    pub const R_OP: Self = Self(ATYPE_Rop as u16);
+    pub const W_PRE: Self = Self(ATYPE_Wpre as u16);
+    pub const W_SAFE: Self = Self(ATYPE_Wsafe as u16);
+    pub const W_OP: Self = Self(ATYPE_Wop as u16);
+    /// Stored for backup (OD list type 4)
+    pub const BACKUP: Self = Self(0x0100);
+    /// Startup parameter (OD list type 5)
+    pub const SETTINGS: Self = Self(0x0200);
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// SDO read allowed in `state`
+    pub const fn readable(self, state: AlState) -> bool {
+        match state {
+            AlState::PreOp => self.contains(Self::R_PRE),
+            AlState::SafeOp => self.contains(Self::R_SAFE),
+            AlState::Op => self.contains(Self::R_OP),
+            AlState::Init | AlState::Boot => false,
+        }
+    }
+
+    /// SDO write allowed in `state`
+    pub const fn writable(self, state: AlState) -> bool {
+        match state {
+            AlState::PreOp => self.contains(Self::W_PRE),
+            AlState::SafeOp => self.contains(Self::W_SAFE),
+            AlState::Op => self.contains(Self::W_OP),
+            AlState::Init | AlState::Boot => false,
+        }
+    }
+
+    /// Writable in some state
+    pub const fn any_write(self) -> bool {
+        self.0 & Self::WO.0 != 0
+    }
+}
+
+/// One subindex of an object (`_objd`) and its SDO Info value info
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Entry(_objd, ValueInfo);
+
+/// Unit, range and allowed values: checked on SDO downloads, reported in
+/// the SDO Info entry description
+#[derive(Clone, Copy)]
+struct ValueInfo {
+    unit: Option<u32>,
+    // Value of 64 bit entries, `_objd::value` only holds 32 bits
+    value: Option<u64>,
+    // Little endian encoding in the entry data type
+    min: Option<u64>,
+    max: Option<u64>,
+    // Enumerated values, any value when empty
+    allowed: &'static [i64],
+}
+
+// Entries are immutable tables, `data` is only written by the CoE layer
+unsafe impl Sync for Entry {}
+
+impl Entry {
+    /// Entry with a constant value, see [`Self::value`] and [`Self::bind`]
+    pub const fn new(
+        subindex: u8,
+        name: &'static CStr,
+        datatype: DataType,
+        access: Access,
+    ) -> Self {
+        Self(
+            _objd {
+                subindex: subindex as u16,
+                datatype: datatype as u16,
+                bitlength: datatype.bitlen(),
+                flags: access.0,
+                name: name.as_ptr(),
+                value: 0,
+                data: core::ptr::null_mut(),
+            },
+            ValueInfo {
+                unit: None,
+                value: None,
+                min: None,
+                max: None,
+                allowed: &[],
+            },
+        )
+    }
+
+    /// Subindex 0 of a record or array, holding the highest subindex
+    pub const fn max_subindex(max: u8) -> Self {
+        Self::new(0, c"Max SubIndex", DataType::Unsigned8, Access::RO).value(max as u32)
+    }
+
+    /// Read-only visible string, e.g. 0x1008 device name
+    pub const fn string(subindex: u8, name: &'static CStr, text: &'static CStr) -> Self {
+        let mut entry = Self::new(subindex, name, DataType::VisibleString, Access::RO);
+        entry.0.bitlength = (text.count_bytes() * 8) as u16;
+        entry.0.data = text.as_ptr() as *mut c_void;
+        entry
+    }
+
+    /// Constant value, or default value when the entry is bound
+    pub const fn value(mut self, value: u32) -> Self {
+        self.0.value = value;
+        self
+    }
+
+    /// [`Self::value`] of a 64 bit entry, converted for REAL64 like
+    /// [`Self::range`]
+    pub const fn value64(mut self, value: i64) -> Self {
+        let value = self.encode(value);
+        self.0.value = value as u32;
+        self.1.value = Some(value);
+        self
+    }
+
+    /// Override the bit length (strings, padding)
+    pub const fn bitlength(mut self, bits: u16) -> Self {
+        self.0.bitlength = bits;
+        self
+    }
+
+    /// Store the entry in `data`, usually a field of the application object
+    /// struct (`addr_of_mut!(OBJ.field)`)
+    pub const fn bind<T>(mut self, data: *mut T) -> Self {
+        assert!(
+            core::mem::size_of::<T>() * 8 >= self.0.bitlength as usize,
+            "object entry storage smaller than its data type"
+        );
+        self.0.data = data as *mut c_void;
+        self
+    }
+
+    /// Allow mapping the entry in a RxPDO (outputs)
+    pub const fn rxpdo(mut self) -> Self {
+        self.0.flags |= ATYPE_RXPDO as u16;
+        self
+    }
+
+    /// Allow mapping the entry in a TxPDO (inputs)
+    pub const fn txpdo(mut self) -> Self {
+        self.0.flags |= ATYPE_TXPDO as u16;
+        self
+    }
+
+    /// Listed in the backup objects, saved by the parameter storage
+    pub const fn backup(mut self) -> Self {
+        self.0.flags |= Access::BACKUP.0;
+        self
+    }
+
+    /// Listed in the startup parameters
+    pub const fn settings(mut self) -> Self {
+        self.0.flags |= Access::SETTINGS.0;
+        self
+    }
+
+    /// Unit type (ETG.1004 encoding: prefix, SI unit index)
+    pub const fn unit(mut self, unit: u32) -> Self {
+        self.1.unit = Some(unit);
+        self
+    }
+
+    /// Value range, checked on SDO downloads and converted for REAL
+    /// entries
+    pub const fn range(mut self, min: i64, max: i64) -> Self {
+        self.1.min = Some(self.encode(min));
+        self.1.max = Some(self.encode(max));
+        self
+    }
+
+    /// Only these values may be written, e.g. the states of an enumeration
+    pub const fn allowed(mut self, values: &'static [i64]) -> Self {
+        assert!(!values.is_empty(), "allowed values must not be empty");
+        self.1.allowed = values;
+        self
+    }
+
+    const fn encode(&self, value: i64) -> u64 {
+        if self.0.datatype == DataType::Real32 as u16 {
+            (value as f32).to_bits() as u64
+        } else if self.0.datatype == DataType::Real64 as u16 {
+            (value as f64).to_bits()
+        } else {
+            value as u64
+        }
+    }
+
+    pub const fn subindex(&self) -> u8 {
+        self.0.subindex as u8
+    }
+
+    pub const fn datatype(&self) -> u16 {
+        self.0.datatype
+    }
+
+    pub const fn bit_length(&self) -> u16 {
+        self.0.bitlength
+    }
+
+    pub const fn access(&self) -> Access {
+        Access(self.0.flags)
+    }
+
+    pub const fn default_value(&self) -> u32 {
+        self.0.value
+    }
+
+    /// Default value with all 64 bits, see [`Self::value64`]
+    pub const fn default_u64(&self) -> u64 {
+        match self.1.value {
+            Some(value) => value,
+            None => self.0.value as u64,
+        }
+    }
+
+    pub const fn data(&self) -> *mut c_void {
+        self.0.data
+    }
+
+    pub const fn name(&self) -> &'static CStr {
+        // SAFETY: entries are built from 'static C strings
+        unsafe { CStr::from_ptr(self.0.name) }
+    }
+
+    /// Visible, octet or unicode string, sized per entry
+    pub const fn is_string(&self) -> bool {
+        let datatype = self.0.datatype;
+        datatype == DataType::VisibleString as u16
+            || datatype == DataType::OctetString as u16
+            || datatype == DataType::UnicodeString as u16
+    }
+
+    pub const fn unit_type(&self) -> Option<u32> {
+        self.1.unit
+    }
+
+    /// Minimum value, little endian in the entry data type. Without a
+    /// range, the lowest allowed value.
+    pub const fn minimum(&self) -> Option<u64> {
+        match self.1.min {
+            Some(min) => Some(min),
+            None => self.allowed_limit(false),
+        }
+    }
+
+    /// Maximum value, little endian in the entry data type. Without a
+    /// range, the highest allowed value.
+    pub const fn maximum(&self) -> Option<u64> {
+        match self.1.max {
+            Some(max) => Some(max),
+            None => self.allowed_limit(true),
+        }
+    }
+
+    pub const fn allowed_values(&self) -> &'static [i64] {
+        self.1.allowed
+    }
+
+    const fn allowed_limit(&self, highest: bool) -> Option<u64> {
+        let allowed = self.1.allowed;
+        if allowed.is_empty() {
+            return None;
+        }
+        let mut limit = allowed[0];
+        let mut i = 1;
+        while i < allowed.len() {
+            if (allowed[i] > limit) == highest {
+                limit = allowed[i];
+            }
+            i += 1;
+        }
+        Some(self.encode(limit))
+    }
+
+    /// Size of the entry in bytes
+    pub const fn byte_len(&self) -> usize {
+        (self.0.bitlength as usize).div_ceil(8)
+    }
+
+    /// Copy the current value from byte `offset` into `buf`: the bound
+    /// storage, or the constant value (little endian). Returns the number
+    /// of bytes copied.
+    pub fn read_at(&self, offset: usize, buf: &mut [u8]) -> usize {
+        let len = self.byte_len().saturating_sub(offset).min(buf.len());
+        if self.0.data.is_null() {
+            let value = self.default_u64().to_le_bytes();
+            for (i, byte) in buf[..len].iter_mut().enumerate() {
+                *byte = value.get(offset + i).copied().unwrap_or(0);
+            }
+        } else {
+            // SAFETY: `bind` checked the storage holds `byte_len` bytes
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    (self.0.data as *const u8).add(offset),
+                    buf.as_mut_ptr(),
+                    len,
+                )
+            };
+        }
+        len
+    }
+
+    /// Write `buf` to the bound storage from byte `offset`. Returns false
+    /// for an entry without storage.
+    pub fn write_at(&self, offset: usize, buf: &[u8]) -> bool {
+        if self.0.data.is_null() {
+            return false;
+        }
+        let len = self.byte_len().saturating_sub(offset).min(buf.len());
+        let data = self.0.data as *mut u8;
+        // SAFETY: as `read_at`
+        unsafe {
+            if self.0.bitlength < 8 {
+                // Unused bits stay cleared, the storage may be a `bool`
+                if len > 0 {
+                    *data = buf[0] & ((1u16 << self.0.bitlength) - 1) as u8;
+                }
+            } else {
+                ptr::copy_nonoverlapping(buf.as_ptr(), data.add(offset), len);
+            }
+        }
+        true
+    }
+
+    /// Current value of an entry of up to 64 bits
+    pub fn value_u64(&self) -> u64 {
+        let mut bytes = [0; 8];
+        self.read_at(0, &mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+/// One object of the dictionary (`_objectlist`)
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct Object(_objectlist);
+
+unsafe impl Sync for Object {}
+
+impl Object {
+    // End of table marker expected by the CoE layer
+    const END: Self = Self(_objectlist {
+        index: 0xffff,
+        objtype: 0xff,
+        maxsub: 0xff,
+        pad1: 0xff,
+        name: core::ptr::null(),
+        objdesc: core::ptr::null(),
+    });
+
+    /// Single value object, named after its entry
+    pub const fn var(index: u16, entry: &'static Entry) -> Self {
+        assert!(entry.0.subindex == 0, "VAR object entry must be subindex 0");
+        Self(_objectlist {
+            index,
+            objtype: OTYPE_VAR as u16,
+            maxsub: 0,
+            pad1: 0,
+            name: entry.0.name,
+            objdesc: entry as *const Entry as *const _objd,
+        })
+    }
+
+    /// Array object, subindex 0 from [`Entry::max_subindex`]
+    pub const fn array(index: u16, name: &'static CStr, entries: &'static [Entry]) -> Self {
+        Self::compound(index, OTYPE_ARRAY as u16, name, entries)
+    }
+
+    /// Record object, subindex 0 from [`Entry::max_subindex`]. Subindexes
+    /// may be sparse but must be increasing.
+    pub const fn record(index: u16, name: &'static CStr, entries: &'static [Entry]) -> Self {
+        Self::compound(index, OTYPE_RECORD as u16, name, entries)
+    }
+
+    const fn compound(
+        index: u16,
+        objtype: u16,
+        name: &'static CStr,
+        entries: &'static [Entry],
+    ) -> Self {
+        assert!(
+            !entries.is_empty() && entries[0].0.subindex == 0,
+            "object must start with its max subindex"
+        );
+        let maxsub = entries[0].0.value;
+        let mut i = 1;
+        while i < entries.len() {
+            assert!(
+                entries[i].0.subindex > entries[i - 1].0.subindex,
+                "object subindexes must be increasing"
+            );
+            i += 1;
+        }
+        assert!(
+            entries[entries.len() - 1].0.subindex as u32 == maxsub,
+            "last subindex must match the max subindex"
+        );
+
+        Self(_objectlist {
+            index,
+            objtype,
+            maxsub: maxsub as u8,
+            pad1: 0,
+            name: name.as_ptr(),
+            objdesc: entries.as_ptr() as *const _objd,
+        })
+    }
+
+    pub const fn index(&self) -> u16 {
+        self.0.index
+    }
+
+    pub const fn object_type(&self) -> u16 {
+        self.0.objtype
+    }
+
+    pub const fn max_subindex(&self) -> u8 {
+        self.0.maxsub
+    }
+
+    pub const fn name(&self) -> &'static CStr {
+        // SAFETY: objects are built from 'static C strings
+        unsafe { CStr::from_ptr(self.0.name) }
+    }
+
+    /// Entry of `subindex`, None for a gap in a sparse record
+    pub fn entry(&self, subindex: u8) -> Option<&'static Entry> {
+        self.entries()
+            .iter()
+            .find(|entry| entry.subindex() == subindex)
+    }
+
+    /// Entries of the object, up to the max subindex
+    pub fn entries(&self) -> &'static [Entry] {
+        let objdesc = self.0.objdesc as *const Entry;
+        let maxsub = self.0.maxsub;
+        let mut len = 1;
+        // SAFETY: objects are only built from 'static entry slices ending at maxsub
+        unsafe {
+            while (*objdesc.add(len - 1)).subindex() < maxsub {
+                len += 1;
+            }
+            core::slice::from_raw_parts(objdesc, len)
+        }
+    }
+}
+
+/// Object table with its end marker, laid out like the C `SDOobjects[]`
+#[repr(C)]
+pub struct ObjectDictionary<const N: usize> {
+    objects: [Object; N],
+    end: Object,
+}
+
+impl<const N: usize> ObjectDictionary<N> {
+    /// Objects must be sorted by increasing index
+    pub const fn new(objects: [Object; N]) -> Self {
+        let mut i = 1;
+        while i < N {
+            assert!(
+                objects[i].0.index > objects[i - 1].0.index,
+                "object indexes must be increasing"
+            );
+            i += 1;
+        }
+        Self {
+            objects,
+            end: Object::END,
+        }
+    }
+
+    /// Merge object groups (e.g. the ones generated by
+    /// `#[derive(EtherCatObjects)]`) into one table sorted by index
+    pub const fn from_parts(parts: &[&[Object]]) -> Self {
+        let mut objects = [Object::END; N];
+        let mut len = 0;
+        let mut p = 0;
+        while p < parts.len() {
+            let mut i = 0;
+            while i < parts[p].len() {
+                assert!(len < N, "more objects than the dictionary size");
+                // Insertion sort, the tables are small and built at compile time
+                let object = parts[p][i];
+                let mut j = len;
+                while j > 0 && objects[j - 1].0.index > object.0.index {
+                    objects[j] = objects[j - 1];
+                    j -= 1;
+                }
+                assert!(
+                    j == 0 || objects[j - 1].0.index != object.0.index,
+                    "object index declared twice"
+                );
+                objects[j] = object;
+                len += 1;
+                i += 1;
+            }
+            p += 1;
+        }
+        assert!(len == N, "fewer objects than the dictionary size");
+        Self::new(objects)
+    }
+
+    pub fn objects(&self) -> &[Object] {
+        &self.objects
+    }
+
+    pub fn find(&self, index: u16) -> Option<&Object> {
+        self.objects.iter().find(|object| object.index() == index)
+    }
+}
+
+extern "C" {
+    // Defined by the application with `object_dictionary!`
+    static SDOobjects: Object;
+}
+
+/// Objects of the application dictionary, sorted by index
+pub fn objects() -> &'static [Object] {
+    // SAFETY: `SDOobjects` is an `ObjectDictionary`, objects followed by
+    // the end marker
+    unsafe {
+        let first = addr_of!(SDOobjects);
+        let mut len = 0;
+        while (*first.add(len)).index() != Object::END.index() {
+            len += 1;
+        }
+        core::slice::from_raw_parts(first, len)
+    }
+}
+
+/// Object `index` of the application dictionary
+pub fn find(index: u16) -> Option<&'static Object> {
+    objects()
+        .binary_search_by_key(&index, Object::index)
+        .ok()
+        .map(|n| &objects()[n])
+}
+
+/// Write the default value of every bound entry, strings excepted, then
+/// call `set_defaults_hook` (port of `COE_initDefaultValues`). Skipped with
+/// `skip_default_initialization`.
+pub fn init_defaults() {
+    // SAFETY: called from `EcatSlave::init`, before the stack runs
+    let (skip, hook) = unsafe { (ESCvar.skip_default_initialization, ESCvar.set_defaults_hook) };
+    if skip {
+        return;
+    }
+    for object in objects() {
+        for entry in object.entries() {
+            if !entry.data().is_null() && !entry.is_string() {
+                let value = entry.default_u64().to_le_bytes();
+                entry.write_at(0, &value[..entry.byte_len().min(8)]);
+            }
+        }
+    }
+    if let Some(hook) = hook {
+        unsafe { hook() };
+    }
+}
+
+#[doc(hidden)]
+/// `CStr` from a NUL terminated literal, for generated code
+pub const fn cstr(bytes: &'static [u8]) -> &'static CStr {
+    match CStr::from_bytes_with_nul(bytes) {
+        Ok(text) => text,
+        Err(_) => panic!("object name must be NUL terminated without inner NUL"),
+    }
+}
+
+/// Derive generating the PDO objects, mappings and SM assignments of a
+/// process data struct
+pub use SOES_rs_macros::EtherCatObjects;
+
+/// Objects generated for a struct by `#[derive(EtherCatObjects)]`
+pub trait EtherCatObjects {
+    const OBJECTS: &'static [Object];
+}
+
+/// Declare the application object dictionary as the `SDOobjects` table
+/// served by the CoE layer. Types deriving [`EtherCatObjects`] listed after
+/// a `;` add their generated objects.
+///
+/// ```ignore
+/// static DEVICE_TYPE: Entry =
+///     Entry::new(0, c"Device Type", DataType::Unsigned32, Access::RO).value(5001);
+/// static KEY1: Entry = Entry::new(0, c"Key1", DataType::Boolean, Access::RO)
+///     .txpdo()
+///     .bind(unsafe { addr_of_mut!(OBJ.key1) });
+///
+/// object_dictionary![
+///     Object::var(0x1000, &DEVICE_TYPE),
+///     Object::var(0x6000, &KEY1);
+///     ProcessData,
+/// ];
+/// ```
+#[macro_export]
+macro_rules! object_dictionary {
+    ($($object:expr),* $(,)? $(; $($objects:ty),+ $(,)?)?) => {
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        pub static SDOobjects: $crate::od::ObjectDictionary<
+            {
+                [$(stringify!($object)),*].len()
+                    $($(+ <$objects as $crate::od::EtherCatObjects>::OBJECTS.len())+)?
+            },
+        > = $crate::od::ObjectDictionary::from_parts(&[
+            &[$($object),*],
+            $($(<$objects as $crate::od::EtherCatObjects>::OBJECTS),+)?
+        ]);
+    };
+}
@@ -0,0 +1,416 @@
+//! SII EEPROM image generator (host side, `std` feature): builds the image
+//! read by the ESC and the master from the object dictionary and the
+//! stack configuration, replacing the `eeprom.bin` / `eeprom.hex` exported
+//! by the SOES ESI tool.
+//!
+//! Identity (0x1018), device name (0x1008) and the TxPDO/RxPDO categories
+//! (0x1C12/0x1C13 assignments) come from the object dictionary, mailbox
+//! and sync manager layout from `ecat_options.h`, the rest from
+//! [`SiiConfig`].
+//!
+//! [`parse`] decodes an image back and [`SiiImage::check`] reports where it
+//! does not match the firmware.
+
+use std::fmt::Write;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::bindings::*;
+use crate::coe::pdo;
+use crate::od::{self, Entry, Object};
+
+mod parse;
+
+pub use parse::{parse, General, Mismatch, ParseError, Pdo, PdoEntry, SiiImage, SyncManager};
+
+/// Mailbox protocols (word 0x1C)
+pub const MBX_EOE: u16 = 0x0002;
+pub const MBX_COE: u16 = 0x0004;
+pub const MBX_FOE: u16 = 0x0008;
+
+/// CoE details of the general category
+pub const COE_SDO: u8 = 0x01;
+pub const COE_SDO_INFO: u8 = 0x02;
+pub const COE_PDO_ASSIGN: u8 = 0x04;
+pub const COE_PDO_CONFIG: u8 = 0x08;
+pub const COE_UPLOAD_AT_STARTUP: u8 = 0x10;
+pub const COE_COMPLETE_ACCESS: u8 = 0x20;
+
+const CAT_STRINGS: u16 = 10;
+const CAT_GENERAL: u16 = 30;
+const CAT_FMMU: u16 = 40;
+const CAT_SYNCM: u16 = 41;
+const CAT_TXPDO: u16 = 50;
+const CAT_RXPDO: u16 = 51;
+const CAT_END: u16 = 0xFFFF;
+
+/// FMMU usage
+const FMMU_OUTPUTS: u8 = 1;
+const FMMU_INPUTS: u8 = 2;
+const FMMU_MBX_STATE: u8 = 3;
+
+/// Sync manager types
+const SM_MBX_OUT: u8 = 1;
+const SM_MBX_IN: u8 = 2;
+const SM_OUTPUTS: u8 = 3;
+const SM_INPUTS: u8 = 4;
+
+/// Categories start after the fixed area of 64 words
+const CATEGORIES: usize = 0x80;
+const IDENTITY: u16 = 0x1018;
+const DEVICE_NAME: u16 = 0x1008;
+
+/// Image generation failures
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SiiError {
+    /// The categories do not fit in the EEPROM size
+    Capacity,
+    /// More than 255 strings, or a string longer than 255 bytes
+    Strings,
+    /// A PDO maps an entry missing from the object dictionary
+    Mapping(u16),
+}
+
+/// Device description not held in the object dictionary
+#[derive(Clone, Debug)]
+pub struct SiiConfig {
+    /// PDI control, PDI configuration, sync impulse length, extended PDI
+    /// configuration (config area words 0 to 3)
+    pub pdi: [u16; 4],
+    pub station_alias: u16,
+    /// EEPROM size in bytes, a multiple of 128
+    pub eeprom_size: usize,
+    /// `MBX_*` protocols
+    pub mailbox_protocols: u16,
+    pub group: String,
+    pub image: String,
+    /// Device type (order) text
+    pub device_type: String,
+    /// `COE_*` details
+    pub coe_details: u8,
+    pub foe_details: u8,
+    pub eoe_details: u8,
+    /// Physical layer of ports 0 to 3, a nibble each (1 = MII)
+    pub physical_ports: u16,
+    /// Vendor and group names, ESI file only
+    pub vendor_name: String,
+    pub group_name: String,
+}
+
+impl Default for SiiConfig {
+    /// LAN9252 in SPI mode, 2 kB EEPROM, ports 0 and 1, CoE with SDO Info
+    fn default() -> Self {
+        Self {
+            pdi: [0x0680, 0x4400, 0x0064, 0],
+            station_alias: 0,
+            eeprom_size: 2048,
+            mailbox_protocols: MBX_COE,
+            group: String::new(),
+            image: String::new(),
+            device_type: String::new(),
+            coe_details: COE_SDO | COE_SDO_INFO | COE_UPLOAD_AT_STARTUP,
+            foe_details: 0,
+            eoe_details: 0,
+            physical_ports: 0x0011,
+            vendor_name: String::new(),
+            group_name: String::new(),
+        }
+    }
+}
+
+/// CRC-8 of the config area (polynomial 0x07, initial value 0xFF)
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc: u8 = 0xFF;
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// String category, indexes from 1 (0 is no string)
+#[derive(Default)]
+struct Strings(Vec<String>);
+
+impl Strings {
+    fn index(&mut self, text: &str) -> u8 {
+        if text.is_empty() {
+            return 0;
+        }
+        let position = match self.0.iter().position(|string| string == text) {
+            Some(position) => position,
+            None => {
+                self.0.push(text.into());
+                self.0.len() - 1
+            }
+        };
+        // More than 255 strings fail in `category`
+        (position + 1) as u8
+    }
+
+    fn category(&self) -> Result<Vec<u8>, SiiError> {
+        if self.0.len() > u8::MAX as usize {
+            return Err(SiiError::Strings);
+        }
+        let mut data = vec![self.0.len() as u8];
+        for string in &self.0 {
+            let len = u8::try_from(string.len()).map_err(|_| SiiError::Strings)?;
+            data.push(len);
+            data.extend_from_slice(string.as_bytes());
+        }
+        Ok(data)
+    }
+}
+
+/// 0x1018 `subindex`, the default of a bound entry (serial number)
+pub(crate) fn identity(subindex: u8) -> u32 {
+    od::find(IDENTITY)
+        .and_then(|object| object.entry(subindex))
+        .map_or(0, |entry| entry.default_value())
+}
+
+/// Text of a string entry, up to its first NUL
+pub(crate) fn text(entry: &Entry) -> String {
+    let mut text = vec![0; entry.byte_len()];
+    entry.read_at(0, &mut text);
+    let len = text
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(text.len());
+    String::from_utf8_lossy(&text[..len]).into()
+}
+
+/// 0x1008 device name
+pub(crate) fn device_name() -> String {
+    od::find(DEVICE_NAME)
+        .and_then(|object| object.entry(0))
+        .filter(|entry| entry.is_string())
+        .map(text)
+        .unwrap_or_default()
+}
+
+fn put_u16(data: &mut [u8], word: usize, value: u16) {
+    data[word * 2..word * 2 + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(data: &mut [u8], word: usize, value: u32) {
+    data[word * 2..word * 2 + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+/// Sync manager entry: start, length, control, status, enable, type. The
+/// process data SMs are disabled without PDO.
+fn sync_manager(data: &mut Vec<u8>, start: u32, length: u16, control: u32, sm_type: u8) {
+    let enable = u8::from(sm_type <= SM_MBX_IN || length != 0);
+    data.extend_from_slice(&(start as u16).to_le_bytes());
+    data.extend_from_slice(&length.to_le_bytes());
+    data.extend_from_slice(&[control as u8, 0, enable, sm_type]);
+}
+
+/// PDO mapping objects assigned in `assign` (0x1C12 or 0x1C13), unknown
+/// PDOs are skipped as in SOES
+pub(crate) fn assigned_pdos(assign: u16) -> Vec<&'static Object> {
+    let Some(assignment) = od::find(assign) else {
+        return Vec::new();
+    };
+    let assigned = assignment.entries()[0].value_u64() as usize;
+    assignment
+        .entries()
+        .iter()
+        .skip(1)
+        .take(assigned)
+        .filter_map(|pdo| od::find(pdo.value_u64() as u16))
+        .collect()
+}
+
+/// Size in bytes of the PDOs assigned in `assign`, 0 for an invalid mapping
+pub(crate) fn pdo_size(assign: u16) -> u16 {
+    pdo::size_of_pdo(assign, &mut []).map_or(0, |(size, _)| size)
+}
+
+/// Index, subindex and bit length of the entries mapped in `pdo`
+pub(crate) fn mappings(pdo: &Object) -> Vec<(u16, u8, u8)> {
+    let mapped = pdo.entries()[0].value_u64() as usize;
+    pdo.entries()
+        .iter()
+        .skip(1)
+        .take(mapped)
+        .map(|mapping| {
+            let value = mapping.value_u64() as u32;
+            ((value >> 16) as u16, (value >> 8) as u8, value as u8)
+        })
+        .collect()
+}
+
+/// TxPDO or RxPDO category of the PDOs assigned in `assign` to `sm`
+fn pdo_category(assign: u16, sm: u8, strings: &mut Strings) -> Result<Vec<u8>, SiiError> {
+    let mut data = Vec::new();
+    for pdo in assigned_pdos(assign) {
+        let mappings = mappings(pdo);
+        let name = strings.index(pdo.name().to_str().unwrap_or_default());
+        data.extend_from_slice(&pdo.index().to_le_bytes());
+        data.extend_from_slice(&[mappings.len() as u8, sm, 0, name]);
+        data.extend_from_slice(&0u16.to_le_bytes());
+
+        for (index, subindex, bits) in mappings {
+            // Padding has no name nor data type
+            let (name, datatype) = match (index, subindex) {
+                (0, 0) => (0, 0),
+                _ => {
+                    let object = od::find(index).ok_or(SiiError::Mapping(index))?;
+                    let entry = object.entry(subindex).ok_or(SiiError::Mapping(index))?;
+                    let text = entry.name().to_str().unwrap_or_default();
+                    (strings.index(text), entry.datatype() as u8)
+                }
+            };
+            data.extend_from_slice(&index.to_le_bytes());
+            data.extend_from_slice(&[subindex, name, datatype, bits]);
+            data.extend_from_slice(&0u16.to_le_bytes());
+        }
+    }
+    Ok(data)
+}
+
+fn category(image: &mut Vec<u8>, category: u16, data: &[u8]) {
+    if data.is_empty() {
+        return;
+    }
+    let words = data.len().div_ceil(2);
+    image.extend_from_slice(&category.to_le_bytes());
+    image.extend_from_slice(&(words as u16).to_le_bytes());
+    image.extend_from_slice(data);
+    image.resize(image.len().next_multiple_of(2), 0);
+}
+
+/// Build the SII image of the object dictionary, `config.eeprom_size`
+/// bytes filled with 0xFF after the categories
+pub fn image(config: &SiiConfig) -> Result<Vec<u8>, SiiError> {
+    let mut image = vec![0; CATEGORIES];
+
+    // Config area, checked by the ESC at load
+    for (word, value) in config.pdi.iter().enumerate() {
+        put_u16(&mut image, word, *value);
+    }
+    put_u16(&mut image, 4, config.station_alias);
+    let checksum = crc8(&image[..14]);
+    put_u16(&mut image, 7, checksum as u16);
+
+    // Identity
+    for (word, subindex) in [(0x08, 1), (0x0A, 2), (0x0C, 3), (0x0E, 4)] {
+        put_u32(&mut image, word, identity(subindex));
+    }
+
+    // Bootstrap and standard mailboxes
+    put_u16(&mut image, 0x14, MBX0_sma_b as u16);
+    put_u16(&mut image, 0x15, MBX0_sml_b as u16);
+    put_u16(&mut image, 0x16, MBX1_sma_b as u16);
+    put_u16(&mut image, 0x17, MBX1_sml_b as u16);
+    put_u16(&mut image, 0x18, MBX0_sma as u16);
+    put_u16(&mut image, 0x19, MBX0_sml as u16);
+    put_u16(&mut image, 0x1A, MBX1_sma as u16);
+    put_u16(&mut image, 0x1B, MBX1_sml as u16);
+    put_u16(&mut image, 0x1C, config.mailbox_protocols);
+    put_u16(
+        &mut image,
+        0x3E,
+        (config.eeprom_size * 8 / 1024).saturating_sub(1) as u16,
+    );
+    put_u16(&mut image, 0x3F, 1);
+
+    // Strings first referenced by the general category
+    let mut strings = Strings::default();
+    let device_name = device_name();
+    let mut general = vec![0; 32];
+    general[..4].copy_from_slice(&[
+        strings.index(&config.group),
+        strings.index(&config.image),
+        strings.index(&config.device_type),
+        strings.index(&device_name),
+    ]);
+    general[5..8].copy_from_slice(&[config.coe_details, config.foe_details, config.eoe_details]);
+    general[16..18].copy_from_slice(&config.physical_ports.to_le_bytes());
+
+    let txpdo = pdo_category(TX_PDO_OBJIDX as u16, 3, &mut strings)?;
+    let rxpdo = pdo_category(RX_PDO_OBJIDX as u16, 2, &mut strings)?;
+
+    let mut fmmu = Vec::new();
+    if !rxpdo.is_empty() {
+        fmmu.push(FMMU_OUTPUTS);
+    }
+    if !txpdo.is_empty() {
+        fmmu.push(FMMU_INPUTS);
+    }
+    fmmu.push(FMMU_MBX_STATE);
+
+    let mut syncm = Vec::new();
+    sync_manager(&mut syncm, MBX0_sma, MBX0_sml as u16, MBX0_smc, SM_MBX_OUT);
+    sync_manager(&mut syncm, MBX1_sma, MBX1_sml as u16, MBX1_smc, SM_MBX_IN);
+    sync_manager(
+        &mut syncm,
+        SM2_sma,
+        pdo_size(RX_PDO_OBJIDX as u16),
+        SM2_smc,
+        SM_OUTPUTS,
+    );
+    sync_manager(
+        &mut syncm,
+        SM3_sma,
+        pdo_size(TX_PDO_OBJIDX as u16),
+        SM3_smc,
+        SM_INPUTS,
+    );
+
+    category(&mut image, CAT_STRINGS, &strings.category()?);
+    category(&mut image, CAT_GENERAL, &general);
+    category(&mut image, CAT_FMMU, &fmmu);
+    category(&mut image, CAT_SYNCM, &syncm);
+    category(&mut image, CAT_TXPDO, &txpdo);
+    category(&mut image, CAT_RXPDO, &rxpdo);
+    image.extend_from_slice(&CAT_END.to_le_bytes());
+
+    if image.len() > config.eeprom_size {
+        return Err(SiiError::Capacity);
+    }
+    image.resize(config.eeprom_size, 0xFF);
+    Ok(image)
+}
+
+/// Intel HEX of `image`, 32 byte records
+pub fn intel_hex(image: &[u8]) -> String {
+    let mut hex = String::new();
+    let mut record = |record_type: u8, address: u16, data: &[u8]| {
+        let address = address.to_be_bytes();
+        let mut sum = (data.len() as u8)
+            .wrapping_add(address[0])
+            .wrapping_add(address[1])
+            .wrapping_add(record_type);
+        let _ = write!(
+            hex,
+            ":{:02X}{:04X}{:02X}",
+            data.len(),
+            u16::from_be_bytes(address),
+            record_type
+        );
+        for byte in data {
+            sum = sum.wrapping_add(*byte);
+            let _ = write!(hex, "{byte:02X}");
+        }
+        let _ = writeln!(hex, "{:02X}", sum.wrapping_neg());
+    };
+
+    for (n, chunk) in image.chunks(32).enumerate() {
+        let address = n * 32;
+        // Extended linear address above 64 kB
+        if address > 0 && address & 0xFFFF == 0 {
+            record(0x04, 0, &((address >> 16) as u16).to_be_bytes());
+        }
+        record(0x00, address as u16, chunk);
+    }
+    record(0x01, 0, &[]);
+    hex
+}
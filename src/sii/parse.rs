@@ -0,0 +1,324 @@
+//! SII image decoding and checks against the compiled stack configuration
+
+use std::fmt;
+use std::string::String;
+use std::vec::Vec;
+
+use super::*;
+
+/// Image decoding failures
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// Shorter than the fixed area
+    TooShort,
+    /// A category of this type runs past the end of the image
+    Truncated(u16),
+}
+
+/// General category, string indexes from 1
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct General {
+    pub group: u8,
+    pub image: u8,
+    pub order: u8,
+    pub name: u8,
+    pub coe_details: u8,
+    pub foe_details: u8,
+    pub eoe_details: u8,
+    pub physical_ports: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyncManager {
+    pub start: u16,
+    pub length: u16,
+    pub control: u8,
+    pub status: u8,
+    pub enable: u8,
+    /// 1 mailbox out, 2 mailbox in, 3 outputs, 4 inputs
+    pub sm_type: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PdoEntry {
+    pub index: u16,
+    pub subindex: u8,
+    pub name: u8,
+    pub datatype: u8,
+    pub bit_length: u8,
+    pub flags: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pdo {
+    pub index: u16,
+    pub sm: u8,
+    pub synchronization: u8,
+    pub name: u8,
+    pub flags: u16,
+    pub entries: Vec<PdoEntry>,
+}
+
+/// Decoded SII image
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SiiImage {
+    pub pdi: [u16; 4],
+    pub station_alias: u16,
+    pub checksum: u8,
+    /// Checksum computed over the config area
+    pub computed_checksum: u8,
+    pub vendor_id: u32,
+    pub product_code: u32,
+    pub revision: u32,
+    pub serial: u32,
+    /// Receive offset, receive size, send offset, send size
+    pub boot_mailbox: [u16; 4],
+    pub mailbox: [u16; 4],
+    pub mailbox_protocols: u16,
+    /// EEPROM size in bytes
+    pub eeprom_size: usize,
+    pub version: u16,
+    pub strings: Vec<String>,
+    pub general: Option<General>,
+    pub fmmu: Vec<u8>,
+    pub sync_managers: Vec<SyncManager>,
+    pub txpdos: Vec<Pdo>,
+    pub rxpdos: Vec<Pdo>,
+    /// Other categories, type and data
+    pub other: Vec<(u16, Vec<u8>)>,
+}
+
+fn get_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn get_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+fn strings(data: &[u8]) -> Result<Vec<String>, ParseError> {
+    let truncated = ParseError::Truncated(CAT_STRINGS);
+    let count = *data.first().ok_or(truncated)?;
+    let mut strings = Vec::new();
+    let mut position = 1;
+    for _ in 0..count {
+        let len = *data.get(position).ok_or(truncated)? as usize;
+        let text = data
+            .get(position + 1..position + 1 + len)
+            .ok_or(truncated)?;
+        strings.push(String::from_utf8_lossy(text).into());
+        position += 1 + len;
+    }
+    Ok(strings)
+}
+
+fn general(data: &[u8]) -> Result<General, ParseError> {
+    if data.len() < 18 {
+        return Err(ParseError::Truncated(CAT_GENERAL));
+    }
+    Ok(General {
+        group: data[0],
+        image: data[1],
+        order: data[2],
+        name: data[3],
+        coe_details: data[5],
+        foe_details: data[6],
+        eoe_details: data[7],
+        physical_ports: get_u16(data, 16),
+    })
+}
+
+fn sync_managers(data: &[u8]) -> Vec<SyncManager> {
+    data.chunks_exact(8)
+        .map(|sm| SyncManager {
+            start: get_u16(sm, 0),
+            length: get_u16(sm, 2),
+            control: sm[4],
+            status: sm[5],
+            enable: sm[6],
+            sm_type: sm[7],
+        })
+        .collect()
+}
+
+fn pdos(category: u16, data: &[u8]) -> Result<Vec<Pdo>, ParseError> {
+    let mut pdos = Vec::new();
+    let mut position = 0;
+    while position < data.len() {
+        let header = data
+            .get(position..position + 8)
+            .ok_or(ParseError::Truncated(category))?;
+        let count = header[2] as usize;
+        let entries = data
+            .get(position + 8..position + 8 + count * 8)
+            .ok_or(ParseError::Truncated(category))?;
+        pdos.push(Pdo {
+            index: get_u16(header, 0),
+            sm: header[3],
+            synchronization: header[4],
+            name: header[5],
+            flags: get_u16(header, 6),
+            entries: entries
+                .chunks_exact(8)
+                .map(|entry| PdoEntry {
+                    index: get_u16(entry, 0),
+                    subindex: entry[2],
+                    name: entry[3],
+                    datatype: entry[4],
+                    bit_length: entry[5],
+                    flags: get_u16(entry, 6),
+                })
+                .collect(),
+        });
+        position += 8 + count * 8;
+    }
+    Ok(pdos)
+}
+
+/// Decode an SII image, e.g. an `eeprom.bin`
+pub fn parse(data: &[u8]) -> Result<SiiImage, ParseError> {
+    if data.len() < CATEGORIES + 2 {
+        return Err(ParseError::TooShort);
+    }
+    let word = |word: usize| get_u16(data, word * 2);
+    let mut image = SiiImage {
+        pdi: [word(0), word(1), word(2), word(3)],
+        station_alias: word(4),
+        checksum: data[14],
+        computed_checksum: crc8(&data[..14]),
+        vendor_id: get_u32(data, 0x10),
+        product_code: get_u32(data, 0x14),
+        revision: get_u32(data, 0x18),
+        serial: get_u32(data, 0x1C),
+        boot_mailbox: [word(0x14), word(0x15), word(0x16), word(0x17)],
+        mailbox: [word(0x18), word(0x19), word(0x1A), word(0x1B)],
+        mailbox_protocols: word(0x1C),
+        eeprom_size: (word(0x3E) as usize + 1) * 1024 / 8,
+        version: word(0x3F),
+        ..SiiImage::default()
+    };
+
+    let mut position = CATEGORIES;
+    loop {
+        let category = get_u16(data, position);
+        if category == CAT_END {
+            return Ok(image);
+        }
+        let len = data
+            .get(position + 2..position + 4)
+            .map(|size| get_u16(size, 0) as usize * 2)
+            .ok_or(ParseError::Truncated(category))?;
+        // The next category header (or end marker) must follow
+        let content = data
+            .get(position + 4..position + 4 + len)
+            .filter(|_| position + 4 + len + 2 <= data.len())
+            .ok_or(ParseError::Truncated(category))?;
+        match category {
+            CAT_STRINGS => image.strings = strings(content)?,
+            CAT_GENERAL => image.general = Some(general(content)?),
+            CAT_FMMU => image.fmmu = content.to_vec(),
+            CAT_SYNCM => image.sync_managers = sync_managers(content),
+            CAT_TXPDO => image.txpdos = pdos(category, content)?,
+            CAT_RXPDO => image.rxpdos = pdos(category, content)?,
+            _ => image.other.push((category, content.to_vec())),
+        }
+        position += 4 + len;
+    }
+}
+
+/// Difference between the image and the firmware
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub field: String,
+    pub image: u32,
+    pub firmware: u32,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: image 0x{:X}, firmware 0x{:X}",
+            self.field, self.image, self.firmware
+        )
+    }
+}
+
+impl SiiImage {
+    /// String of a category index, `None` for 0 or past the strings
+    pub fn string(&self, index: u8) -> Option<&str> {
+        let index = (index as usize).checked_sub(1)?;
+        self.strings.get(index).map(String::as_str)
+    }
+
+    /// Compare the image with the firmware: config area checksum, identity
+    /// (0x1018 defaults, but the serial number), mailboxes and sync managers
+    /// (`ecat_options.h`), SM2/SM3 lengths when set (PDO sizes of the
+    /// object dictionary). Every mismatch found is returned.
+    pub fn check(&self) -> Vec<Mismatch> {
+        let mut mismatches = Vec::new();
+        let mut compare = |field: &str, image: u32, firmware: u32| {
+            if image != firmware {
+                mismatches.push(Mismatch {
+                    field: field.into(),
+                    image,
+                    firmware,
+                });
+            }
+        };
+
+        compare(
+            "config checksum",
+            self.checksum as u32,
+            self.computed_checksum as u32,
+        );
+        compare("vendor ID", self.vendor_id, identity(1));
+        compare("product code", self.product_code, identity(2));
+        compare("revision", self.revision, identity(3));
+
+        let fields = ["receive offset", "receive size", "send offset", "send size"];
+        let boot = [MBX0_sma_b, MBX0_sml_b, MBX1_sma_b, MBX1_sml_b];
+        for ((field, image), firmware) in fields.iter().zip(self.boot_mailbox).zip(boot) {
+            compare(
+                &format!("bootstrap mailbox {field}"),
+                image as u32,
+                firmware,
+            );
+        }
+        let standard = [MBX0_sma, MBX0_sml, MBX1_sma, MBX1_sml];
+        for ((field, image), firmware) in fields.iter().zip(self.mailbox).zip(standard) {
+            compare(&format!("mailbox {field}"), image as u32, firmware);
+        }
+
+        // Process data lengths from the PDO assignments
+        let size = |assign| u32::from(pdo_size(assign));
+        let firmware = [
+            (MBX0_sma, MBX0_sml, MBX0_smc, SM_MBX_OUT),
+            (MBX1_sma, MBX1_sml, MBX1_smc, SM_MBX_IN),
+            (SM2_sma, size(RX_PDO_OBJIDX as u16), SM2_smc, SM_OUTPUTS),
+            (SM3_sma, size(TX_PDO_OBJIDX as u16), SM3_smc, SM_INPUTS),
+        ];
+        compare(
+            "sync managers",
+            self.sync_managers.len() as u32,
+            firmware.len() as u32,
+        );
+        for (n, (sm, (start, length, control, sm_type))) in
+            self.sync_managers.iter().zip(firmware).enumerate()
+        {
+            compare(&format!("SM{n} start"), sm.start as u32, start);
+            // 0 leaves the process data size to the master
+            if sm_type <= SM_MBX_IN || sm.length != 0 {
+                compare(&format!("SM{n} length"), sm.length as u32, length);
+            }
+            compare(&format!("SM{n} control"), sm.control as u32, control);
+            compare(&format!("SM{n} type"), sm.sm_type as u32, sm_type as u32);
+        }
+        mismatches
+    }
+}
@@ -1,12 +1,24 @@
-use cty::c_void;
-
+use crate::al::{self, AlState, AlStatusCode};
 use crate::bindings::*;
-use crate::esc_driver::EscDriver;
-use core::slice;
+use crate::coe::{self, pdo, ObjectHandler, PdoMapping};
+use crate::dc;
+use crate::drivers::esc_c::{bind_driver, esc_irq_disable, esc_irq_enable};
+use crate::drivers::{take_driver_error, ALEVENT_MASK, ESCREG_SYNC0_STATUS, ESCREG_SYNC1_STATUS};
+use crate::eeprom::{self, Eeprom};
+use crate::esc;
+use crate::esc_driver::{EscDriver, EscError};
+use crate::foe::{self, FoeFile};
+use crate::mbx::{self, MbxError, MbxProtocol};
+use crate::od;
+use crate::storage::{self, ParameterStorage};
 
-use core::cell::Cell;
 use core::ffi::CStr;
 use core::mem::MaybeUninit;
+use core::ptr::addr_of;
+
+use embassy_futures::select::{select, Either};
+use embassy_time::{Duration, Timer};
+use embedded_hal_async::digital::Wait;
 
 #[no_mangle]
 pub extern "C" fn DPRINT_RUST(msg: *const u8) {
@@ -30,132 +42,154 @@ pub const fn max(a: usize, b: usize) -> usize {
 #[no_mangle]
 pub static mut ESCvar: _ESCvar = unsafe { MaybeUninit::zeroed().assume_init() };
 
-#[no_mangle]
-pub static mut MBXcontrol: [_MBXcontrol; MBXBUFFERS as usize] =
-    unsafe { MaybeUninit::zeroed().assume_init() };
+/// Entries mapped in the RxPDO (SM2)
+pub static mut SMmap2: [PdoMapping; MAX_MAPPINGS_SM2 as usize] =
+    [PdoMapping::EMPTY; MAX_MAPPINGS_SM2 as usize];
 
-pub const MBX_SIZE: usize = MBXBUFFERS as usize * max(MBXSIZE as usize, MBXSIZEBOOT as usize);
-#[no_mangle]
-pub static mut MBX: [u8; MBX_SIZE] = [0; MBX_SIZE];
+/// Entries mapped in the TxPDO (SM3)
+pub static mut SMmap3: [PdoMapping; MAX_MAPPINGS_SM3 as usize] =
+    [PdoMapping::EMPTY; MAX_MAPPINGS_SM3 as usize];
 
-#[no_mangle]
-pub static mut SMmap2: [_SMmap; MAX_MAPPINGS_SM2 as usize] =
-    unsafe { MaybeUninit::zeroed().assume_init() };
+/// AL events raising the interrupt, state and mailbox ones plus the
+/// emulated EEPROM commands when served
+fn irq_events() -> u32 {
+    ALEVENT_MASK | eeprom::events()
+}
 
-#[no_mangle]
-pub static mut SMmap3: [_SMmap; MAX_MAPPINGS_SM3 as usize] =
-    unsafe { MaybeUninit::zeroed().assume_init() };
-
-/// EtherCAT Slave abstraction wrapping SOES and the LAN9252 driver
-//pub struct EcatSlave<D: EscDriver> {
-//    driver: D,
-//    config: esc_cfg,
-//}
-pub struct EcatSlave {
+/// EtherCAT Slave abstraction wrapping SOES and its ESC driver
+pub struct EcatSlave<D: EscDriver> {
+    driver: D,
+    stack: SlaveStack,
+}
+
+// Slave state driven through the C stack, kept apart from the driver so the
+// driver can be bound to `ESC_read`/`ESC_write` while it runs
+struct SlaveStack {
     cfg: esc_cfg,
-    // Global variables
-    //mbx: [[u8; MAX_MBXSIZE]; MBXBUFFERS],
-    //sm2_map: [_SMmap; MAX_MAPPINGS_SM2],
-    //sm3_map: [_SMmap; MAX_MAPPINGS_SM3],
-    //esc_var: _ESCvar,
 
     // Private
     watchdog: i32,
 
-    // Optional PDO buffers
-    //#[cfg(MAX_MAPPINGS_SM2 > 0)]
+    // PDO buffers
     rxpdo: [u8; MAX_RXPDO_SIZE as usize],
-    //#[cfg(MAX_MAPPINGS_SM3 > 0)]
     txpdo: [u8; MAX_TXPDO_SIZE as usize],
 
     //IO callbacks
     output_cb: Option<fn()>,
     input_cb: Option<fn()>,
+
+    // Max number of DL status polls while waiting for ESC startup
+    startup_polls: u32,
 }
 
-impl EcatSlave {
-    pub fn new(cfg: esc_cfg_t) -> Self {
-        let esc_var: _ESCvar = unsafe { MaybeUninit::zeroed().assume_init() };
+/// Default number of DL status polls before `EcatSlave::init` gives up
+pub const DEFAULT_STARTUP_POLLS: u32 = 100_000;
 
+impl SlaveStack {
+    fn new(cfg: esc_cfg_t) -> Self {
         Self {
             cfg,
-            //esc_var,
-            watchdog: cfg.watchdog_cnt as i32,
+            watchdog: cfg.watchdog_cnt,
             rxpdo: [0u8; MAX_RXPDO_SIZE as usize],
             txpdo: [0u8; MAX_TXPDO_SIZE as usize],
             output_cb: None,
             input_cb: None,
+            startup_polls: DEFAULT_STARTUP_POLLS,
         }
     }
 
-    /// Register a custom output callback
-    pub fn set_output_cb(&mut self, cb: fn()) {
-        self.output_cb = Some(cb);
-    }
+    /// Install the default interrupt/DC hooks and configure the C stack
+    fn configure(&mut self) {
+        // Watchdog
+        let watchdog = self.cfg.watchdog_cnt;
+        defmt::debug!("Watchdog count: {}", watchdog);
+
+        // Interrupt mode: default to masking/unmasking AL events, and
+        // accepting DC setups within the 0x1C32 limits
+        if self.irq_mode() {
+            if self.cfg.esc_hw_interrupt_enable.is_none() {
+                self.cfg.esc_hw_interrupt_enable = Some(esc_irq_enable);
+            }
+            if self.cfg.esc_hw_interrupt_disable.is_none() {
+                self.cfg.esc_hw_interrupt_disable = Some(esc_irq_disable);
+            }
+            if self.cfg.esc_check_dc_handler.is_none() {
+                self.cfg.esc_check_dc_handler = Some(dc::dc_check_handler);
+            }
+        }
 
-    /// Register a custom input callback
-    pub fn set_input_cb(&mut self, cb: fn()) {
-        self.input_cb = Some(cb);
+        if self.cfg.pre_object_download_hook.is_some()
+            || self.cfg.post_object_download_hook.is_some()
+            || self.cfg.pre_object_upload_hook.is_some()
+            || self.cfg.post_object_upload_hook.is_some()
+        {
+            defmt::warn!("esc_cfg object hooks are not called, use EcatSlave::set_object_handler");
+        }
+
+        esc::config(&self.cfg);
     }
 
-    /// Initialize the EtherCAT slave stack (equivalent of `ecat_slv_init`)
-    pub fn init(&mut self) {
+    /// Wait for the ESC startup and reset it to INIT
+    fn startup(&mut self) -> Result<(), EscError> {
         unsafe {
-            defmt::info!("Slave stack init started");
-
-            // Watchdog
-            let watchdog = self.cfg.watchdog_cnt;
-            defmt::debug!("Watchdog count: {}", watchdog);
-
-            // Stack + hardware init
-            ESC_config(&mut self.cfg as *mut esc_cfg);
-
             // Wait until ESC startup done
+            let mut polls: u32 = 0;
             loop {
-                unsafe {
-                    ESC_read(
-                        ESCREG_DLSTATUS as u16,
-                        &mut ESCvar.DLstatus as *mut u16 as *mut core::ffi::c_void,
-                        core::mem::size_of::<u16>() as u16,
-                    );
-
-                    // Convert endianness if needed (C etohs)
-                    ESCvar.DLstatus = u16::from_le(ESCvar.DLstatus);
+                let dl_status = esc::read_u16(ESCREG_DLSTATUS as u16);
+                ESCvar.DLstatus = dl_status;
+
+                if let Some(err) = take_driver_error() {
+                    defmt::error!("ESC not reachable during startup: {}", err);
+                    return Err(err);
                 }
 
-                if (ESCvar.DLstatus & 0x0001) != 0 {
-                    defmt::info!("ESC started up (0x{:04x})", ESCvar.DLstatus);
+                if (dl_status & 0x0001) != 0 {
+                    defmt::info!("ESC started up (0x{:04x})", dl_status);
                     break;
                 }
+
+                polls += 1;
+                if polls >= self.startup_polls {
+                    defmt::error!("ESC startup timeout (DL status 0x{:04x})", dl_status);
+                    return Err(EscError::Timeout);
+                }
             }
 
-            // TODO: add FOE_init, EOE_init if enabled in SOES build
+            // TODO: add EOE_init if enabled in SOES build
 
             // Reset ESC to init state
-            ESC_ALstatus(ESCinit as u8);
+            al::write_status(AlState::Init.bits());
             defmt::info!("Writing AL status to ESCInit");
-            ESC_ALerror(ALERR_NONE as u16);
+            al::write_status_code(AlStatusCode::NONE);
             defmt::info!("Remove errors");
-            ESC_stopmbx();
+            al::stop_mbx();
             defmt::info!("Stopping mailbox");
-            ESC_stopinput();
+            al::stop_input();
             defmt::info!("Stopping input");
-            ESC_stopoutput();
+            al::stop_output();
             defmt::info!("Stopping output");
         }
+
+        take_driver_error().map_or(Ok(()), Err)
+    }
+
+    /// Interrupt mode: state and mailbox events raise the IRQ, SM2 (and
+    /// SYNC0) are added by the stack when entering SAFEOP
+    fn enable_irq_events(&mut self) -> Result<(), EscError> {
+        esc::al_event_mask_write(irq_events());
+        defmt::info!("Interrupt mode enabled");
+        take_driver_error().map_or(Ok(()), Err)
+    }
+
+    /// True when the configuration selects interrupt mode (`use_interrupt`)
+    fn irq_mode(&self) -> bool {
+        self.cfg.use_interrupt != 0
     }
 
     /// Print some ESC registers for debugging PDI
-    pub fn pdi_debug(&mut self) {
-        let mut value: u8 = 0;
-        let mut read_print = |addr: u16, name: &str| {
-            unsafe {
-                ESC_read(
-                    addr as u16,
-                    &mut value as *mut _ as *mut core::ffi::c_void,
-                    core::mem::size_of::<u8>() as u16,
-                );
-            }
+    fn pdi_debug(&mut self) {
+        let read_print = |addr: u16, name: &str| {
+            let value = esc::read_u8(addr);
             defmt::info!("{} [0x{:04X}]: {}", name, addr, value);
         };
 
@@ -168,32 +202,25 @@ impl EcatSlave {
     }
 
     /// Polling function
-    pub fn poll(&mut self) {
+    fn poll(&mut self) {
         // Read local time
 
         unsafe {
-            ESC_read(
-                ESCREG_LOCALTIME as u16,
-                &mut ESCvar.Time as *mut u32 as *mut core::ffi::c_void,
-                core::mem::size_of::<u32>() as u16,
-            );
-            ESCvar.Time = u32::from_le(ESCvar.Time); //not sure in need but i need to handle endianess properly :(
-                                                     //defmt::info!("ESC Local Time: {} µs", ESCvar.Time);
+            ESCvar.Time = esc::read_u32(ESCREG_LOCALTIME as u16);
 
             /* Check the state machine */
-            ESC_state();
+            al::state_event();
 
             /* Check the SM activation event */
-            ESC_sm_act_event();
+            al::sm_act_event();
 
             /* Check mailboxes */
-            // Minimal mailbox handling     //need to implement ESC_download_pre_objecthandler etc
-            if ESC_mbxprocess() > 0 {
-                ESC_coeprocess();
-            }
+            mbx::process();
 
-            /* Call emulated eeprom handler if set */
-            if let Some(handler) = ESCvar.esc_hw_eep_handler {
+            /* Emulated EEPROM, or the C eeprom handler if set */
+            if eeprom::enabled() {
+                eeprom::process();
+            } else if let Some(handler) = ESCvar.esc_hw_eep_handler {
                 handler();
             }
         }
@@ -203,7 +230,7 @@ impl EcatSlave {
      * write ethercat inputs. Implement watch-dog counter to count-out if we have
      * made state change affecting the App.state.
      */
-    pub fn dig_process(&mut self, flags: u8) {
+    fn dig_process(&mut self, flags: u8) {
         // Handle watchdog
         if (flags & DIG_PROCESS_WD_FLAG) > 0 {
             if self.watchdog > 0 {
@@ -212,9 +239,7 @@ impl EcatSlave {
 
             if self.watchdog <= 0 && (unsafe { ESCvar.App.state } & APPSTATE_OUTPUT as u8) > 0 {
                 defmt::warn!("DIG_process watchdog expired");
-                unsafe {
-                    ESC_ALstatusgotoerror((ESCsafeop | ESCerror) as u8, ALERR_WATCHDOG as u16);
-                }
+                al::goto_error(AlState::SafeOp, AlStatusCode::WATCHDOG);
             } else if (unsafe { ESCvar.App.state } & APPSTATE_OUTPUT as u8) == 0 {
                 self.watchdog = unsafe { ESCvar.watchdogcnt };
             }
@@ -247,134 +272,336 @@ impl EcatSlave {
         }
 
         // Handle Inputs
-        if (flags & DIG_PROCESS_INPUTS_FLAG) > 0 {
-            if unsafe { ESCvar.App.state } > 0 {
-                if let Some(cb) = self.input_cb {
-                    cb();
-                } else {
-                    defmt::warn!("ESC: No input cb defined!");
-                }
-                self.txpdo_update();
+        if (flags & DIG_PROCESS_INPUTS_FLAG) > 0 && unsafe { ESCvar.App.state } > 0 {
+            if let Some(cb) = self.input_cb {
+                cb();
+            } else {
+                defmt::warn!("ESC: No input cb defined!");
             }
+            self.txpdo_update();
         }
     }
 
     /// Write local process data to Sync Manager 3 (TXPDO).
-    pub fn txpdo_update(&mut self) {
+    fn txpdo_update(&mut self) {
         unsafe {
             if let Some(override_fn) = ESCvar.txpdo_override {
                 override_fn();
             } else {
                 if MAX_MAPPINGS_SM3 > 0 {
-                    COE_pdoPack(
-                        self.txpdo.as_mut_ptr(),
-                        ESCvar.sm3mappings,
-                        SMmap3.as_mut_ptr(),
-                    );
+                    let mappings = &*addr_of!(SMmap3);
+                    pdo::pack(&mut self.txpdo, &mappings[..ESCvar.sm3mappings as usize]);
                 }
-                ESC_write(
-                    ESC_SM3_sma as u16,
-                    self.txpdo.as_mut_ptr() as *mut core::ffi::c_void,
-                    ESCvar.ESC_SM3_sml,
-                );
+                let len = usize::from(ESCvar.ESC_SM3_sml).min(self.txpdo.len());
+                esc::write(ESC_SM3_sma as u16, &self.txpdo[..len]);
             }
         }
     }
 
     /// Read Sync Manager 2 (RXPDO) into local process data.
-    pub fn rxpdo_update(&mut self) {
+    fn rxpdo_update(&mut self) {
         unsafe {
             if let Some(override_fn) = ESCvar.rxpdo_override {
                 override_fn();
             } else {
-                ESC_read(
-                    ESC_SM2_sma as u16,
-                    self.rxpdo.as_mut_ptr() as *mut core::ffi::c_void,
-                    ESCvar.ESC_SM2_sml,
-                );
+                let len = usize::from(ESCvar.ESC_SM2_sml).min(self.rxpdo.len());
+                esc::read(ESC_SM2_sma as u16, &mut self.rxpdo[..len]);
 
                 if MAX_MAPPINGS_SM2 > 0 {
-                    COE_pdoUnpack(
-                        self.rxpdo.as_mut_ptr(),
-                        ESCvar.sm2mappings,
-                        SMmap2.as_mut_ptr(),
-                    );
+                    let mappings = &*addr_of!(SMmap2);
+                    pdo::unpack(&self.rxpdo, &mappings[..ESCvar.sm2mappings as usize]);
                 }
             }
         }
     }
 
-    pub fn print_al_error(&self) {
-        unsafe {
-            if ESCvar.ALerror != 0 {
-                defmt::warn!("AL Error 0x{:04X}\r\n", ESCvar.ALerror);
-            }
+    fn print_al_error(&self) {
+        let al_error = unsafe { addr_of!(ESCvar.ALerror).read() };
+        if al_error != 0 {
+            defmt::warn!("AL Error 0x{:04X}\r\n", al_error);
         }
     }
 
-    pub fn run(&mut self) {
+    /// Move the slave to an error state after a driver failure.
+    ///
+    /// Outputs are stopped (safe output) and the AL status is lowered to
+    /// SAFEOP at most, with the error flag set.
+    fn handle_driver_error(&mut self, err: EscError) {
+        defmt::error!("ESC driver error: {}", err);
+
+        let target = match al::state() {
+            Some(AlState::SafeOp | AlState::Op) => AlState::SafeOp,
+            Some(state) => state,
+            None => AlState::Init,
+        };
+        al::goto_error(target, AlStatusCode::UNSPECIFIED_ERROR);
+
+        // The bus is likely still failing, don't report our own error handling
+        let _ = take_driver_error();
+    }
+
+    // One iteration of the main loop, see `EcatSlave::run`
+    fn run(&mut self) -> Result<(), EscError> {
         self.poll();
-        self.dig_process(
-            DIG_PROCESS_WD_FLAG
-                | DIG_PROCESS_OUTPUTS_FLAG
-                | DIG_PROCESS_APP_HOOK_FLAG
-                | DIG_PROCESS_INPUTS_FLAG,
-        );
+        if self.irq_mode() {
+            self.dig_process(DIG_PROCESS_WD_FLAG);
+        } else {
+            self.dig_process(
+                DIG_PROCESS_WD_FLAG
+                    | DIG_PROCESS_OUTPUTS_FLAG
+                    | DIG_PROCESS_APP_HOOK_FLAG
+                    | DIG_PROCESS_INPUTS_FLAG,
+            );
+        }
 
-        self.print_al_error()
+        self.print_al_error();
+        self.check_driver_error()
     }
-}
 
-/// ESC C bindings will call this --> Will need proper implementation enventually
-#[no_mangle]
-pub extern "C" fn APP_safeoutput() {
-    defmt::info!("APP_safeoutput() called");
-}
+    // ESC interrupt service, see `EcatSlave::irq_handler`
+    fn irq_handler(&mut self) -> Result<(), EscError> {
+        let mut alevent = esc::al_event_read() as u16;
 
-use cty::{c_uchar, c_uint, c_ushort, size_t};
+        if dc::dc_active() {
+            if alevent & ESCREG_ALEVENT_DC_SYNC0 as u16 != 0 {
+                self.sync0_event(alevent);
+            }
+            if alevent & ESCREG_ALEVENT_DC_SYNC1 as u16 != 0 {
+                // Reading the SYNC1 status acknowledges the event
+                esc::read_u8(ESCREG_SYNC1_STATUS);
+                self.dig_process(DIG_PROCESS_INPUTS_FLAG);
+            }
+            alevent = esc::al_event_read() as u16;
+        } else if alevent & ESCREG_ALEVENT_SM2 as u16 != 0 {
+            self.dig_process(
+                DIG_PROCESS_OUTPUTS_FLAG | DIG_PROCESS_APP_HOOK_FLAG | DIG_PROCESS_INPUTS_FLAG,
+            );
+            alevent = esc::al_event_read() as u16;
+        }
 
-#[no_mangle]
-pub extern "C" fn ESC_download_pre_objecthandler(
-    index: c_ushort,
-    subindex: c_uchar,
-    _data: *mut c_void,
-    _size: size_t,
-    _flags: c_ushort,
-) -> c_uint {
-    defmt::warn!("ESC_download_pre_objecthandler called for index {:04x}, subindex {:02x}, but not implemented", index, subindex);
-    0
-}
+        while alevent & irq_events() as u16 != 0 {
+            self.poll();
+            self.check_driver_error()?;
+            alevent = esc::al_event_read() as u16;
+        }
 
-#[no_mangle]
-pub extern "C" fn ESC_download_post_objecthandler(
-    index: c_ushort,
-    subindex: c_uchar,
-    _flags: c_ushort,
-) -> c_uint {
-    defmt::warn!("ESC_download_post_objecthandler called for index {:04x}, subindex {:02x}, but not implemented", index, subindex);
-    0
+        self.check_driver_error()
+    }
+
+    /// One DC cycle: take the outputs received since the last SYNC0, run the
+    /// application and (without SYNC1) write the inputs
+    fn sync0_event(&mut self, alevent: u16) {
+        // Reading the SYNC0 status acknowledges the event
+        esc::read_u8(ESCREG_SYNC0_STATUS);
+
+        let in_op = (unsafe { ESCvar.App.state } & APPSTATE_OUTPUT as u8) > 0;
+        let sm2_seen = alevent & ESCREG_ALEVENT_SM2 as u16 != 0;
+        if in_op && !dc::sync0_cycle(sm2_seen) {
+            defmt::warn!("DC sync error, SM2 events missed");
+            al::goto_error(AlState::SafeOp, AlStatusCode::SYNC_ERROR);
+            return;
+        }
+
+        let mut flags = DIG_PROCESS_OUTPUTS_FLAG | DIG_PROCESS_APP_HOOK_FLAG;
+        if !dc::sync1_active() {
+            flags |= DIG_PROCESS_INPUTS_FLAG;
+        }
+        self.dig_process(flags);
+    }
+
+    fn check_driver_error(&mut self) -> Result<(), EscError> {
+        if let Some(err) = take_driver_error() {
+            self.handle_driver_error(err);
+            return Err(err);
+        }
+        Ok(())
+    }
 }
 
-#[no_mangle]
-pub extern "C" fn ESC_upload_pre_objecthandler(
-    index: c_ushort,
-    subindex: c_uchar,
-    _data: *mut c_void,
-    _size: size_t,
-    _flags: c_ushort,
-) -> c_uint {
-    defmt::warn!("ESC_upload_pre_objecthandler called for index {:04x}, subindex {:02x}, but not implemented", index, subindex);
-    0
+impl<D: EscDriver> EcatSlave<D> {
+    pub fn new(driver: D, cfg: esc_cfg_t) -> Self {
+        Self {
+            driver,
+            stack: SlaveStack::new(cfg),
+        }
+    }
+
+    /// Access the driver between two stack calls
+    pub fn driver(&mut self) -> &mut D {
+        &mut self.driver
+    }
+
+    /// Give the driver back
+    pub fn release(self) -> D {
+        self.driver
+    }
+
+    /// Set how many times `init` polls the DL status for ESC startup before
+    /// failing with `EscError::Timeout`
+    pub fn set_startup_polls(&mut self, polls: u32) {
+        self.stack.startup_polls = polls;
+    }
+
+    /// Register a custom output callback
+    pub fn set_output_cb(&mut self, cb: fn()) {
+        self.stack.output_cb = Some(cb);
+    }
+
+    /// Register a custom input callback
+    pub fn set_input_cb(&mut self, cb: fn()) {
+        self.stack.input_cb = Some(cb);
+    }
+
+    /// Register a mailbox protocol handler (EoE, a vendor protocol, or a
+    /// CoE/FoE server replacing the built-in one)
+    pub fn add_mbx_protocol(
+        &mut self,
+        protocol: &'static mut dyn MbxProtocol,
+    ) -> Result<(), MbxError> {
+        mbx::register(protocol)
+    }
+
+    /// Register a file served over FoE, matched on its name and password
+    pub fn add_foe_file(&mut self, file: &'static mut dyn FoeFile) -> Result<(), MbxError> {
+        foe::add_file(file)
+    }
+
+    /// Register the application hooks on SDO transfers, e.g. to check a
+    /// value before it is written or refresh one before it is read
+    pub fn set_object_handler(&mut self, handler: &'static mut dyn ObjectHandler) {
+        coe::set_object_handler(handler);
+    }
+
+    /// Register the emulated SII EEPROM, served when the ESC is configured
+    /// for PDI emulated EEPROM
+    pub fn set_eeprom(&mut self, eeprom: &'static mut dyn Eeprom) {
+        eeprom::set(eeprom);
+    }
+
+    /// Register the storage of the backup entries, saved on 0x1010 and
+    /// loaded by [`Self::init`] over the default values
+    pub fn set_parameter_storage(&mut self, storage: &'static mut dyn ParameterStorage) {
+        storage::set(storage);
+    }
+
+    // Run `f` with the driver routed to the C `ESC_read`/`ESC_write`
+    fn bound<R>(&mut self, f: impl FnOnce(&mut SlaveStack) -> R) -> R {
+        // SAFETY: the driver field is only reached through the binding while
+        // it lives, `f` gets the (disjoint) stack state
+        let _binding = unsafe { bind_driver(&mut self.driver as *mut D) };
+        f(&mut self.stack)
+    }
+
+    /// Initialize the EtherCAT slave stack (equivalent of `ecat_slv_init`):
+    /// stack configuration, driver init, ESC startup and reset to INIT
+    pub fn init(&mut self) -> Result<(), EscError> {
+        defmt::info!("Slave stack init started");
+
+        self.bound(|stack| stack.configure());
+        od::init_defaults();
+        storage::load();
+
+        if let Err(err) = self.driver.init() {
+            defmt::error!("ESC driver init failed: {}", err);
+            return Err(err);
+        }
+
+        self.bound(|stack| stack.startup())?;
+
+        if self.irq_mode() {
+            if let Err(err) = self.driver.enable_irq() {
+                defmt::error!("ESC IRQ setup failed: {}", err);
+                return Err(err);
+            }
+            self.bound(|stack| stack.enable_irq_events())?;
+        }
+        Ok(())
+    }
+
+    /// True when the configuration selects interrupt mode (`use_interrupt`)
+    pub fn irq_mode(&self) -> bool {
+        self.stack.irq_mode()
+    }
+
+    /// Print some ESC registers for debugging PDI
+    pub fn pdi_debug(&mut self) {
+        self.bound(|stack| stack.pdi_debug())
+    }
+
+    /// Polling function: local time, state machine, SM activation, mailboxes
+    pub fn poll(&mut self) {
+        self.bound(|stack| stack.poll())
+    }
+
+    /// Update local I/O, see the `DIG_PROCESS_*` flags
+    pub fn dig_process(&mut self, flags: u8) {
+        self.bound(|stack| stack.dig_process(flags))
+    }
+
+    /// Write local process data to Sync Manager 3 (TXPDO).
+    pub fn txpdo_update(&mut self) {
+        self.bound(|stack| stack.txpdo_update())
+    }
+
+    /// Read Sync Manager 2 (RXPDO) into local process data.
+    pub fn rxpdo_update(&mut self) {
+        self.bound(|stack| stack.rxpdo_update())
+    }
+
+    pub fn print_al_error(&self) {
+        self.stack.print_al_error()
+    }
+
+    /// Move the slave to an error state after a driver failure.
+    ///
+    /// Outputs are stopped (safe output) and the AL status is lowered to
+    /// SAFEOP at most, with the error flag set.
+    pub fn handle_driver_error(&mut self, err: EscError) {
+        self.bound(|stack| stack.handle_driver_error(err))
+    }
+
+    /// One iteration of the slave main loop.
+    ///
+    /// In interrupt mode only the state machine, mailboxes and watchdog are
+    /// handled here, process data is left to [`Self::irq_handler`].
+    ///
+    /// A driver failure puts the slave in a safe error state and is returned
+    /// to the caller instead of panicking.
+    pub fn run(&mut self) -> Result<(), EscError> {
+        self.bound(|stack| stack.run())
+    }
+
+    /// Service the ESC interrupt (equivalent of `ecat_slv_isr` + `ecat_slv_worker`).
+    ///
+    /// SM2/SYNC0 events run the process data exchange (SYNC1 latches the
+    /// inputs when used), state and mailbox events run the stack until no
+    /// more of them are pending.
+    pub fn irq_handler(&mut self) -> Result<(), EscError> {
+        self.bound(|stack| stack.irq_handler())
+    }
+
+    /// Interrupt mode main loop step for async executors.
+    ///
+    /// Waits for the (active low) ESC IRQ line and services it, or runs
+    /// [`Self::run`] after `poll_period` without interrupt so the watchdog
+    /// and missed events are still handled.
+    pub async fn run_irq<IRQ: Wait>(
+        &mut self,
+        irq: &mut IRQ,
+        poll_period: Duration,
+    ) -> Result<(), EscError> {
+        match select(irq.wait_for_low(), Timer::after(poll_period)).await {
+            Either::First(Ok(())) => self.irq_handler(),
+            Either::First(Err(_)) => Err(EscError::Bus),
+            Either::Second(()) => self.run(),
+        }
+    }
 }
 
+/// ESC C bindings will call this --> Will need proper implementation enventually
 #[no_mangle]
-pub extern "C" fn ESC_upload_post_objecthandler(
-    index: c_ushort,
-    subindex: c_uchar,
-    _flags: c_ushort,
-) -> c_uint {
-    defmt::warn!("ESC_upload_post_objecthandler called for index {:04x}, subindex {:02x}, but not implemented", index, subindex);
-    0
+pub extern "C" fn APP_safeoutput() {
+    defmt::info!("APP_safeoutput() called");
 }
 
 pub const DIG_PROCESS_INPUTS_FLAG: u8 = 0x01;
@@ -0,0 +1,302 @@
+//! Parameter storage: backup entries ([`Entry::backup`]) saved to NOR flash
+//! through the 0x1010 "Store parameters" object, cleared through 0x1011
+//! "Restore default parameters" and loaded by `EcatSlave::init`.
+//!
+//! The image is a header (magic, data length, CRC-32 of the data) followed
+//! by one record per entry: index, subindex, length and value. Records of
+//! entries no longer in the dictionary, or of a different size, are
+//! skipped on load.
+
+use core::ptr::addr_of_mut;
+
+use embedded_storage::nor_flash::NorFlash;
+
+use crate::coe::SdoAbort;
+use crate::od::{self, Access, DataType, Entry, Object};
+
+pub const STORE_PARAMETERS_INDEX: u16 = 0x1010;
+pub const RESTORE_DEFAULTS_INDEX: u16 = 0x1011;
+
+/// Signature written to 0x1010 to save the parameters
+pub const SAVE: u32 = u32::from_le_bytes(*b"save");
+/// Signature written to 0x1011 to restore the defaults
+pub const LOAD: u32 = u32::from_le_bytes(*b"load");
+
+/// 0x1010, sub 1 saves all parameters. Reads 1: saved on command.
+pub const STORE_PARAMETERS: Object = Object::array(
+    STORE_PARAMETERS_INDEX,
+    c"Store parameters",
+    &[
+        Entry::max_subindex(1),
+        Entry::new(1, c"Save all parameters", DataType::Unsigned32, Access::RW).value(1),
+    ],
+);
+
+/// 0x1011, sub 1 restores all default parameters at the next start. Reads
+/// 1: restore supported.
+pub const RESTORE_DEFAULTS: Object = Object::array(
+    RESTORE_DEFAULTS_INDEX,
+    c"Restore default parameters",
+    &[
+        Entry::max_subindex(1),
+        Entry::new(
+            1,
+            c"Restore all default parameters",
+            DataType::Unsigned32,
+            Access::RW,
+        )
+        .value(1),
+    ],
+);
+
+const MAGIC: u32 = u32::from_le_bytes(*b"SOES");
+const HEADER_SIZE: usize = 12;
+/// Index, subindex, reserved, length
+const RECORD_HEADER_SIZE: usize = 6;
+/// Flash transfers, a multiple of the write size
+pub(crate) const CHUNK_SIZE: usize = 64;
+
+/// Parameter storage failures
+#[derive(Clone, Copy, Debug, PartialEq, Eq, defmt::Format)]
+pub enum StorageError {
+    /// Flash read, erase or write failed
+    Flash,
+    /// The parameters do not fit in the storage area
+    Capacity,
+}
+
+/// Parameter storage backend, see [`FlashStorage`]
+pub trait ParameterStorage {
+    /// Save all backup entries
+    fn save(&mut self) -> Result<(), StorageError>;
+
+    /// Load the saved backup entries, `false` when nothing valid is stored
+    fn load(&mut self) -> Result<bool, StorageError>;
+
+    /// Erase the saved parameters, the defaults apply at the next start
+    fn clear(&mut self) -> Result<(), StorageError>;
+}
+
+/// Parameters stored in a NOR flash area
+pub struct FlashStorage<F: NorFlash> {
+    flash: F,
+    offset: u32,
+    size: u32,
+}
+
+impl<F: NorFlash> FlashStorage<F> {
+    /// Storage in the `size` bytes at `offset` of `flash`
+    ///
+    /// # Panics
+    ///
+    /// When `offset` or `size` is not a multiple of the flash erase size, or
+    /// when the flash read or write size does not divide 64 bytes.
+    pub fn new(flash: F, offset: u32, size: u32) -> Self {
+        assert!(aligned(CHUNK_SIZE, F::WRITE_SIZE) && aligned(CHUNK_SIZE, F::READ_SIZE));
+        assert!(aligned(offset as usize, F::ERASE_SIZE) && aligned(size as usize, F::ERASE_SIZE));
+        Self {
+            flash,
+            offset,
+            size,
+        }
+    }
+
+    /// Read `buf` at `position` in the area, through aligned chunks
+    fn read(&mut self, position: usize, buf: &mut [u8]) -> Result<(), StorageError> {
+        let mut chunk = [0; CHUNK_SIZE];
+        let mut done = 0;
+        while done < buf.len() {
+            let at = position + done;
+            let start = at - at % F::READ_SIZE;
+            let skip = at - start;
+            let len = (buf.len() - done).min(CHUNK_SIZE - skip);
+            let read = (skip + len).next_multiple_of(F::READ_SIZE);
+            self.flash
+                .read(self.offset + start as u32, &mut chunk[..read])
+                .map_err(|_| StorageError::Flash)?;
+            buf[done..done + len].copy_from_slice(&chunk[skip..skip + len]);
+            done += len;
+        }
+        Ok(())
+    }
+}
+
+/// `value` is a multiple of the flash access `size`
+pub(crate) const fn aligned(value: usize, size: usize) -> bool {
+    value.is_multiple_of(size)
+}
+
+/// Write the records of all backup entries to `out`, return the data size
+fn records(mut out: impl FnMut(&[u8])) -> usize {
+    let mut size = 0;
+    for object in od::objects() {
+        for entry in object.entries() {
+            if !entry.access().contains(Access::BACKUP) || entry.data().is_null() {
+                continue;
+            }
+            let len = entry.byte_len();
+            let mut header = [0; RECORD_HEADER_SIZE];
+            header[..2].copy_from_slice(&object.index().to_le_bytes());
+            header[2] = entry.subindex();
+            header[4..].copy_from_slice(&(len as u16).to_le_bytes());
+            out(&header);
+
+            let mut value = [0; CHUNK_SIZE];
+            for offset in (0..len).step_by(CHUNK_SIZE) {
+                let n = entry.read_at(offset, &mut value[..(len - offset).min(CHUNK_SIZE)]);
+                out(&value[..n]);
+            }
+            size += RECORD_HEADER_SIZE + len;
+        }
+    }
+    size
+}
+
+/// CRC-32 (IEEE 802.3) update
+pub(crate) fn crc32(crc: u32, data: &[u8]) -> u32 {
+    !data.iter().fold(!crc, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+impl<F: NorFlash> ParameterStorage for FlashStorage<F> {
+    fn save(&mut self) -> Result<(), StorageError> {
+        let mut crc = 0;
+        let size = records(|data| crc = crc32(crc, data));
+        let total = (HEADER_SIZE + size).next_multiple_of(F::ERASE_SIZE);
+        if total > self.size as usize {
+            return Err(StorageError::Capacity);
+        }
+        self.flash
+            .erase(self.offset, self.offset + total as u32)
+            .map_err(|_| StorageError::Flash)?;
+
+        // Stream the header and records through a chunk, padded with the
+        // erased value
+        let mut chunk = [0xFF; CHUNK_SIZE];
+        let mut used = 0;
+        let mut position = self.offset;
+        let mut result = Ok(());
+        let mut write = |mut data: &[u8], flush: bool| {
+            while !data.is_empty() || (flush && used > 0) {
+                let n = data.len().min(CHUNK_SIZE - used);
+                chunk[used..used + n].copy_from_slice(&data[..n]);
+                used += n;
+                data = &data[n..];
+                if used == CHUNK_SIZE || (flush && data.is_empty()) {
+                    let len = used.next_multiple_of(F::WRITE_SIZE);
+                    if result.is_ok() {
+                        result = self.flash.write(position, &chunk[..len]);
+                    }
+                    position += len as u32;
+                    chunk = [0xFF; CHUNK_SIZE];
+                    used = 0;
+                }
+            }
+        };
+        let mut header = [0; HEADER_SIZE];
+        header[..4].copy_from_slice(&MAGIC.to_le_bytes());
+        header[4..8].copy_from_slice(&(size as u32).to_le_bytes());
+        header[8..].copy_from_slice(&crc.to_le_bytes());
+        write(&header, false);
+        records(|data| write(data, false));
+        write(&[], true);
+        result.map_err(|_| StorageError::Flash)
+    }
+
+    fn load(&mut self) -> Result<bool, StorageError> {
+        let mut header = [0; HEADER_SIZE];
+        self.read(0, &mut header)?;
+        let word = |n: usize| u32::from_le_bytes(header[n..n + 4].try_into().unwrap());
+        let size = word(4) as usize;
+        if word(0) != MAGIC || HEADER_SIZE + size > self.size as usize {
+            return Ok(false);
+        }
+
+        let mut chunk = [0; CHUNK_SIZE];
+        let mut crc = 0;
+        for position in (0..size).step_by(CHUNK_SIZE) {
+            let len = (size - position).min(CHUNK_SIZE);
+            self.read(HEADER_SIZE + position, &mut chunk[..len])?;
+            crc = crc32(crc, &chunk[..len]);
+        }
+        if crc != word(8) {
+            defmt::warn!("Stored parameters corrupted, using defaults");
+            return Ok(false);
+        }
+
+        let mut position = HEADER_SIZE;
+        while position + RECORD_HEADER_SIZE <= HEADER_SIZE + size {
+            let mut record = [0; RECORD_HEADER_SIZE];
+            self.read(position, &mut record)?;
+            position += RECORD_HEADER_SIZE;
+            let index = u16::from_le_bytes([record[0], record[1]]);
+            let len = u16::from_le_bytes([record[4], record[5]]) as usize;
+
+            let entry = od::find(index).and_then(|object| object.entry(record[2]));
+            match entry {
+                Some(entry)
+                    if entry.byte_len() == len && entry.access().contains(Access::BACKUP) =>
+                {
+                    for offset in (0..len).step_by(CHUNK_SIZE) {
+                        let n = (len - offset).min(CHUNK_SIZE);
+                        self.read(position + offset, &mut chunk[..n])?;
+                        entry.write_at(offset, &chunk[..n]);
+                    }
+                }
+                _ => defmt::warn!("Stored parameter {:04x}:{:02x} skipped", index, record[2]),
+            }
+            position += len;
+        }
+        Ok(true)
+    }
+
+    fn clear(&mut self) -> Result<(), StorageError> {
+        self.flash
+            .erase(self.offset, self.offset + F::ERASE_SIZE as u32)
+            .map_err(|_| StorageError::Flash)
+    }
+}
+
+static mut STORAGE: Option<&'static mut dyn ParameterStorage> = None;
+
+fn storage() -> Option<&'static mut dyn ParameterStorage> {
+    // SAFETY: set before the stack runs, then used from the mailbox service
+    unsafe { (*addr_of_mut!(STORAGE)).as_deref_mut() }
+}
+
+/// Register the parameter storage
+pub(crate) fn set(storage: &'static mut dyn ParameterStorage) {
+    unsafe { *addr_of_mut!(STORAGE) = Some(storage) };
+}
+
+/// Load the saved parameters, if any, over the defaults
+pub(crate) fn load() {
+    if let Some(storage) = storage() {
+        match storage.load() {
+            Ok(true) => defmt::info!("Parameters loaded"),
+            Ok(false) => {}
+            Err(err) => defmt::error!("Parameter load failed: {}", err),
+        }
+    }
+}
+
+/// SDO download to 0x1010/0x1011: save or clear the parameters when the
+/// signature matches
+pub(crate) fn command(index: u16, value: u32) -> Result<(), SdoAbort> {
+    let storage = storage().ok_or(SdoAbort::DataStoreLocal)?;
+    let result = match (index, value) {
+        (STORE_PARAMETERS_INDEX, SAVE) => storage.save(),
+        (RESTORE_DEFAULTS_INDEX, LOAD) => storage.clear(),
+        _ => return Err(SdoAbort::DataStore),
+    };
+    result.map_err(|err| {
+        defmt::error!("Parameter storage failed: {}", err);
+        match err {
+            StorageError::Flash => SdoAbort::Hardware,
+            StorageError::Capacity => SdoAbort::DataStore,
+        }
+    })
+}
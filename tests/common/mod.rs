@@ -0,0 +1,363 @@
+//! Fixtures shared by the integration tests: host logger, mock ESC, master
+//! side of the mailbox and NOR flash in RAM. Each test uses a part of them.
+#![allow(dead_code)]
+
+use core::convert::Infallible;
+use core::ptr::{self, addr_of_mut};
+use embedded_storage::nor_flash::{ErrorType, NorFlash, ReadNorFlash};
+use std::sync::{Mutex, MutexGuard, Once, PoisonError};
+use SOES_rs::bindings::esc_cfg;
+use SOES_rs::coe::SdoAbort;
+use SOES_rs::esc_driver::{EscDriver, EscError};
+use SOES_rs::foe::FoeError;
+use SOES_rs::mbx::{MbxHeader, MbxType};
+use SOES_rs::od::{Access, DataType, Entry};
+use SOES_rs::soes::EcatSlave;
+
+// The stack logs through defmt, discard it on the host
+#[defmt::global_logger]
+struct NullLogger;
+
+unsafe impl defmt::Logger for NullLogger {
+    fn acquire() {}
+    unsafe fn flush() {}
+    unsafe fn release() {}
+    unsafe fn write(_bytes: &[u8]) {}
+}
+
+defmt::timestamp!("{=u8}", 0);
+
+#[defmt::panic_handler]
+fn defmt_panic() -> ! {
+    panic!("defmt panic")
+}
+
+/// 0x1000 of the dictionaries built with [`minimal_od!`]
+pub static DEVICE_TYPE: Entry =
+    Entry::new(0, c"Device Type", DataType::Unsigned32, Access::RO).value(5001);
+
+/// Object dictionary of 0x1000 and the objects given, for tests without a
+/// dictionary of their own: the CoE server needs one to link
+#[allow(unused_macros)]
+macro_rules! minimal_od {
+    ($($object:expr),* $(,)?) => {
+        SOES_rs::object_dictionary![
+            SOES_rs::od::Object::var(0x1000, &$crate::common::DEVICE_TYPE),
+            $($object,)*
+        ];
+    };
+}
+#[allow(unused_imports)]
+pub(crate) use minimal_od;
+
+/// Serialise the tests of a binary: they share the stack, the registered
+/// mailbox protocols and files and the flash. A failed test does not fail
+/// the following ones.
+pub fn lock() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub const ALCONTROL: usize = 0x120;
+pub const ALSTATUS: usize = 0x130;
+pub const ALERROR: usize = 0x134;
+pub const ALEVENT: usize = 0x220;
+pub const SM0: usize = 0x800;
+pub const SM1: usize = 0x808;
+pub const MBX0: usize = 0x1000;
+pub const MBX1: usize = 0x1200;
+
+pub const EVENT_CONTROL: u16 = 0x01;
+pub const EVENT_SM_CHANGE: u16 = 0x10;
+pub const EVENT_SM0: u16 = 0x100;
+pub const EVENT_SM1: u16 = 0x200;
+
+/// ESC register file and mailbox memory, AL event register mirrored like
+/// the LAN9252 drivers do
+pub struct MockEsc {
+    pub regs: [u8; 0x2000],
+    /// Address and length of each read
+    pub reads: Vec<(usize, usize)>,
+}
+
+impl MockEsc {
+    /// PDI operational, mailboxes configured
+    pub fn new() -> Self {
+        let mut esc = Self::unconfigured();
+        esc.configure_mailboxes();
+        esc
+    }
+
+    /// PDI operational, sync managers left to the test
+    pub fn unconfigured() -> Self {
+        let mut esc = Self {
+            regs: [0; 0x2000],
+            reads: Vec::new(),
+        };
+        // DL status: PDI operational
+        esc.regs[0x110] = 0x01;
+        esc
+    }
+
+    pub fn set_u16(&mut self, address: usize, value: u16) {
+        self.regs[address..address + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn u16(&self, address: usize) -> u16 {
+        u16::from_le_bytes([self.regs[address], self.regs[address + 1]])
+    }
+
+    /// Reads covering `address`, e.g. a clear on read register
+    pub fn reads_of(&self, address: usize) -> usize {
+        let covers = |(start, len): &&(usize, usize)| (*start..start + len).contains(&address);
+        self.reads.iter().filter(covers).count()
+    }
+
+    /// Mailbox SM setup as written by a master from the default SII
+    pub fn configure_mailboxes(&mut self) {
+        self.regs[SM0..SM0 + 8].copy_from_slice(&[0x00, 0x10, 0x00, 0x02, 0x26, 0x00, 0x01, 0x00]);
+        self.regs[SM1..SM1 + 8].copy_from_slice(&[0x00, 0x12, 0x00, 0x02, 0x22, 0x00, 0x01, 0x00]);
+    }
+
+    /// AL control request raising `event`
+    pub fn request(&mut self, control: u16, event: u16) {
+        self.set_u16(ALCONTROL, control);
+        self.set_u16(ALEVENT, event);
+    }
+
+    /// Master writes a mailbox in SM0
+    pub fn master_write(&mut self, header: MbxHeader, data: &[u8]) {
+        self.regs[MBX0..MBX0 + 6].copy_from_slice(&header.to_bytes());
+        self.regs[MBX0 + 6..MBX0 + 6 + data.len()].copy_from_slice(data);
+        self.regs[SM0 + 5] |= 0x08; // mailbox full
+        self.set_u16(ALEVENT, EVENT_SM0);
+    }
+
+    /// Response currently in SM1
+    pub fn response(&self) -> (MbxHeader, &[u8]) {
+        let header = MbxHeader::from_bytes(self.regs[MBX1..MBX1 + 6].try_into().unwrap());
+        let length = header.length as usize;
+        (header, &self.regs[MBX1 + 6..MBX1 + 6 + length])
+    }
+
+    pub fn clear_response(&mut self) {
+        self.regs[MBX1..MBX1 + 0x200].fill(0);
+    }
+
+    /// Events handled, SM0 read by the slave
+    pub fn clear_events(&mut self) {
+        self.regs[SM0 + 5] &= !0x08;
+        self.set_u16(ALEVENT, 0);
+    }
+}
+
+impl EscDriver for MockEsc {
+    fn init(&mut self) -> Result<(), EscError> {
+        Ok(())
+    }
+
+    fn reset(&mut self) -> Result<(), EscError> {
+        Ok(())
+    }
+
+    fn write(&mut self, address: u16, buf: &[u8]) -> Result<(), EscError> {
+        let address = address as usize;
+        self.regs[address..address + buf.len()].copy_from_slice(buf);
+        Ok(())
+    }
+
+    fn read(&mut self, address: u16, buf: &mut [u8]) -> Result<(), EscError> {
+        let address = address as usize;
+        self.reads.push((address, buf.len()));
+        buf.copy_from_slice(&self.regs[address..address + buf.len()]);
+        unsafe { SOES_rs::ESCvar.ALevent = self.u16(ALEVENT) };
+        Ok(())
+    }
+}
+
+pub fn cfg() -> esc_cfg {
+    esc_cfg {
+        user_arg: ptr::null_mut(),
+        use_interrupt: 0,
+        watchdog_cnt: 100,
+        skip_default_initialization: false,
+        set_defaults_hook: None,
+        pre_state_change_hook: None,
+        post_state_change_hook: None,
+        application_hook: None,
+        safeoutput_override: None,
+        pre_object_download_hook: None,
+        post_object_download_hook: None,
+        pre_object_upload_hook: None,
+        post_object_upload_hook: None,
+        rxpdo_override: None,
+        txpdo_override: None,
+        esc_hw_interrupt_enable: None,
+        esc_hw_interrupt_disable: None,
+        esc_hw_eep_handler: None,
+        esc_check_dc_handler: None,
+    }
+}
+
+/// Master side of the mailbox
+pub struct Master {
+    pub slave: EcatSlave<MockEsc>,
+    counter: u8,
+}
+
+impl Master {
+    /// Slave started (power cycle) in PREOP with the mailboxes running
+    pub fn new() -> Self {
+        let mut slave = EcatSlave::new(MockEsc::new(), cfg());
+        slave.init().unwrap();
+        let mut master = Self { slave, counter: 0 };
+        master.state(0x02);
+        master
+    }
+
+    /// Slave in PREOP, `register` run on the first call of the test binary
+    /// to register its files and protocols
+    pub fn registered(register: impl FnOnce(&mut EcatSlave<MockEsc>)) -> Self {
+        static REGISTER: Once = Once::new();
+        let mut master = Self::new();
+        REGISTER.call_once(|| register(&mut master.slave));
+        master
+    }
+
+    /// Request an AL state
+    pub fn state(&mut self, state: u16) {
+        self.slave.driver().request(state, EVENT_CONTROL);
+        self.slave.poll();
+        self.slave.driver().set_u16(ALEVENT, 0);
+    }
+
+    /// Send a mailbox, return the response type and data once read
+    pub fn request(&mut self, mbx_type: MbxType, data: &[u8]) -> (MbxType, Vec<u8>) {
+        self.counter = self.counter % 7 + 1;
+        let header = MbxHeader {
+            counter: self.counter,
+            ..MbxHeader::response(mbx_type, data.len() as u16)
+        };
+        let esc = self.slave.driver();
+        esc.clear_response();
+        esc.master_write(header, data);
+        self.slave.poll();
+
+        self.slave.driver().clear_events();
+        self.read()
+    }
+
+    /// Read the response in the send mailbox
+    pub fn read(&mut self) -> (MbxType, Vec<u8>) {
+        let esc = self.slave.driver();
+        let (header, data) = esc.response();
+        let response = data.to_vec();
+
+        esc.set_u16(ALEVENT, EVENT_SM1);
+        self.slave.poll();
+        self.slave.driver().set_u16(ALEVENT, 0);
+        (header.mbx_type, response)
+    }
+
+    pub fn coe(&mut self, data: &[u8]) -> Vec<u8> {
+        let (mbx_type, response) = self.request(MbxType::COE, data);
+        assert_eq!(mbx_type, MbxType::COE);
+        response
+    }
+
+    pub fn foe(&mut self, data: &[u8]) -> Vec<u8> {
+        let (mbx_type, response) = self.request(MbxType::FOE, data);
+        assert_eq!(mbx_type, MbxType::FOE);
+        response
+    }
+}
+
+/// SDO request frame: CoE header, command, index, subindex, size or data
+pub fn sdo(command: u8, index: u16, subindex: u8, data: [u8; 4]) -> Vec<u8> {
+    let index = index.to_le_bytes();
+    let mut frame = vec![0x00, 0x20, command, index[0], index[1], subindex];
+    frame.extend_from_slice(&data);
+    frame
+}
+
+pub fn sdo_abort(index: u16, subindex: u8, abort: SdoAbort) -> Vec<u8> {
+    sdo(0x80, index, subindex, abort.code().to_le_bytes())
+}
+
+pub fn sdo_response(command: u8, index: u16, subindex: u8, data: [u8; 4]) -> Vec<u8> {
+    let mut frame = sdo(command, index, subindex, data);
+    frame[1] = 0x30;
+    frame
+}
+
+/// FoE frame: opcode, reserved, password / packet number, data
+pub fn foe(opcode: u8, value: u32, data: &[u8]) -> Vec<u8> {
+    let mut frame = vec![opcode, 0];
+    frame.extend_from_slice(&value.to_le_bytes());
+    frame.extend_from_slice(data);
+    frame
+}
+
+pub fn foe_error(error: FoeError) -> Vec<u8> {
+    foe(5, error.code(), error.text().as_bytes())
+}
+
+/// CRC-32 (IEEE 802.3) of `data`
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+pub const FLASH_SIZE: usize = 2048;
+pub const ERASE_SIZE: usize = 256;
+
+static mut FLASH: [u8; FLASH_SIZE] = [0xFF; FLASH_SIZE];
+
+/// Content of the [`RamFlash`]
+pub fn flash() -> &'static mut [u8; FLASH_SIZE] {
+    unsafe { &mut *addr_of_mut!(FLASH) }
+}
+
+/// NOR flash in RAM: writes only go to erased bytes
+pub struct RamFlash;
+
+impl ErrorType for RamFlash {
+    type Error = Infallible;
+}
+
+impl ReadNorFlash for RamFlash {
+    const READ_SIZE: usize = 1;
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Infallible> {
+        let offset = offset as usize;
+        bytes.copy_from_slice(&flash()[offset..offset + bytes.len()]);
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        FLASH_SIZE
+    }
+}
+
+impl NorFlash for RamFlash {
+    const WRITE_SIZE: usize = 4;
+    const ERASE_SIZE: usize = ERASE_SIZE;
+
+    fn erase(&mut self, from: u32, to: u32) -> Result<(), Infallible> {
+        assert!((from | to) as usize & (ERASE_SIZE - 1) == 0);
+        flash()[from as usize..to as usize].fill(0xFF);
+        Ok(())
+    }
+
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Infallible> {
+        assert!(offset & 3 == 0 && bytes.len() & 3 == 0);
+        for (byte, value) in flash()[offset as usize..].iter_mut().zip(bytes) {
+            assert_eq!(*byte, 0xFF, "write to a byte not erased");
+            *byte = *value;
+        }
+        Ok(())
+    }
+}
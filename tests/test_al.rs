@@ -0,0 +1,75 @@
+extern crate SOES_rs;
+mod common;
+
+use common::{cfg, minimal_od, MockEsc, ALERROR, ALSTATUS, SM0, SM1};
+use SOES_rs::al::{self, AlState, AlStatusCode};
+use SOES_rs::esc_driver::EscError;
+use SOES_rs::soes::EcatSlave;
+
+minimal_od!();
+
+/// AL control requests produce the same AL status / AL status code as the
+/// C state machine
+#[test]
+fn test_al_state_machine() {
+    let mut slave = EcatSlave::new(MockEsc::unconfigured(), cfg());
+    slave.init().unwrap();
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x01);
+    assert_eq!(al::state(), Some(AlState::Init));
+
+    // INIT -> OP is refused, error flag set
+    slave.driver().request(0x08, 0x01);
+    slave.poll();
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x11);
+    assert_eq!(slave.driver().u16(ALERROR), 0x11);
+    assert_eq!(al::status_code(), AlStatusCode::INVALID_STATE_CHANGE);
+
+    // Requests without error acknowledge are ignored
+    slave.driver().configure_mailboxes();
+    slave.driver().request(0x02, 0x01);
+    slave.poll();
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x11);
+
+    // PREOP with error acknowledge starts the mailboxes and clears the code
+    slave.driver().request(0x12, 0x01);
+    slave.poll();
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x02);
+    assert_eq!(slave.driver().u16(ALERROR), 0x00);
+    assert!(!al::error_flag());
+    // SM0/SM1 enabled from the PDI side
+    assert_eq!(slave.driver().regs[SM0 + 7] & 0x01, 0);
+    assert_eq!(slave.driver().regs[SM1 + 7] & 0x01, 0);
+
+    // Mailbox SM deactivated by the master: back to INIT with an error
+    slave.driver().regs[SM0 + 6] = 0x00;
+    slave.driver().request(0x02, 0x10);
+    slave.poll();
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x11);
+    assert_eq!(slave.driver().u16(ALERROR), 0x16);
+    assert_eq!(slave.driver().regs[SM0 + 7] & 0x01, 1);
+
+    // A wrong mailbox configuration is refused on INIT -> PREOP
+    slave.driver().regs[SM0 + 6] = 0x01;
+    slave.driver().regs[SM0 + 2] = 0x80;
+    slave.driver().request(0x12, 0x01);
+    slave.poll();
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x11);
+    assert_eq!(al::status_code(), AlStatusCode::INVALID_MBX_CONFIG);
+
+    slave.driver().configure_mailboxes();
+    slave.driver().request(0x12, 0x01);
+    slave.poll();
+    assert_eq!(al::state(), Some(AlState::PreOp));
+
+    // Driver failure: PREOP kept with the error flag
+    slave.handle_driver_error(EscError::Bus);
+    assert!(al::error_flag());
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x12);
+    assert_eq!(slave.driver().u16(ALERROR), 0x01);
+
+    // INIT always acknowledges the error
+    slave.driver().request(0x01, 0x01);
+    slave.poll();
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x01);
+    assert_eq!(slave.driver().u16(ALERROR), 0x00);
+}
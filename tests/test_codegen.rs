@@ -0,0 +1,384 @@
+use SOES_rs::bindings::*;
+use SOES_rs_codegen::{Error, Esi, Layout};
+
+const ESI: &str = r#"{
+  "form": {
+    "VendorName": "Vendor",
+    "VendorID": "0x1337",
+    "ProductCode": "0x00ab123",
+    "ProfileNo": "5001",
+    "RevisionNumber": "2",
+    "SerialNumber": "0x001",
+    "HWversion": "1.0",
+    "EEPROMsize": "4096",
+    "RxMailboxOffset": "0x1000",
+    "TxMailboxOffset": "0x1080",
+    "MailboxSize": "128",
+    "SM2Offset": "0x1100",
+    "SM3Offset": "0x1400",
+    "TextGroupType": "Drive",
+    "TextGroupName5": "Drives",
+    "ImageName": "",
+    "TextDeviceType": "Axis",
+    "TextDeviceName": "Axis \"1\"",
+    "Port0Physical": "Y",
+    "Port1Physical": "K",
+    "ESC": "LAN9252",
+    "SPImode": "3",
+    "CoeDetailsEnableSDO": "EnableSDO",
+    "CoeDetailsEnableSDOInfo": "EnableSDOInfo",
+    "CoeDetailsEnableSDOCompleteAccess": "EnableSDOCompleteAccess",
+    "FoeDetailsEnableFoE": "EnableFoE"
+  },
+  "od": {
+    "sdo": {
+      "2000": {
+        "otype": "VAR",
+        "name": "Offset",
+        "access": "RWpre",
+        "dtype": "INTEGER16",
+        "value": "-5",
+        "data": "&Obj.Offset"
+      },
+      "2001": {
+        "otype": "ARRAY",
+        "name": "Gains",
+        "access": "RW",
+        "dtype": "UNSIGNED8",
+        "items": [
+          { "name": "Max SubIndex" },
+          { "name": "Gain 1", "value": "0x10", "data": "&Obj.Gains[0]" },
+          { "name": "Gain 2", "value": "3", "data": "&Obj.Gains[1]" }
+        ]
+      },
+      "2002": {
+        "otype": "RECORD",
+        "name": "Limits",
+        "items": [
+          { "name": "Max SubIndex" },
+          { "name": "Speed", "dtype": "REAL32", "access": "RW", "value": "1.5", "data": "&Obj.Limits.Speed" },
+          { "name": "Label", "dtype": "VISIBLE_STRING", "value": "axis" }
+        ]
+      }
+    },
+    "txpdo": {
+      "6001": { "otype": "VAR", "name": "Position", "dtype": "INTEGER32", "value": "-1", "data": "&Obj.Position" },
+      "6000": { "otype": "VAR", "name": "Ready", "dtype": "BOOLEAN", "value": "1" }
+    },
+    "rxpdo": {
+      "7000": { "otype": "VAR", "name": "Setpoint", "dtype": "UNSIGNED16", "data": "&Obj.Setpoint" }
+    }
+  },
+  "dc": []
+}"#;
+
+fn esi() -> Esi {
+    Esi::parse(ESI).unwrap()
+}
+
+fn invalid(esi: &str) -> String {
+    let esi = Esi::parse(esi).unwrap();
+    let error = esi
+        .layout(|_| None)
+        .and_then(|layout| esi.objectlist().and(esi.sii_config(&layout)))
+        .unwrap_err();
+    assert!(matches!(error, Error::Invalid(_)), "{error:?}");
+    error.to_string()
+}
+
+/// Value of a `#define` of a generated header
+fn define(header: &str, name: &str) -> u32 {
+    let value = header
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix("#define ")?
+                .strip_prefix(name)?
+                .strip_prefix(' ')
+        })
+        .unwrap()
+        .trim();
+    match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16).unwrap(),
+        None => value.parse().unwrap(),
+    }
+}
+
+/// The demo esi.json is the configuration the stack is compiled with
+#[test]
+fn test_codegen_demo() {
+    let esi = Esi::load("src/soes-c/soes-esi/esi.json".as_ref()).unwrap();
+    let header = esi.layout(|_| None).unwrap().ecat_options();
+    for (name, value) in [
+        ("USE_FOE", USE_FOE),
+        ("USE_EOE", USE_EOE),
+        ("MBXSIZE", MBXSIZE),
+        ("MBXSIZEBOOT", MBXSIZEBOOT),
+        ("MBX0_sma", MBX0_sma),
+        ("MBX1_sma", MBX1_sma),
+        ("MBX0_sma_b", MBX0_sma_b),
+        ("MBX1_sma_b", MBX1_sma_b),
+        ("SM2_sma", SM2_sma),
+        ("SM3_sma", SM3_sma),
+        ("MAX_MAPPINGS_SM2", MAX_MAPPINGS_SM2),
+        ("MAX_MAPPINGS_SM3", MAX_MAPPINGS_SM3),
+    ] {
+        assert_eq!(define(&header, name), value, "{name}");
+    }
+}
+
+/// Mailbox and SM layout, the mapping tables sized as in options.h
+#[test]
+fn test_codegen_options() {
+    let header = esi().layout(|_| None).unwrap().ecat_options();
+    assert!(header.contains("#ifndef __ECAT_OPTIONS_H__\n"));
+    assert!(header.contains("#define MBX0_sme         MBX0_sma+MBX0_sml-1\n"));
+    assert!(header.contains("#define MBX1_sml_b       MBXSIZEBOOT\n"));
+    for (name, value) in [
+        ("USE_FOE", 1),
+        ("USE_EOE", 0),
+        ("MBXSIZE", 128),
+        ("MBX0_sma", 0x1000),
+        ("MBX1_sma", 0x1080),
+        ("MBX1_sma_b", 0x1080),
+        ("SM2_sma", 0x1100),
+        ("SM3_sma", 0x1400),
+        ("MAX_MAPPINGS_SM2", 16),
+        ("MAX_MAPPINGS_SM3", 16),
+    ] {
+        assert_eq!(define(&header, name), value, "{name}");
+    }
+}
+
+/// Defines overridden one by one (`SOES_<DEFINE>` in build scripts)
+#[test]
+fn test_codegen_layout() {
+    let overrides = |define: &str| {
+        let value = match define {
+            "USE_EOE" => "1",
+            "MBXSIZEBOOT" => "0x40",
+            "MBXBUFFERS" => "4",
+            "MBX1_sma_b" => "0x1040",
+            "MAX_MAPPINGS_SM3" => "8",
+            "MAX_RXPDO_SIZE" => "64",
+            _ => return None,
+        };
+        Some(value.to_string())
+    };
+    let layout = esi().layout(overrides).unwrap();
+    assert_eq!(
+        layout,
+        Layout {
+            use_foe: true,
+            use_eoe: true,
+            mbx_size: 128,
+            mbx_size_boot: 64,
+            mbx_buffers: 4,
+            mbx0_sma: 0x1000,
+            mbx1_sma: 0x1080,
+            mbx0_sma_b: 0x1000,
+            mbx1_sma_b: 0x1040,
+            sm2_sma: 0x1100,
+            sm3_sma: 0x1400,
+            max_mappings_sm2: 16,
+            max_mappings_sm3: 8,
+            max_rxpdo_size: 64,
+            max_txpdo_size: 512,
+        }
+    );
+    let header = layout.ecat_options();
+    assert_eq!(define(&header, "MBXBUFFERS"), 4);
+    assert_eq!(define(&header, "MBX1_sma_b"), 0x1040);
+    assert!(header.contains("#define MBX1_sml_b       MBXSIZEBOOT\n"));
+
+    std::env::set_var("SOES_MAX_TXPDO_SIZE", "32");
+    assert_eq!(
+        SOES_rs_codegen::env_override("MAX_TXPDO_SIZE").as_deref(),
+        Some("32")
+    );
+    std::env::set_var("SOES_SM2_sma", "0x1200");
+    assert_eq!(
+        SOES_rs_codegen::env_override("SM2_sma").as_deref(),
+        Some("0x1200")
+    );
+    assert_eq!(SOES_rs_codegen::env_override("MBX0_sma_b"), None);
+
+    let cases = [
+        ("MBXSIZE", "x", "MBXSIZE: invalid number \"x\""),
+        ("MBXBUFFERS", "0", "MBXBUFFERS must not be 0"),
+        (
+            "MBXSIZEBOOT",
+            "0x100",
+            "mailboxes at 0x1000 and 0x1080 overlap (256 bytes)",
+        ),
+        // Ready, padding, Position
+        (
+            "MAX_MAPPINGS_SM3",
+            "2",
+            "MAX_MAPPINGS_SM3 2 is less than the 3 TxPDO entries",
+        ),
+        (
+            "MAX_TXPDO_SIZE",
+            "4",
+            "MAX_TXPDO_SIZE 4 is less than the 5 TxPDO bytes",
+        ),
+        (
+            "SM3_sma",
+            "0x1105",
+            "3 RxPDO buffers of 2 bytes at SM2 0x1100 overlap SM3 0x1105",
+        ),
+        (
+            "MBX1_sma",
+            "0x10C0",
+            "mailbox at 0x10C0 overlaps the SM2 buffers at 0x1100 (6 bytes)",
+        ),
+        (
+            "MBX0_sma",
+            "0x1400",
+            "mailbox at 0x1400 overlaps the SM3 buffers at 0x1400 (15 bytes)",
+        ),
+    ];
+    for (name, value, error) in cases {
+        let error_of = esi()
+            .layout(|define| (define == name).then(|| value.to_string()))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error_of, error);
+    }
+}
+
+/// Storage fields, bound and constant entries, PDO fields
+#[test]
+fn test_codegen_objectlist() {
+    let objectlist = esi().objectlist().unwrap();
+    let expected = [
+        "pub struct Objects {
+    pub serial: u32,
+    pub offset: i16,
+    pub gains: [u8; 2],
+    pub limits_speed: f32,
+    #[txpdo(index = 0x6000, name = \"Ready\")]
+    pub ready: bool,
+    #[txpdo(index = 0x6001, name = \"Position\")]
+    pub position: i32,
+    #[rxpdo(index = 0x7000, name = \"Setpoint\")]
+    pub setpoint: u16,
+}",
+        "pub static mut OBJ: Objects = Objects {
+    serial: 0,
+    offset: 0,
+    gains: [0; 2],
+    limits_speed: 0.0,
+    ready: true,
+    position: -1,
+    setpoint: 0,
+};",
+        "static SDO1008: Entry = Entry::string(0x00, c\"Device Name\", c\"Axis \\\"1\\\"\");",
+        "static SDO1009: Entry = Entry::string(0x00, c\"Hardware Version\", c\"1.0\");",
+        "Entry::new(0x02, c\"Product Code\", DataType::Unsigned32, Access::RO).value(0xAB123),
+    Entry::new(0x03, c\"Revision Number\", DataType::Unsigned32, Access::RO).value(2),",
+        "static SDO2000: Entry = Entry::new(0x00, c\"Offset\", DataType::Integer16, Access::RW_PRE)\
+         .value(-5i16 as u16 as u32).bind(unsafe { addr_of_mut!(OBJ.offset) });",
+        "static SDO2001: [Entry; 3] = [
+    Entry::max_subindex(2),
+    Entry::new(0x01, c\"Gain 1\", DataType::Unsigned8, Access::RW).value(0x10)\
+         .bind(unsafe { addr_of_mut!(OBJ.gains[0]) }),
+    Entry::new(0x02, c\"Gain 2\", DataType::Unsigned8, Access::RW).value(3)\
+         .bind(unsafe { addr_of_mut!(OBJ.gains[1]) }),
+];",
+        "Entry::new(0x01, c\"Speed\", DataType::Real32, Access::RW).value(1.5f32.to_bits())\
+         .bind(unsafe { addr_of_mut!(OBJ.limits_speed) }),
+    Entry::string(0x02, c\"Label\", c\"axis\"),",
+        "    Object::array(0x1C00, c\"Sync Manager Communication Type\", &SDO1C00),
+    Object::var(0x2000, &SDO2000),
+    Object::array(0x2001, c\"Gains\", &SDO2001),
+    Object::record(0x2002, c\"Limits\", &SDO2002),
+    dc::SM2_PARAMETERS,
+    dc::SM3_PARAMETERS;
+    Objects
+];",
+    ];
+    for expected in expected {
+        assert!(objectlist.contains(expected), "missing {expected}");
+    }
+    // No software version
+    assert!(!objectlist.contains("SDO100A"));
+}
+
+#[test]
+fn test_codegen_sii_config() {
+    let esi = esi();
+    let layout = esi.layout(|_| None).unwrap();
+    let config = esi.sii_config(&layout).unwrap();
+    for expected in [
+        "pdi: [0x0680, 0x4403, 0x0064, 0x0000],",
+        "eeprom_size: 4096,",
+        // CoE and FoE
+        "mailbox_protocols: 0x000C,",
+        "group: \"Drive\".into(),",
+        "device_type: \"Axis\".into(),",
+        "coe_details: 0x23,",
+        "foe_details: 1,",
+        "physical_ports: 0x0031,",
+        "vendor_name: \"Vendor\".into(),",
+        "group_name: \"Drives\".into(),",
+    ] {
+        assert!(config.contains(expected), "missing {expected}");
+    }
+
+    // Mailbox protocols follow the overridden layout
+    let no_foe = esi.layout(|define| (define == "USE_FOE").then(|| "0".into()));
+    let config = esi.sii_config(&no_foe.unwrap()).unwrap();
+    assert!(config.contains("mailbox_protocols: 0x0004,"));
+    assert!(config.contains("foe_details: 0,"));
+}
+
+#[test]
+fn test_codegen_errors() {
+    let cases = [
+        (
+            "\"TxMailboxOffset\": \"0x1080\"",
+            "\"TxMailboxOffset\": \"0x1040\"",
+            "mailboxes at 0x1000 and 0x1040 overlap (128 bytes)",
+        ),
+        (
+            "\"SM2Offset\": \"0x1100\"",
+            "\"SM2Offset\": \"0x11G0\"",
+            "SM2Offset: invalid number \"0x11G0\"",
+        ),
+        (
+            "\"dtype\": \"UNSIGNED16\"",
+            "\"dtype\": \"UNSIGNED128\"",
+            "7000: unsupported dtype \"UNSIGNED128\"",
+        ),
+        (
+            "\"otype\": \"VAR\", \"name\": \"Setpoint\"",
+            "\"otype\": \"ARRAY\", \"name\": \"Setpoint\"",
+            "7000: only VAR process data objects are supported",
+        ),
+        (
+            "&Obj.Setpoint",
+            "&Obj.Position",
+            "Obj.position is bound to more than one entry",
+        ),
+        (
+            "\"value\": \"-5\"",
+            "\"value\": \"-40000\"",
+            "2000:00: invalid INTEGER16 \"-40000\"",
+        ),
+        (
+            "\"access\": \"RWpre\"",
+            "\"access\": \"RWop\"",
+            "2000: unsupported access \"RWop\"",
+        ),
+        (
+            "\"ESC\": \"LAN9252\"",
+            "\"ESC\": \"ET1100\"",
+            "ESC ET1100 is not supported, only LAN9252",
+        ),
+    ];
+    for (from, to, error) in cases {
+        assert!(ESI.contains(from), "{from}");
+        assert_eq!(invalid(&ESI.replace(from, to)), error);
+    }
+    assert!(matches!(Esi::parse("{\"form\": 1}"), Err(Error::Json(_))));
+}
@@ -0,0 +1,619 @@
+extern crate SOES_rs;
+mod common;
+
+use common::{sdo, sdo_abort, sdo_response, Master};
+use core::ptr::{addr_of, addr_of_mut};
+use std::sync::Mutex;
+use SOES_rs::coe::{pdo, ObjectHandler, PdoMapping, SdoAbort, SdoAccess, SdoValue};
+use SOES_rs::mbx::{MbxError, MbxType};
+use SOES_rs::object_dictionary;
+use SOES_rs::od::{Access, DataType, Entry, EtherCatObjects, Object};
+
+const BLOB_SIZE: usize = 600;
+/// Enough objects for the OD list to take two frames
+const FILLERS: usize = 300;
+const UNIT: u32 = 0x0001_2D00;
+
+struct Objects {
+    value8: u8,
+    value16: u16,
+    value32: u32,
+    flag: bool,
+    bits: u8,
+    secret: u32,
+    pre: u8,
+    mode: i16,
+    level: u8,
+    gain: f32,
+    blob: [u8; BLOB_SIZE],
+}
+
+static mut OBJ: Objects = Objects {
+    value8: 0,
+    value16: 0,
+    value32: 0,
+    flag: false,
+    bits: 0,
+    secret: 0,
+    pre: 0,
+    mode: 0,
+    level: 1,
+    gain: 0.0,
+    blob: [0; BLOB_SIZE],
+};
+
+static SDO1000: Entry = Entry::new(0, c"Device Type", DataType::Unsigned32, Access::RO).value(5001);
+static SDO1009: Entry = Entry::string(0, c"Hardware Version", c"Hardware v1.0");
+static SDO1600: [Entry; 4] = [
+    Entry::max_subindex(3),
+    Entry::new(1, c"Flag", DataType::Unsigned32, Access::RO).value(0x2002_0101),
+    Entry::new(2, c"Padding", DataType::Unsigned32, Access::RO).value(0x0000_0007),
+    Entry::new(3, c"Value16", DataType::Unsigned32, Access::RO).value(0x2000_0210),
+];
+static SDO1A00: [Entry; 2] = [
+    Entry::max_subindex(1),
+    Entry::new(1, c"Value32", DataType::Unsigned32, Access::RO).value(0x2000_0320),
+];
+static SDO1C12: [Entry; 2] = [
+    Entry::max_subindex(1),
+    Entry::new(1, c"PDO Mapping", DataType::Unsigned16, Access::RO).value(0x1600),
+];
+static SDO1C13: [Entry; 2] = [
+    Entry::max_subindex(1),
+    Entry::new(1, c"PDO Mapping", DataType::Unsigned16, Access::RO).value(0x1A00),
+];
+static SDO2000: [Entry; 4] = [
+    Entry::max_subindex(3),
+    Entry::new(1, c"Value8", DataType::Unsigned8, Access::RW)
+        .settings()
+        .bind(unsafe { addr_of_mut!(OBJ.value8) }),
+    Entry::new(2, c"Value16", DataType::Unsigned16, Access::RW_PRE)
+        .rxpdo()
+        .bind(unsafe { addr_of_mut!(OBJ.value16) }),
+    Entry::new(3, c"Value32", DataType::Unsigned32, Access::RO)
+        .txpdo()
+        .bind(unsafe { addr_of_mut!(OBJ.value32) }),
+];
+static SDO2001: Entry = Entry::new(0, c"Secret", DataType::Unsigned32, Access::WO)
+    .bind(unsafe { addr_of_mut!(OBJ.secret) });
+static SDO2002: [Entry; 3] = [
+    Entry::max_subindex(2),
+    Entry::new(1, c"Flag", DataType::Boolean, Access::RW)
+        .rxpdo()
+        .bind(unsafe { addr_of_mut!(OBJ.flag) }),
+    Entry::new(2, c"Bits", DataType::Bit3, Access::RW).bind(unsafe { addr_of_mut!(OBJ.bits) }),
+];
+static SDO2003: Entry = Entry::new(
+    0,
+    c"Preop Only",
+    DataType::Unsigned8,
+    Access::R_PRE.union(Access::W_PRE),
+)
+.backup()
+.unit(UNIT)
+.range(1, 100)
+.bind(unsafe { addr_of_mut!(OBJ.pre) });
+static SDO2004: Entry = Entry::new(0, c"Blob", DataType::OctetString, Access::RW)
+    .bitlength((BLOB_SIZE * 8) as u16)
+    .bind(unsafe { addr_of_mut!(OBJ.blob) });
+static SDO2005: [Entry; 4] = [
+    Entry::max_subindex(3),
+    Entry::new(1, c"Mode", DataType::Integer16, Access::RW)
+        .range(-10, 10)
+        .bind(unsafe { addr_of_mut!(OBJ.mode) }),
+    Entry::new(2, c"Level", DataType::Unsigned8, Access::RW)
+        .allowed(&[1, 2, 4])
+        .bind(unsafe { addr_of_mut!(OBJ.level) }),
+    Entry::new(3, c"Gain", DataType::Real32, Access::RW)
+        .range(0, 2)
+        .bind(unsafe { addr_of_mut!(OBJ.gain) }),
+];
+static FILLER: Entry = Entry::new(0, c"Filler", DataType::Unsigned8, Access::RO);
+
+/// Objects 0x4000.. to fragment the OD list
+struct Fillers;
+
+impl EtherCatObjects for Fillers {
+    const OBJECTS: &'static [Object] = &{
+        let mut objects = [Object::var(0x4000, &FILLER); FILLERS];
+        let mut i = 0;
+        while i < FILLERS {
+            objects[i] = Object::var(0x4000 + i as u16, &FILLER);
+            i += 1;
+        }
+        objects
+    };
+}
+
+object_dictionary![
+    Object::var(0x1000, &SDO1000),
+    Object::var(0x1009, &SDO1009),
+    Object::record(0x1600, c"RxPDO", &SDO1600),
+    Object::record(0x1A00, c"TxPDO", &SDO1A00),
+    Object::array(0x1C12, c"Sync Manager 2 PDO Assignment", &SDO1C12),
+    Object::array(0x1C13, c"Sync Manager 3 PDO Assignment", &SDO1C13),
+    Object::record(0x2000, c"Values", &SDO2000),
+    Object::var(0x2001, &SDO2001),
+    Object::record(0x2002, c"Bits", &SDO2002),
+    Object::var(0x2003, &SDO2003),
+    Object::var(0x2004, &SDO2004),
+    Object::record(0x2005, c"Limits", &SDO2005);
+    Fillers
+];
+
+#[test]
+fn test_sdo_abort_codes() {
+    assert_eq!(SdoAbort::Toggle.code(), 0x0503_0000);
+    assert_eq!(SdoAbort::NoObject.code(), 0x0602_0000);
+    assert_eq!(SdoAbort::NotInThisState.code(), 0x0800_0022);
+    assert_eq!(SdoAbort::from_code(0x0609_0011), Some(SdoAbort::NoSubindex));
+    assert_eq!(SdoAbort::from_code(0x1234_5678), None);
+}
+
+/// Expedited, normal and segmented transfers, complete access, aborts and
+/// access rights per AL state, checked against recorded frames
+#[test]
+fn test_sdo_server() {
+    let _lock = common::lock();
+    let mut master = Master::new();
+
+    // Expedited upload
+    let response = master.coe(&sdo(0x40, 0x1000, 0, [0; 4]));
+    assert_eq!(response, sdo_response(0x43, 0x1000, 0, [0x89, 0x13, 0, 0]));
+
+    // Normal upload
+    let response = master.coe(&sdo(0x40, 0x1009, 0, [0; 4]));
+    assert_eq!(response[..10], sdo_response(0x41, 0x1009, 0, [13, 0, 0, 0]));
+    assert_eq!(&response[10..], b"Hardware v1.0");
+
+    // Expedited download of 1 and 2 bytes
+    master.coe(&sdo(0x2F, 0x2000, 1, [0xAB, 0, 0, 0]));
+    let response = master.coe(&sdo(0x2B, 0x2000, 2, [0x34, 0x12, 0, 0]));
+    assert_eq!(response, sdo_response(0x60, 0x2000, 2, [0; 4]));
+    unsafe {
+        assert_eq!({ OBJ.value8 }, 0xAB);
+        assert_eq!({ OBJ.value16 }, 0x1234);
+    }
+    let response = master.coe(&sdo(0x40, 0x2000, 2, [0; 4]));
+    assert_eq!(response, sdo_response(0x4B, 0x2000, 2, [0x34, 0x12, 0, 0]));
+
+    // Aborts
+    let response = master.coe(&sdo(0x2B, 0x2000, 1, [0x34, 0x12, 0, 0]));
+    assert_eq!(response, sdo_abort(0x2000, 1, SdoAbort::TypeMismatch));
+    let response = master.coe(&sdo(0x23, 0x1000, 0, [0; 4]));
+    assert_eq!(response, sdo_abort(0x1000, 0, SdoAbort::ReadOnly));
+    let response = master.coe(&sdo(0x40, 0x2001, 0, [0; 4]));
+    assert_eq!(response, sdo_abort(0x2001, 0, SdoAbort::WriteOnly));
+    let response = master.coe(&sdo(0x40, 0x3000, 0, [0; 4]));
+    assert_eq!(response, sdo_abort(0x3000, 0, SdoAbort::NoObject));
+    let response = master.coe(&sdo(0x40, 0x2000, 5, [0; 4]));
+    assert_eq!(response, sdo_abort(0x2000, 5, SdoAbort::NoSubindex));
+    let response = master.coe(&sdo(0xE0, 0x2000, 0, [0; 4]));
+    assert_eq!(response, sdo_abort(0x2000, 0, SdoAbort::UnknownCommand));
+
+    // Complete access upload from subindex 0: subindex 0 on 16 bits
+    unsafe { OBJ.value32 = 0x0403_0201 };
+    let response = master.coe(&sdo(0x50, 0x2000, 0, [0; 4]));
+    assert_eq!(response[..10], sdo_response(0x51, 0x2000, 0, [9, 0, 0, 0]));
+    assert_eq!(response[10..], [3, 0, 0xAB, 0x34, 0x12, 1, 2, 3, 4]);
+
+    // Bit entries are packed
+    unsafe {
+        OBJ.flag = true;
+        OBJ.bits = 5;
+    }
+    let response = master.coe(&sdo(0x50, 0x2002, 0, [0; 4]));
+    assert_eq!(response, sdo_response(0x57, 0x2002, 0, [2, 0, 0x0B, 0]));
+
+    // Complete access download from subindex 1, read-only entries kept
+    let mut request = sdo(0x31, 0x2000, 1, [7, 0, 0, 0]);
+    request.extend_from_slice(&[0x11, 0x22, 0x33, 0xFF, 0xFF, 0xFF, 0xFF]);
+    let response = master.coe(&request);
+    assert_eq!(response, sdo_response(0x70, 0x2000, 1, [0; 4]));
+    unsafe {
+        assert_eq!({ OBJ.value8 }, 0x11);
+        assert_eq!({ OBJ.value16 }, 0x3322);
+        assert_eq!({ OBJ.value32 }, 0x0403_0201);
+    }
+    let response = master.coe(&sdo(0x3F, 0x2002, 1, [0x06, 0, 0, 0]));
+    assert_eq!(response, sdo_response(0x70, 0x2002, 1, [0; 4]));
+    unsafe {
+        assert!(!OBJ.flag);
+        assert_eq!({ OBJ.bits }, 3);
+    }
+
+    // Segmented upload: 496 bytes in the initiate response, 104 in the
+    // last segment
+    unsafe {
+        for (i, byte) in (*addr_of_mut!(OBJ.blob)).iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+    }
+    let blob = unsafe { *addr_of!(OBJ.blob) };
+    let response = master.coe(&sdo(0x40, 0x2004, 0, [0; 4]));
+    assert_eq!(
+        response[..10],
+        sdo_response(0x41, 0x2004, 0, [0x58, 0x02, 0, 0])
+    );
+    assert_eq!(response[10..], blob[..496]);
+    let response = master.coe(&sdo(0x60, 0, 0, [0; 4]));
+    assert_eq!(response[..3], [0x00, 0x30, 0x01]);
+    assert_eq!(response[3..], blob[496..]);
+
+    // Wrong toggle aborts the transfer
+    master.coe(&sdo(0x40, 0x2004, 0, [0; 4]));
+    let response = master.coe(&sdo(0x70, 0, 0, [0; 4]));
+    assert_eq!(response, sdo_abort(0x2004, 0, SdoAbort::Toggle));
+    let response = master.coe(&sdo(0x60, 0, 0, [0; 4]));
+    assert_eq!(response, sdo_abort(0, 0, SdoAbort::UnknownCommand));
+
+    // Segmented download: initiate, a middle and a last segment
+    let mut request = sdo(0x21, 0x2004, 0, [0x58, 0x02, 0, 0]);
+    request.extend((0..496).map(|i| !(i as u8)));
+    let response = master.coe(&request);
+    assert_eq!(response, sdo_response(0x60, 0x2004, 0, [0; 4]));
+    let mut request = vec![0x00, 0x20, 0x00];
+    request.extend((496..546).map(|i| !(i as u8)));
+    let response = master.coe(&request);
+    assert_eq!(response, [0x00, 0x30, 0x20, 0, 0, 0, 0, 0, 0, 0]);
+    let mut request = vec![0x00, 0x20, 0x11];
+    request.extend((546..600).map(|i| !(i as u8)));
+    let response = master.coe(&request);
+    assert_eq!(response, [0x00, 0x30, 0x30, 0, 0, 0, 0, 0, 0, 0]);
+    unsafe {
+        let blob = *addr_of!(OBJ.blob);
+        assert!(blob.iter().enumerate().all(|(i, byte)| *byte == !(i as u8)));
+    }
+
+    // Access rights follow the AL state
+    master.coe(&sdo(0x2F, 0x2003, 0, [7, 0, 0, 0]));
+    unsafe { SOES_rs::ESCvar.ALstatus = 0x04 };
+    let response = master.coe(&sdo(0x2B, 0x2000, 2, [0, 0, 0, 0]));
+    assert_eq!(response, sdo_abort(0x2000, 2, SdoAbort::NotInThisState));
+    let response = master.coe(&sdo(0x40, 0x2003, 0, [0; 4]));
+    assert_eq!(response, sdo_abort(0x2003, 0, SdoAbort::WriteOnly));
+    let response = master.coe(&sdo(0x40, 0x2000, 2, [0; 4]));
+    assert_eq!(response, sdo_response(0x4B, 0x2000, 2, [0x22, 0x33, 0, 0]));
+    unsafe { SOES_rs::ESCvar.ALstatus = 0x02 };
+    let response = master.coe(&sdo(0x40, 0x2003, 0, [0; 4]));
+    assert_eq!(response, sdo_response(0x4F, 0x2003, 0, [7, 0, 0, 0]));
+
+    // No service: mailbox error
+    let (mbx_type, response) = master.request(MbxType::COE, &[0; 10]);
+    assert_eq!(mbx_type, MbxType::ERR);
+    assert_eq!(response[2..4], MbxError::INVALID_HEADER.0.to_le_bytes());
+}
+
+/// SDO Info request frame: CoE header, opcode, fragments left, data
+fn info(opcode: u8, data: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0x00, 0x80, opcode, 0, 0, 0];
+    frame.extend_from_slice(data);
+    frame
+}
+
+fn info_error(abort: SdoAbort) -> Vec<u8> {
+    info(0x07, &abort.code().to_le_bytes())
+}
+
+/// OD lists and their fragments, object and entry descriptions with value
+/// info
+#[test]
+fn test_sdo_info() {
+    let _lock = common::lock();
+    let mut master = Master::new();
+
+    // Number of objects per list: all, RxPDO, TxPDO, backup, settings
+    let response = master.coe(&info(0x01, &[0, 0]));
+    let mut lengths = vec![0, 0];
+    for length in [12 + FILLERS as u16, 2, 1, 1, 1] {
+        lengths.extend_from_slice(&length.to_le_bytes());
+    }
+    assert_eq!(response, info(0x02, &lengths));
+
+    let response = master.coe(&info(0x01, &[2, 0]));
+    assert_eq!(response, info(0x02, &[2, 0, 0x00, 0x20, 0x02, 0x20]));
+    let response = master.coe(&info(0x01, &[4, 0]));
+    assert_eq!(response, info(0x02, &[4, 0, 0x03, 0x20]));
+    let response = master.coe(&info(0x01, &[6, 0]));
+    assert_eq!(response, info_error(SdoAbort::UnsupportedAccess));
+
+    // All objects: 249 indexes fill the first frame, one fragment follows
+    let response = master.coe(&info(0x01, &[1, 0]));
+    assert_eq!(response.len(), 506);
+    assert_eq!(response[..8], [0x00, 0x80, 0x82, 0, 1, 0, 1, 0]);
+    assert_eq!(response[8..12], [0x00, 0x10, 0x09, 0x10]);
+    let (mbx_type, response) = master.read();
+    assert_eq!(mbx_type, MbxType::COE);
+    assert_eq!(response[..6], [0x00, 0x80, 0x02, 0, 0, 0]);
+    let indexes: Vec<u16> = response[6..]
+        .chunks(2)
+        .map(|index| u16::from_le_bytes([index[0], index[1]]))
+        .collect();
+    assert_eq!(
+        indexes,
+        (0x4000 + 237..0x4000 + FILLERS as u16).collect::<Vec<_>>()
+    );
+
+    // Object descriptions: element type for arrays, none for records
+    let response = master.coe(&info(0x03, &[0x12, 0x1C]));
+    let mut expected = info(0x04, &[0x12, 0x1C, 0x06, 0, 1, 8]);
+    expected.extend_from_slice(b"Sync Manager 2 PDO Assignment");
+    assert_eq!(response, expected);
+    let response = master.coe(&info(0x03, &[0x00, 0x20]));
+    assert_eq!(response, info(0x04, b"\x00\x20\x00\x00\x03\x09Values"));
+    let response = master.coe(&info(0x03, &[0x00, 0x30]));
+    assert_eq!(response, info_error(SdoAbort::NoObject));
+
+    // Entry descriptions, value info only for what the entry has
+    let response = master.coe(&info(0x05, &[0x00, 0x10, 0, 0x78]));
+    let mut expected = info(0x06, &[0x00, 0x10, 0, 0x10, 0x07, 0, 32, 0, 0x07, 0]);
+    expected.extend_from_slice(&5001u32.to_le_bytes());
+    expected.extend_from_slice(b"Device Type");
+    assert_eq!(response, expected);
+    let response = master.coe(&info(0x05, &[0x03, 0x20, 0, 0x78]));
+    let mut expected = info(0x06, &[0x03, 0x20, 0, 0x78, 0x05, 0, 8, 0, 0x09, 0x01]);
+    expected.extend_from_slice(&UNIT.to_le_bytes());
+    expected.extend_from_slice(&[0, 1, 100]);
+    expected.extend_from_slice(b"Preop Only");
+    assert_eq!(response, expected);
+    let response = master.coe(&info(0x05, &[0x00, 0x20, 2, 0]));
+    let mut expected = info(0x06, &[0x00, 0x20, 2, 0, 0x06, 0, 16, 0, 0x4F, 0]);
+    expected.extend_from_slice(b"Value16");
+    assert_eq!(response, expected);
+    let response = master.coe(&info(0x05, &[0x00, 0x20, 5, 0]));
+    assert_eq!(response, info_error(SdoAbort::NoSubindex));
+}
+
+// Object handler calls: hook, object, value
+static EVENTS: Mutex<Vec<(&str, SdoAccess, String)>> = Mutex::new(Vec::new());
+// Value 0x2003 is refreshed with before an upload
+static REFRESH: Mutex<Option<u8>> = Mutex::new(None);
+
+struct Hooks;
+
+static mut HOOKS: Hooks = Hooks;
+
+fn event(hook: &'static str, access: &SdoAccess, value: String) {
+    EVENTS.lock().unwrap().push((hook, *access, value));
+}
+
+impl ObjectHandler for Hooks {
+    fn download_pre(&mut self, access: &SdoAccess, value: SdoValue) -> Result<(), SdoAbort> {
+        let described = match value {
+            SdoValue::Bytes(bytes) => format!("Bytes({})", bytes.len()),
+            value => format!("{value:?}"),
+        };
+        event("download_pre", access, described);
+        match (access.index, value) {
+            (0x2003, SdoValue::U8(99)) => Err(SdoAbort::GeneralParameter),
+            (0x2004, SdoValue::Bytes(bytes)) if bytes[0] == 0 => Err(SdoAbort::GeneralParameter),
+            _ => Ok(()),
+        }
+    }
+
+    fn download_post(&mut self, access: &SdoAccess) -> Result<(), SdoAbort> {
+        event("download_post", access, String::new());
+        Ok(())
+    }
+
+    fn upload_pre(&mut self, access: &SdoAccess) -> Result<(), SdoAbort> {
+        event("upload_pre", access, String::new());
+        if let (0x2003, Some(value)) = (access.index, *REFRESH.lock().unwrap()) {
+            unsafe { OBJ.pre = value };
+        }
+        Ok(())
+    }
+
+    fn upload_post(&mut self, access: &SdoAccess) -> Result<(), SdoAbort> {
+        event("upload_post", access, String::new());
+        Ok(())
+    }
+}
+
+fn access(index: u16, subindex: u8, complete_access: bool) -> SdoAccess {
+    SdoAccess {
+        index,
+        subindex,
+        complete_access,
+    }
+}
+
+/// Application hooks see typed values before they are written and can
+/// reject them with an abort code
+#[test]
+fn test_object_handler() {
+    let _lock = common::lock();
+    let mut master = Master::new();
+    master
+        .slave
+        .set_object_handler(unsafe { &mut *addr_of_mut!(HOOKS) });
+    EVENTS.lock().unwrap().clear();
+
+    // Rejected value is not written
+    master.coe(&sdo(0x2F, 0x2003, 0, [5, 0, 0, 0]));
+    let response = master.coe(&sdo(0x2F, 0x2003, 0, [99, 0, 0, 0]));
+    assert_eq!(response, sdo_abort(0x2003, 0, SdoAbort::GeneralParameter));
+    assert_eq!(unsafe { OBJ.pre }, 5);
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [
+            ("download_pre", access(0x2003, 0, false), "U8(5)".into()),
+            ("download_post", access(0x2003, 0, false), String::new()),
+            ("download_pre", access(0x2003, 0, false), "U8(99)".into()),
+        ]
+    );
+    EVENTS.lock().unwrap().clear();
+
+    // Complete access and typed values
+    let mut request = sdo(0x31, 0x2000, 1, [7, 0, 0, 0]);
+    request.extend_from_slice(&[1, 2, 0, 0, 0, 0, 0]);
+    master.coe(&request);
+    master.coe(&sdo(0x2B, 0x2000, 2, [0xCD, 0xAB, 0, 0]));
+    master.coe(&sdo(0x2F, 0x2002, 1, [1, 0, 0, 0]));
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [
+            ("download_pre", access(0x2000, 1, true), "Bytes(7)".into()),
+            ("download_post", access(0x2000, 1, true), String::new()),
+            (
+                "download_pre",
+                access(0x2000, 2, false),
+                "U16(43981)".into()
+            ),
+            ("download_post", access(0x2000, 2, false), String::new()),
+            (
+                "download_pre",
+                access(0x2002, 1, false),
+                "Bool(true)".into()
+            ),
+            ("download_post", access(0x2002, 1, false), String::new()),
+        ]
+    );
+    EVENTS.lock().unwrap().clear();
+
+    // Segmented download checked once complete, rejected before any write
+    unsafe { (*addr_of_mut!(OBJ.blob)).fill(0x55) };
+    let mut request = sdo(0x21, 0x2004, 0, [0x58, 0x02, 0, 0]);
+    request.extend([0; 496]);
+    master.coe(&request);
+    let mut request = vec![0x00, 0x20, 0x01];
+    request.extend([0; 104]);
+    let response = master.coe(&request);
+    assert_eq!(response, sdo_abort(0x2004, 0, SdoAbort::GeneralParameter));
+    assert!(unsafe { *addr_of!(OBJ.blob) }
+        .iter()
+        .all(|byte| *byte == 0x55));
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [(
+            "download_pre",
+            access(0x2004, 0, false),
+            "Bytes(600)".into()
+        )]
+    );
+    EVENTS.lock().unwrap().clear();
+
+    // Value refreshed before an upload
+    *REFRESH.lock().unwrap() = Some(9);
+    let response = master.coe(&sdo(0x40, 0x2003, 0, [0; 4]));
+    *REFRESH.lock().unwrap() = None;
+    assert_eq!(response, sdo_response(0x4F, 0x2003, 0, [9, 0, 0, 0]));
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [
+            ("upload_pre", access(0x2003, 0, false), String::new()),
+            ("upload_post", access(0x2003, 0, false), String::new()),
+        ]
+    );
+}
+
+/// Range and allowed values enforced on downloads and complete access,
+/// reported in the entry descriptions
+#[test]
+fn test_value_limits() {
+    let _lock = common::lock();
+    let mut master = Master::new();
+
+    let response = master.coe(&sdo(0x2F, 0x2003, 0, [0, 0, 0, 0]));
+    assert_eq!(response, sdo_abort(0x2003, 0, SdoAbort::ValueTooLow));
+    let response = master.coe(&sdo(0x2F, 0x2003, 0, [101, 0, 0, 0]));
+    assert_eq!(response, sdo_abort(0x2003, 0, SdoAbort::ValueTooHigh));
+    let response = master.coe(&sdo(0x2F, 0x2003, 0, [100, 0, 0, 0]));
+    assert_eq!(response, sdo_response(0x60, 0x2003, 0, [0; 4]));
+    assert_eq!(unsafe { OBJ.pre }, 100);
+
+    // Signed range
+    let response = master.coe(&sdo(0x2B, 0x2005, 1, [0xF5, 0xFF, 0, 0]));
+    assert_eq!(response, sdo_abort(0x2005, 1, SdoAbort::ValueTooLow));
+    let response = master.coe(&sdo(0x2B, 0x2005, 1, [11, 0, 0, 0]));
+    assert_eq!(response, sdo_abort(0x2005, 1, SdoAbort::ValueTooHigh));
+    master.coe(&sdo(0x2B, 0x2005, 1, [0xF6, 0xFF, 0, 0]));
+    assert_eq!(unsafe { OBJ.mode }, -10);
+
+    // Allowed values
+    let response = master.coe(&sdo(0x2F, 0x2005, 2, [3, 0, 0, 0]));
+    assert_eq!(response, sdo_abort(0x2005, 2, SdoAbort::ValueRange));
+    master.coe(&sdo(0x2F, 0x2005, 2, [4, 0, 0, 0]));
+    assert_eq!(unsafe { OBJ.level }, 4);
+
+    // REAL range
+    let response = master.coe(&sdo(0x23, 0x2005, 3, 2.5f32.to_le_bytes()));
+    assert_eq!(response, sdo_abort(0x2005, 3, SdoAbort::ValueTooHigh));
+    master.coe(&sdo(0x23, 0x2005, 3, 1.5f32.to_le_bytes()));
+    assert_eq!(unsafe { OBJ.gain }, 1.5);
+
+    // Complete access: nothing written when one entry is out of range
+    let mut request = sdo(0x31, 0x2005, 1, [7, 0, 0, 0]);
+    request.extend_from_slice(&[5, 0, 3]);
+    request.extend_from_slice(&1.0f32.to_le_bytes());
+    let response = master.coe(&request);
+    assert_eq!(response, sdo_abort(0x2005, 1, SdoAbort::ValueRange));
+    unsafe {
+        assert_eq!({ OBJ.mode }, -10);
+        assert_eq!({ OBJ.gain }, 1.5);
+    }
+    request[12] = 2;
+    let response = master.coe(&request);
+    assert_eq!(response, sdo_response(0x70, 0x2005, 1, [0; 4]));
+    unsafe {
+        assert_eq!({ OBJ.mode }, 5);
+        assert_eq!({ OBJ.level }, 2);
+        assert_eq!({ OBJ.gain }, 1.0);
+    }
+
+    // Minimum and maximum in the entry descriptions
+    let response = master.coe(&info(0x05, &[0x05, 0x20, 1, 0x60]));
+    assert_eq!(response[6..14], [0x05, 0x20, 1, 0x60, 0x03, 0, 16, 0]);
+    assert_eq!(response[16..20], [0xF6, 0xFF, 10, 0]);
+    let response = master.coe(&info(0x05, &[0x05, 0x20, 2, 0x60]));
+    assert_eq!(response[16..18], [1, 4]);
+    let response = master.coe(&info(0x05, &[0x05, 0x20, 3, 0x60]));
+    assert_eq!(response[16..20], 0f32.to_le_bytes());
+    assert_eq!(response[20..24], 2f32.to_le_bytes());
+}
+
+/// PDO layout from the 0x1C12/0x1C13 assignments, bit and byte entries
+#[test]
+fn test_pdo_mapping() {
+    let _lock = common::lock();
+    let mut rx = [PdoMapping::EMPTY; 3];
+    assert_eq!(pdo::size_of_pdo(0x1C12, &mut rx), Some((3, 3)));
+    assert!(rx[1].entry.is_none());
+    assert_eq!(rx[2].offset, 8);
+    let mut tx = [PdoMapping::EMPTY; 1];
+    assert_eq!(pdo::size_of_pdo(0x1C13, &mut tx), Some((4, 1)));
+
+    // Too many mapped entries
+    let mut small = [PdoMapping::EMPTY; 2];
+    assert_eq!(pdo::size_of_pdo(0x1C12, &mut small), None);
+    // Size only
+    assert_eq!(pdo::size_of_pdo(0x1C12, &mut []), Some((3, 0)));
+
+    pdo::unpack(&[0xFF, 0xCD, 0xAB], &rx);
+    unsafe {
+        assert!(OBJ.flag);
+        assert_eq!({ OBJ.value16 }, 0xABCD);
+    }
+
+    unsafe { OBJ.value32 = 0x1234_5678 };
+    let mut buf = [0; 4];
+    pdo::pack(&mut buf, &tx);
+    assert_eq!(buf, [0x78, 0x56, 0x34, 0x12]);
+
+    // Entries past the end of the buffer are left out
+    let mut buf = [0; 3];
+    pdo::pack(&mut buf, &tx);
+    assert_eq!(buf, [0; 3]);
+    let flag = PdoMapping {
+        offset: 24,
+        ..rx[0]
+    };
+    pdo::pack(&mut buf, &[flag]);
+    assert_eq!(buf, [0; 3]);
+    pdo::unpack(&[0x00, 0x34], &rx[2..]);
+    pdo::unpack(&[0x00; 3], &[flag]);
+    unsafe {
+        assert!(OBJ.flag);
+        assert_eq!({ OBJ.value16 }, 0xABCD);
+    }
+}
@@ -0,0 +1,161 @@
+extern crate SOES_rs;
+mod common;
+
+use common::{cfg, minimal_od, MockEsc, ALERROR, ALEVENT, ALSTATUS, EVENT_CONTROL};
+use SOES_rs::bindings::{SM2_act, SM2_sma, SM2_smc, SM3_act, SM3_sma, SM3_smc};
+use SOES_rs::dc::{
+    self, SM2Param, SM3Param, SYNC_MODE_DC_SYNC0, SYNC_MODE_DC_SYNC1, SYNC_MODE_FREERUN,
+};
+use SOES_rs::soes::EcatSlave;
+
+minimal_od!(dc::SM2_PARAMETERS, dc::SM3_PARAMETERS);
+
+const SM2: usize = 0x810;
+const SM3: usize = 0x818;
+const SYNC_ACT: usize = 0x981;
+const SYNC0_CYCLE: usize = 0x9A0;
+const SYNC1_CYCLE: usize = 0x9A4;
+const EVENT_SYNC0: u16 = 0x04;
+const EVENT_SM2: u16 = 0x400;
+
+/// Activated, SYNC0 and SYNC1 enabled
+const SYNC0_EN: u8 = 0x03;
+const SYNC1_EN: u8 = 0x07;
+
+/// AL events left unmasked: the DC mode is only left through the stack
+unsafe extern "C" fn irq_disable(_mask: u32) {}
+
+/// Slave in PREOP in interrupt mode, SM2/SM3 configured without process data
+fn slave() -> EcatSlave<MockEsc> {
+    let mut cfg = cfg();
+    cfg.use_interrupt = 1;
+    cfg.esc_hw_interrupt_disable = Some(irq_disable);
+    let mut slave = EcatSlave::new(MockEsc::new(), cfg);
+    slave.init().unwrap();
+    let esc = slave.driver();
+    esc.regs[SM2..SM2 + 8].copy_from_slice(&[0, 0, 0, 0, SM2_smc as u8, 0, SM2_act as u8, 0]);
+    esc.set_u16(SM2, SM2_sma as u16);
+    esc.regs[SM3..SM3 + 8].copy_from_slice(&[0, 0, 0, 0, SM3_smc as u8, 0, SM3_act as u8, 0]);
+    esc.set_u16(SM3, SM3_sma as u16);
+    state(&mut slave, 0x02);
+    slave
+}
+
+/// Request an AL state, error acknowledged, return AL status and code
+fn state(slave: &mut EcatSlave<MockEsc>, state: u16) -> (u16, u16) {
+    slave.driver().request(state | 0x10, EVENT_CONTROL);
+    slave.poll();
+    let esc = slave.driver();
+    esc.set_u16(ALEVENT, 0);
+    (esc.u16(ALSTATUS), esc.u16(ALERROR))
+}
+
+/// SYNC unit set up by the master: activation and SYNC0/SYNC1 cycle times
+fn sync(slave: &mut EcatSlave<MockEsc>, activation: u8, sync0: u32, sync1: u32) {
+    let esc = slave.driver();
+    esc.regs[SYNC_ACT] = activation;
+    esc.regs[SYNC0_CYCLE..SYNC0_CYCLE + 4].copy_from_slice(&sync0.to_le_bytes());
+    esc.regs[SYNC1_CYCLE..SYNC1_CYCLE + 4].copy_from_slice(&sync1.to_le_bytes());
+}
+
+/// SYNC0 interrupt, `sm2` when the outputs were received since the last one
+fn sync0(slave: &mut EcatSlave<MockEsc>, sm2: bool) {
+    let event = if sm2 {
+        EVENT_SYNC0 | EVENT_SM2
+    } else {
+        EVENT_SYNC0
+    };
+    slave.driver().set_u16(ALEVENT, event);
+    slave.irq_handler().unwrap();
+    slave.driver().set_u16(ALEVENT, 0);
+}
+
+/// SYNC unit setups refused on PREOP -> SAFEOP, then accepted and published
+/// in 0x1C32/0x1C33
+#[test]
+fn test_dc_check() {
+    let _lock = common::lock();
+    let mut slave = slave();
+    unsafe { SM2Param.sync_mode = SYNC_MODE_FREERUN };
+    dc::set_min_cycle_time(250_000);
+
+    // Activated without SYNC0
+    sync(&mut slave, 0x01, 1_000_000, 0);
+    assert_eq!(state(&mut slave, 0x04), (0x12, 0x30));
+    assert!(!dc::dc_active());
+
+    // SYNC0 cycle below the minimum
+    let too_small = unsafe { SM2Param.cycle_time_too_small };
+    sync(&mut slave, SYNC0_EN, 100_000, 0);
+    assert_eq!(state(&mut slave, 0x04), (0x12, 0x36));
+    assert_eq!(unsafe { SM2Param.cycle_time_too_small }, too_small + 1);
+
+    // Sync mode written by the master not matching the SYNC unit
+    unsafe { SM2Param.sync_mode = SYNC_MODE_DC_SYNC1 };
+    sync(&mut slave, SYNC0_EN, 1_000_000, 0);
+    assert_eq!(state(&mut slave, 0x04), (0x12, 0x30));
+
+    // SYNC0 and SYNC1: accepted
+    sync(&mut slave, SYNC1_EN, 1_000_000, 200_000);
+    assert_eq!(state(&mut slave, 0x04), (0x04, 0x00));
+    assert!(dc::dc_active() && dc::sync1_active());
+    unsafe {
+        assert_eq!({ SM2Param.sync_mode }, SYNC_MODE_DC_SYNC1);
+        assert_eq!({ SM2Param.cycle_time }, 1_000_000);
+        assert_eq!({ SM3Param.sync0_cycle_time }, 1_000_000);
+        assert_eq!({ SM3Param.shift_time }, 200_000);
+    }
+
+    // Back to free run when the inputs stop
+    assert_eq!(state(&mut slave, 0x02), (0x02, 0x00));
+    assert!(!dc::dc_active());
+    unsafe {
+        assert_eq!({ SM2Param.sync_mode }, SYNC_MODE_FREERUN);
+        assert_eq!({ SM3Param.cycle_time }, 0);
+    }
+
+    // SYNC0 only
+    sync(&mut slave, SYNC0_EN, 500_000, 0);
+    assert_eq!(state(&mut slave, 0x04), (0x04, 0x00));
+    assert!(dc::dc_active() && !dc::sync1_active());
+    assert_eq!(unsafe { SM3Param.sync_mode }, SYNC_MODE_DC_SYNC0);
+}
+
+/// Missed SM2 events count +3 and received ones -1, above the limit the
+/// slave drops to SAFEOP with a sync error
+#[test]
+fn test_sync_error_counter() {
+    let _lock = common::lock();
+    let mut slave = slave();
+    unsafe { SM2Param.sync_mode = SYNC_MODE_FREERUN };
+    dc::set_min_cycle_time(250_000);
+    dc::set_sync_error_limit(4);
+    sync(&mut slave, SYNC0_EN, 1_000_000, 0);
+    assert_eq!(state(&mut slave, 0x04), (0x04, 0x00));
+
+    // Not counted before OP
+    sync0(&mut slave, false);
+    sync0(&mut slave, false);
+    assert_eq!(unsafe { SM2Param.sm_event_missed }, 0);
+    assert_eq!(state(&mut slave, 0x08), (0x08, 0x00));
+
+    // 3, 2, 1, 0, 3: within the limit
+    sync0(&mut slave, false);
+    for _ in 0..3 {
+        sync0(&mut slave, true);
+    }
+    sync0(&mut slave, false);
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x08);
+    assert_eq!(unsafe { SM2Param.sm_event_missed }, 2);
+
+    // 2, 5: above the limit
+    sync0(&mut slave, true);
+    sync0(&mut slave, false);
+    assert_eq!(slave.driver().u16(ALSTATUS), 0x14);
+    assert_eq!(slave.driver().u16(ALERROR), 0x1A);
+    unsafe {
+        assert_eq!({ SM2Param.sm_event_missed }, 3);
+        assert_eq!({ SM2Param.sync_error }, 1);
+        assert_eq!({ SM3Param.sync_error }, 1);
+    }
+}
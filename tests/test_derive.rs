@@ -0,0 +1,77 @@
+extern crate SOES_rs;
+use SOES_rs::bindings::{ATYPE_RXPDO, ATYPE_TXPDO, DTYPE_BOOLEAN, OTYPE_ARRAY, OTYPE_RECORD};
+use SOES_rs::object_dictionary;
+use SOES_rs::od::{Access, DataType, Entry, EtherCatObjects, Object};
+
+#[derive(EtherCatObjects)]
+#[ethercat(storage = OBJ)]
+struct ProcessData {
+    #[txpdo(index = 0x6000, name = "Key1")]
+    key1: bool,
+    #[txpdo(index = 0x6001)]
+    key2: bool,
+    #[txpdo(index = 0x6002, name = "Counter")]
+    counter: u32,
+    #[rxpdo(index = 0x7000, name = "LedIn")]
+    led_in: bool,
+    #[allow(dead_code)]
+    scratch: u16,
+}
+
+static mut OBJ: ProcessData = ProcessData {
+    key1: false,
+    key2: false,
+    counter: 0,
+    led_in: false,
+    scratch: 0,
+};
+
+static SDO1000: Entry =
+    Entry::new(0x0, c"Device Type", DataType::Unsigned32, Access::RO).value(5001);
+
+object_dictionary![
+    Object::var(0x1000, &SDO1000);
+    ProcessData
+];
+
+fn mapping(object: &Object) -> Vec<u32> {
+    object.entries()[1..]
+        .iter()
+        .map(Entry::default_value)
+        .collect()
+}
+
+/// The derive generates the PDO objects, the mappings and the assignments
+#[test]
+fn test_derive_process_data() {
+    let indexes: Vec<u16> = SDOobjects.objects().iter().map(Object::index).collect();
+    assert_eq!(
+        indexes,
+        [0x1000, 0x1600, 0x1A00, 0x1C12, 0x1C13, 0x6000, 0x6001, 0x6002, 0x7000]
+    );
+
+    let key1 = SDOobjects.find(0x6000).unwrap().entries()[0];
+    assert_eq!(key1.datatype(), DTYPE_BOOLEAN as u16);
+    assert_eq!(key1.bit_length(), 1);
+    assert!(key1.access().contains(Access(ATYPE_TXPDO as u16)));
+    assert_eq!(key1.data(), unsafe {
+        core::ptr::addr_of_mut!(OBJ.key1) as *mut core::ffi::c_void
+    });
+    let led_in = SDOobjects.find(0x7000).unwrap().entries()[0];
+    assert!(led_in.access().contains(Access(ATYPE_RXPDO as u16)));
+
+    // Bits are padded before the next byte aligned entry and at the end
+    let txpdo = SDOobjects.find(0x1A00).unwrap();
+    assert_eq!(txpdo.object_type(), OTYPE_RECORD as u16);
+    assert_eq!(txpdo.max_subindex(), 4);
+    assert_eq!(mapping(txpdo), [0x60000001, 0x60010001, 6, 0x60020020]);
+    let rxpdo = SDOobjects.find(0x1600).unwrap();
+    assert_eq!(mapping(rxpdo), [0x70000001, 7]);
+
+    let assign = SDOobjects.find(0x1C13).unwrap();
+    assert_eq!(assign.object_type(), OTYPE_ARRAY as u16);
+    assert_eq!(mapping(assign), [0x1A00]);
+    assert_eq!(mapping(SDOobjects.find(0x1C12).unwrap()), [0x1600]);
+
+    assert_eq!(<ProcessData as EtherCatObjects>::OBJECTS.len(), 8);
+}
@@ -0,0 +1,160 @@
+extern crate SOES_rs;
+mod common;
+
+use common::{cfg, flash, minimal_od, MockEsc, RamFlash, ALEVENT, EVENT_CONTROL};
+use core::ptr::addr_of_mut;
+use SOES_rs::eeprom::{FlashEeprom, RamEeprom};
+use SOES_rs::soes::EcatSlave;
+
+const EECONTSTAT: usize = 0x502;
+const EEADDRESS: usize = 0x504;
+const EEDATA: usize = 0x508;
+
+const EVENT_EEP: u16 = 0x20;
+
+/// Busy, 8 byte reads
+const BUSY: u16 = 0x8040;
+const ACK_ERROR: u16 = 0x2000;
+const READ: u16 = 0x100;
+const WRITE: u16 = 0x200;
+const RELOAD: u16 = 0x300;
+
+const EEPROM_SIZE: usize = 256;
+
+minimal_od!();
+
+static mut RAM_IMAGE: [u8; EEPROM_SIZE] = [0; EEPROM_SIZE];
+static mut RAM_EEPROM: Option<RamEeprom> = None;
+static mut FLASH_IMAGE: [u8; EEPROM_SIZE] = [0; EEPROM_SIZE];
+static mut FLASH_EEPROM: Option<FlashEeprom<RamFlash>> = None;
+
+/// Slave with its EEPROM, the master side of the EEPROM interface
+struct Master {
+    slave: EcatSlave<MockEsc>,
+}
+
+impl Master {
+    fn new() -> Self {
+        let mut slave = EcatSlave::new(MockEsc::new(), cfg());
+        slave.init().unwrap();
+        Self { slave }
+    }
+
+    /// Run an EEPROM command, return the acknowledged status
+    fn command(&mut self, command: u16, address: u32) -> u16 {
+        self.status(BUSY | command, address)
+    }
+
+    /// Write the EEPROM status and address, return the status after a poll
+    fn status(&mut self, status: u16, address: u32) -> u16 {
+        let esc = self.slave.driver();
+        esc.set_u16(EECONTSTAT, status);
+        esc.regs[EEADDRESS..EEADDRESS + 4].copy_from_slice(&address.to_le_bytes());
+        esc.set_u16(ALEVENT, EVENT_EEP);
+        self.slave.poll();
+        let esc = self.slave.driver();
+        esc.set_u16(ALEVENT, 0);
+        esc.u16(EECONTSTAT)
+    }
+
+    fn read(&mut self, address: u32) -> [u8; 8] {
+        assert_eq!(self.command(READ, address), BUSY | READ);
+        self.slave.driver().regs[EEDATA..EEDATA + 8]
+            .try_into()
+            .unwrap()
+    }
+
+    fn write(&mut self, address: u32, data: [u8; 2]) {
+        self.slave.driver().regs[EEDATA..EEDATA + 2].copy_from_slice(&data);
+        assert_eq!(self.command(WRITE, address), BUSY | WRITE);
+    }
+
+    /// AL control event: the EEPROM is saved
+    fn control(&mut self) {
+        self.slave.driver().request(0x01, EVENT_CONTROL);
+        self.slave.poll();
+        self.slave.driver().set_u16(ALEVENT, 0);
+    }
+}
+
+/// Reads of 8 (or 4) bytes and writes of 2 bytes at word addresses,
+/// errors acknowledged with the ack error bit
+#[test]
+fn test_eeprom_commands() {
+    let _lock = common::lock();
+    let image = unsafe { &mut *addr_of_mut!(RAM_IMAGE) };
+    for (n, byte) in image.iter_mut().enumerate() {
+        *byte = n as u8;
+    }
+    let mut master = Master::new();
+    unsafe { *addr_of_mut!(RAM_EEPROM) = Some(RamEeprom::new(image)) };
+    master
+        .slave
+        .set_eeprom(unsafe { (*addr_of_mut!(RAM_EEPROM)).as_mut().unwrap() });
+
+    assert_eq!(master.read(4), [8, 9, 10, 11, 12, 13, 14, 15]);
+    master.write(5, [0xAB, 0xCD]);
+    assert_eq!(master.read(4), [8, 9, 0xAB, 0xCD, 12, 13, 14, 15]);
+
+    // 4 byte reads, reload served as a read
+    master.slave.driver().regs[EEDATA..EEDATA + 8].fill(0);
+    let status = master.status(0x8000 | READ, 0);
+    assert_eq!(status, 0x8000 | READ);
+    assert_eq!(
+        master.slave.driver().regs[EEDATA..EEDATA + 8],
+        [0, 1, 2, 3, 0, 0, 0, 0]
+    );
+    assert_eq!(master.command(RELOAD, 2), BUSY | RELOAD);
+    assert_eq!(
+        master.slave.driver().regs[EEDATA..EEDATA + 8],
+        [4, 5, 6, 7, 8, 9, 0xAB, 0xCD]
+    );
+
+    // Past the end, the last bytes read erased
+    assert_eq!(
+        master.read(126),
+        [252, 253, 254, 255, 0xFF, 0xFF, 0xFF, 0xFF]
+    );
+    assert_eq!(master.command(READ, 128), BUSY | READ | ACK_ERROR);
+    assert_eq!(master.command(WRITE, 128), BUSY | WRITE | ACK_ERROR);
+    assert_eq!(master.command(0x700, 0), BUSY | 0x700 | ACK_ERROR);
+
+    // No command pending
+    assert_eq!(master.status(READ | ACK_ERROR, 0), READ | ACK_ERROR);
+}
+
+/// Flash EEPROM: default image while erased, writes saved before the next
+/// read or on an AL control event, loaded at the next start
+#[test]
+fn test_flash_eeprom() {
+    let _lock = common::lock();
+    flash().fill(0xFF);
+    let image = unsafe { &mut *addr_of_mut!(FLASH_IMAGE) };
+    image.fill(0x11);
+    let eeprom = FlashEeprom::new(RamFlash, 256, image).unwrap();
+    unsafe { *addr_of_mut!(FLASH_EEPROM) = Some(eeprom) };
+    let mut master = Master::new();
+    master
+        .slave
+        .set_eeprom(unsafe { (*addr_of_mut!(FLASH_EEPROM)).as_mut().unwrap() });
+
+    assert_eq!(master.read(0), [0x11; 8]);
+    master.write(0, [1, 2]);
+    assert!(flash()[256..512].iter().all(|byte| *byte == 0xFF));
+    assert_eq!(master.read(0), [1, 2, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11]);
+    assert_eq!(flash()[256..260], [1, 2, 0x11, 0x11]);
+
+    master.write(2, [3, 4]);
+    master.control();
+    assert_eq!(flash()[256..262], [1, 2, 0x11, 0x11, 3, 4]);
+
+    // Loaded over the default image
+    let image = unsafe { &mut *addr_of_mut!(FLASH_IMAGE) };
+    image.fill(0x22);
+    let eeprom = FlashEeprom::new(RamFlash, 256, image).unwrap();
+    unsafe { *addr_of_mut!(FLASH_EEPROM) = Some(eeprom) };
+    master
+        .slave
+        .set_eeprom(unsafe { (*addr_of_mut!(FLASH_EEPROM)).as_mut().unwrap() });
+    assert_eq!(master.read(0), [1, 2, 0x11, 0x11, 3, 4, 0x11, 0x11]);
+}
@@ -0,0 +1,275 @@
+#![cfg(feature = "std")]
+
+extern crate SOES_rs;
+mod common;
+
+use core::ptr::addr_of_mut;
+use SOES_rs::esi;
+use SOES_rs::object_dictionary;
+use SOES_rs::od::{Access, DataType, Entry, EtherCatObjects, Object};
+use SOES_rs::sii::{SiiConfig, COE_SDO, COE_SDO_INFO, MBX_COE, MBX_FOE};
+
+#[derive(EtherCatObjects)]
+#[ethercat(storage = OBJ)]
+struct ProcessData {
+    offset: i16,
+    gains: [u8; 2],
+    #[txpdo(index = 0x6000, name = "Key1")]
+    key1: bool,
+    #[rxpdo(index = 0x7000, name = "Setpoint")]
+    setpoint: i32,
+}
+
+static mut OBJ: ProcessData = ProcessData {
+    offset: 0,
+    gains: [0; 2],
+    key1: false,
+    setpoint: 0,
+};
+
+static SDO1000: Entry =
+    Entry::new(0x0, c"Device Type", DataType::Unsigned32, Access::RO).value(0x0002_1389);
+static SDO1008: Entry = Entry::string(0x0, c"Device Name", c"ESI <test> & co");
+static SDO1018: [Entry; 3] = [
+    Entry::max_subindex(2),
+    Entry::new(0x01, c"Vendor ID", DataType::Unsigned32, Access::RO).value(0x1337),
+    Entry::new(0x02, c"Product Code", DataType::Unsigned32, Access::RO).value(0xAB123),
+];
+static SDO2000: [Entry; 3] = [
+    Entry::max_subindex(2),
+    Entry::new(0x01, c"Offset", DataType::Integer16, Access::RW_PRE)
+        .range(-100, 100)
+        .value(-5i16 as u16 as u32)
+        .backup()
+        .bind(unsafe { addr_of_mut!(OBJ.offset) }),
+    Entry::new(0x02, c"Enabled", DataType::Boolean, Access::RW).value(1),
+];
+static SDO2001: [Entry; 3] = [
+    Entry::max_subindex(2),
+    Entry::new(0x01, c"Gain", DataType::Unsigned8, Access::RW)
+        .bind(unsafe { addr_of_mut!(OBJ.gains[0]) }),
+    Entry::new(0x02, c"Gain", DataType::Unsigned8, Access::RW)
+        .bind(unsafe { addr_of_mut!(OBJ.gains[1]) }),
+];
+static SDO2002: Entry =
+    Entry::new(0x0, c"Position", DataType::Integer64, Access::RW).value64(-(1 << 40));
+
+object_dictionary![
+    Object::var(0x1000, &SDO1000),
+    Object::var(0x1008, &SDO1008),
+    Object::record(0x1018, c"Identity Object", &SDO1018),
+    Object::record(0x2000, c"Settings", &SDO2000),
+    Object::array(0x2001, c"Gains", &SDO2001),
+    Object::var(0x2002, &SDO2002);
+    ProcessData
+];
+
+fn config() -> SiiConfig {
+    SiiConfig {
+        mailbox_protocols: MBX_COE | MBX_FOE,
+        coe_details: COE_SDO | COE_SDO_INFO,
+        group: "Drive".into(),
+        device_type: "ESI-1".into(),
+        vendor_name: "Vendor".into(),
+        group_name: "Drives".into(),
+        ..SiiConfig::default()
+    }
+}
+
+/// Tags are balanced and the lines indented by depth
+fn check_well_formed(xml: &str) {
+    let mut stack = vec![];
+    for line in xml.lines().skip(1) {
+        let text = line.trim_start();
+        if let Some(closing) = text.strip_prefix("</") {
+            assert_eq!(stack.pop(), Some(closing.trim_end_matches('>')));
+            assert_eq!(line.len() - text.len(), stack.len() * 2, "{line}");
+            continue;
+        }
+        assert_eq!(line.len() - text.len(), stack.len() * 2, "{line}");
+        if !text.ends_with("/>") && !text.contains("</") {
+            stack.push(text[1..].split([' ', '>']).next().unwrap());
+        }
+    }
+    assert!(stack.is_empty());
+}
+
+/// Identity, device description and mailbox from the dictionary and config
+#[test]
+fn test_esi_device() {
+    let xml = esi::xml(&config()).unwrap();
+    check_well_formed(&xml);
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<EtherCATInfo>\n"));
+    for expected in [
+        "    <Id>#x1337</Id>\n    <Name LcId=\"1033\">Vendor</Name>",
+        "<Type>Drive</Type>\n        <Name LcId=\"1033\">Drives</Name>",
+        "<Device Physics=\"YY\">",
+        "<Type ProductCode=\"#xAB123\" RevisionNo=\"#x0\">ESI-1</Type>",
+        "<Name LcId=\"1033\">ESI &lt;test&gt; &amp; co</Name>",
+        "<ProfileNo>5001</ProfileNo>\n          <AddInfo>2</AddInfo>",
+        "<Fmmu>Outputs</Fmmu>\n        <Fmmu>Inputs</Fmmu>\n        <Fmmu>MBoxState</Fmmu>",
+        "<Sm DefaultSize=\"512\" StartAddress=\"#x1000\" ControlByte=\"#x26\" Enable=\"1\">MBoxOut</Sm>",
+        "<Sm DefaultSize=\"4\" StartAddress=\"#x1600\" ControlByte=\"#x24\" Enable=\"1\">Outputs</Sm>",
+        "<Sm DefaultSize=\"1\" StartAddress=\"#x1A00\" ControlByte=\"#x20\" Enable=\"1\">Inputs</Sm>",
+        "<CoE SdoInfo=\"true\" PdoAssign=\"false\" PdoConfig=\"false\" PdoUpload=\"false\" CompleteAccess=\"false\" />\n          <FoE />",
+        "<Dc>
+          <OpMode>
+            <Name>FreeRun</Name>
+            <Desc>FreeRun/SM-Synchron</Desc>
+            <AssignActivate>#x0</AssignActivate>
+          </OpMode>
+          <OpMode>
+            <Name>DC-SYNC0</Name>
+            <Desc>DC-Synchron (SYNC0)</Desc>
+            <AssignActivate>#x300</AssignActivate>
+          </OpMode>
+          <OpMode>
+            <Name>DC-SYNC1</Name>
+            <Desc>DC-Synchron (SYNC0 + SYNC1)</Desc>
+            <AssignActivate>#x700</AssignActivate>
+          </OpMode>
+        </Dc>",
+        "<ByteSize>2048</ByteSize>\n          <ConfigData>8006004464000000000000000000</ConfigData>",
+    ] {
+        assert!(xml.contains(expected), "missing {expected}");
+    }
+}
+
+/// PDOs with their entries and padding
+#[test]
+fn test_esi_pdos() {
+    let xml = esi::xml(&config()).unwrap();
+    let rxpdo = "
+        <RxPdo Fixed=\"true\" Mandatory=\"true\" Sm=\"2\">
+          <Index>#x1600</Index>
+          <Name>Outputs</Name>
+          <Entry>
+            <Index>#x7000</Index>
+            <SubIndex>0</SubIndex>
+            <BitLen>32</BitLen>
+            <Name>Setpoint</Name>
+            <DataType>DINT</DataType>
+          </Entry>
+        </RxPdo>
+        <TxPdo Fixed=\"true\" Mandatory=\"true\" Sm=\"3\">
+          <Index>#x1A00</Index>
+          <Name>Inputs</Name>
+          <Entry>
+            <Index>#x6000</Index>
+            <SubIndex>0</SubIndex>
+            <BitLen>1</BitLen>
+            <Name>Key1</Name>
+            <DataType>BOOL</DataType>
+          </Entry>
+          <Entry>
+            <Index>#x0000</Index>
+            <SubIndex>0</SubIndex>
+            <BitLen>7</BitLen>
+          </Entry>
+        </TxPdo>
+";
+    assert!(xml.contains(rxpdo));
+}
+
+/// Records, arrays and variables with their data types, access and values
+#[test]
+fn test_esi_dictionary() {
+    let xml = esi::xml(&config()).unwrap();
+    let record = "
+              <DataType>
+                <Name>DT2000</Name>
+                <BitSize>40</BitSize>
+                <SubItem>
+                  <SubIdx>0</SubIdx>
+                  <Name>Max SubIndex</Name>
+                  <Type>USINT</Type>
+                  <BitSize>8</BitSize>
+                  <BitOffs>0</BitOffs>
+                  <Flags>
+                    <Access>ro</Access>
+                  </Flags>
+                </SubItem>
+                <SubItem>
+                  <SubIdx>1</SubIdx>
+                  <Name>Offset</Name>
+                  <Type>INT</Type>
+                  <BitSize>16</BitSize>
+                  <BitOffs>16</BitOffs>
+                  <Flags>
+                    <Access WriteRestrictions=\"PreOP\">rw</Access>
+                    <Backup>1</Backup>
+                  </Flags>
+                </SubItem>
+                <SubItem>
+                  <SubIdx>2</SubIdx>
+                  <Name>Enabled</Name>
+                  <Type>BOOL</Type>
+                  <BitSize>1</BitSize>
+                  <BitOffs>32</BitOffs>
+";
+    assert!(xml.contains(record));
+
+    let array = "
+              <DataType>
+                <Name>DT2001ARR</Name>
+                <BaseType>USINT</BaseType>
+                <BitSize>16</BitSize>
+                <ArrayInfo>
+                  <LBound>1</LBound>
+                  <Elements>2</Elements>
+                </ArrayInfo>
+              </DataType>
+              <DataType>
+                <Name>DT2001</Name>
+                <BitSize>32</BitSize>
+";
+    assert!(xml.contains(array));
+    for simple in [
+        "<Name>STRING(15)</Name>\n                <BitSize>120</BitSize>",
+        "<Name>DINT</Name>\n                <BitSize>32</BitSize>",
+        "<Name>INT</Name>\n                <BitSize>16</BitSize>",
+    ] {
+        assert!(xml.contains(simple), "missing {simple}");
+    }
+
+    let objects = [
+        "<Index>#x1008</Index>
+                <Name>Device Name</Name>
+                <Type>STRING(15)</Type>
+                <BitSize>120</BitSize>
+                <Info>
+                  <DefaultString>ESI &lt;test&gt; &amp; co</DefaultString>
+                </Info>",
+        "<Name>Offset</Name>
+                    <Info>
+                      <MinValue>-100</MinValue>
+                      <MaxValue>100</MaxValue>
+                      <DefaultValue>-5</DefaultValue>
+                    </Info>",
+        "<Index>#x6000</Index>
+                <Name>Key1</Name>
+                <Type>BOOL</Type>
+                <BitSize>1</BitSize>
+                <Info>
+                  <DefaultValue>0</DefaultValue>
+                </Info>
+                <Flags>
+                  <Access>ro</Access>
+                  <PdoMapping>T</PdoMapping>
+                </Flags>",
+        "<Name>PDO Mapping</Name>
+                    <Info>
+                      <DefaultValue>#x1600</DefaultValue>
+                    </Info>",
+        "<Index>#x2002</Index>
+                <Name>Position</Name>
+                <Type>LINT</Type>
+                <BitSize>64</BitSize>
+                <Info>
+                  <DefaultValue>-1099511627776</DefaultValue>
+                </Info>",
+    ];
+    for object in objects {
+        assert!(xml.contains(object), "missing {object}");
+    }
+}
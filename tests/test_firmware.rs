@@ -0,0 +1,175 @@
+extern crate SOES_rs;
+mod common;
+
+use common::{crc32, flash, foe, foe_error, minimal_od, Master, RamFlash, ALSTATUS, FLASH_SIZE};
+use core::ptr::addr_of_mut;
+use std::sync::Mutex;
+use SOES_rs::firmware::{Bootloader, FirmwareUpdate};
+use SOES_rs::foe::FoeError;
+use SOES_rs::mbx::MbxType;
+use SOES_rs::storage::StorageError;
+
+/// Data packet size with 512 byte boot mailboxes
+const PACKET: usize = 500;
+const PASSWORD: u32 = 0x0B00_7000;
+
+minimal_od!();
+
+/// Records the firmware marked for swap
+struct MockBootloader;
+
+static MARKED: Mutex<Option<u32>> = Mutex::new(None);
+
+impl Bootloader for MockBootloader {
+    fn mark_updated(&mut self, size: u32) -> Result<(), StorageError> {
+        *MARKED.lock().unwrap() = Some(size);
+        Ok(())
+    }
+}
+
+static mut UPDATE: Option<FirmwareUpdate<RamFlash, MockBootloader>> = None;
+
+/// Slave with the update file registered, `state` requested after PREOP
+fn master(state: u16) -> Master {
+    flash().fill(0);
+    *MARKED.lock().unwrap() = None;
+    let mut master = Master::registered(|slave| {
+        let update = FirmwareUpdate::new("firmware.efw", RamFlash, MockBootloader);
+        unsafe { *addr_of_mut!(UPDATE) = Some(update.password(PASSWORD)) };
+        slave
+            .add_foe_file(unsafe { (*addr_of_mut!(UPDATE)).as_mut().unwrap() })
+            .unwrap();
+    });
+    if state == 0x03 {
+        master.state(0x01);
+    }
+    master.state(state);
+    assert_eq!(master.slave.driver().u16(ALSTATUS), state);
+    master
+}
+
+/// Firmware followed by its CRC
+fn image(size: usize) -> Vec<u8> {
+    let mut image: Vec<u8> = (0..size).map(|n| (n * 13) as u8).collect();
+    image.extend_from_slice(&crc32(&image).to_le_bytes());
+    image
+}
+
+/// Send a data packet again while the slave answers busy
+fn data(master: &mut Master, packet: u32, data: &[u8]) -> Vec<u8> {
+    loop {
+        let response = master.foe(&foe(3, packet, data));
+        if response[0] != 6 {
+            return response;
+        }
+    }
+}
+
+/// Write the image packet by packet, return the last answer
+fn write(master: &mut Master, image: &[u8]) -> Vec<u8> {
+    let response = master.foe(&foe(2, PASSWORD, b"firmware.efw"));
+    assert_eq!(response, foe(4, 0, &[]));
+    let mut response = Vec::new();
+    for (n, packet) in image.chunks(PACKET).enumerate() {
+        response = data(master, n as u32 + 1, packet);
+    }
+    response
+}
+
+/// The firmware is only written in BOOT
+#[test]
+fn test_boot_only() {
+    let _lock = common::lock();
+    let mut master = master(0x02);
+    let response = master.foe(&foe(2, PASSWORD, b"firmware.efw"));
+    assert_eq!(response, foe_error(FoeError::NotInBootstrap));
+}
+
+/// Firmware received in BOOT, erased block by block behind busy answers,
+/// checked and marked for swap when BOOT is left
+#[test]
+fn test_good_image() {
+    let _lock = common::lock();
+    let mut master = master(0x03);
+
+    // The first packet waits for the first two blocks
+    let response = master.foe(&foe(2, PASSWORD, b"firmware.efw"));
+    assert_eq!(response, foe(4, 0, &[]));
+    let image = image(1000);
+    let response = master.foe(&foe(3, 1, &image[..PACKET]));
+    assert_eq!(response, foe(6, 0x0008_0001, &[]));
+    let response = master.foe(&foe(3, 1, &image[..PACKET]));
+    assert_eq!(response, foe(6, 0x0008_0002, &[]));
+    let response = master.foe(&foe(3, 1, &image[..PACKET]));
+    assert_eq!(response, foe(4, 1, &[]));
+    data(&mut master, 2, &image[PACKET..2 * PACKET]);
+    let response = data(&mut master, 3, &image[2 * PACKET..]);
+    assert_eq!(response, foe(4, 3, &[]));
+    assert_eq!(&flash()[..image.len()], &image[..]);
+    assert_eq!(*MARKED.lock().unwrap(), None);
+
+    master.state(0x01);
+    assert_eq!(*MARKED.lock().unwrap(), Some(1000));
+}
+
+/// Corrupted image refused and not marked
+#[test]
+fn test_bad_crc() {
+    let _lock = common::lock();
+    let mut master = master(0x03);
+    let mut image = image(700);
+    image[10] ^= 0x01;
+    let response = write(&mut master, &image);
+    assert_eq!(response, foe_error(FoeError::Checksum));
+    master.state(0x01);
+    assert_eq!(*MARKED.lock().unwrap(), None);
+}
+
+/// Write request refused without the password, nothing erased
+#[test]
+fn test_wrong_password() {
+    let _lock = common::lock();
+    let mut master = master(0x03);
+    let response = master.foe(&foe(2, PASSWORD + 1, b"firmware.efw"));
+    assert_eq!(response, foe_error(FoeError::NoRights));
+    let response = master.foe(&foe(3, 1, &image(100)));
+    assert_eq!(response, foe_error(FoeError::Illegal));
+    assert!(flash().iter().all(|byte| *byte == 0));
+    master.state(0x01);
+    assert_eq!(*MARKED.lock().unwrap(), None);
+}
+
+/// Transfer aborted by the master: nothing marked, the next one starts over
+#[test]
+fn test_abort() {
+    let _lock = common::lock();
+    let mut master = master(0x03);
+    let image = image(1000);
+    let response = master.foe(&foe(2, PASSWORD, b"firmware.efw"));
+    assert_eq!(response, foe(4, 0, &[]));
+    let response = data(&mut master, 1, &image[..PACKET]);
+    assert_eq!(response, foe(4, 1, &[]));
+
+    // Error from the master, no answer
+    let (mbx_type, _) = master.request(MbxType::FOE, &foe(5, 0x8000, &[]));
+    assert_eq!(mbx_type, MbxType(0));
+    let response = master.foe(&foe(3, 2, &image[PACKET..2 * PACKET]));
+    assert_eq!(response, foe_error(FoeError::Illegal));
+    master.state(0x01);
+    assert_eq!(*MARKED.lock().unwrap(), None);
+
+    master.state(0x03);
+    let response = write(&mut master, &image);
+    assert_eq!(response, foe(4, 3, &[]));
+    master.state(0x01);
+    assert_eq!(*MARKED.lock().unwrap(), Some(1000));
+}
+
+/// Image too large for the partition
+#[test]
+fn test_disk_full() {
+    let _lock = common::lock();
+    let mut master = master(0x03);
+    let response = write(&mut master, &image(FLASH_SIZE));
+    assert_eq!(response, foe_error(FoeError::DiskFull));
+}
@@ -0,0 +1,210 @@
+extern crate SOES_rs;
+mod common;
+
+use common::{foe, foe_error, minimal_od, Master};
+use core::ptr::addr_of_mut;
+use SOES_rs::foe::{FoeError, FoeFile, FoeMode, FoeWrite};
+use SOES_rs::mbx::MbxType;
+
+/// Data packet size with 512 byte mailboxes
+const PACKET: usize = 500;
+const FILE_SIZE: usize = 1200;
+
+minimal_od!();
+
+/// File in RAM
+struct RamFile {
+    name: &'static str,
+    password: u32,
+    boot_only: bool,
+    data: [u8; FILE_SIZE],
+    size: usize,
+    /// Busy answers before the next write is taken
+    busy: u16,
+    /// Last close, `Some(complete)`
+    closed: Option<bool>,
+}
+
+impl RamFile {
+    const fn new(name: &'static str, password: u32, boot_only: bool) -> Self {
+        Self {
+            name,
+            password,
+            boot_only,
+            data: [0; FILE_SIZE],
+            size: 0,
+            busy: 0,
+            closed: None,
+        }
+    }
+}
+
+impl FoeFile for RamFile {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn password(&self) -> u32 {
+        self.password
+    }
+
+    fn boot_only(&self) -> bool {
+        self.boot_only
+    }
+
+    fn open(&mut self, mode: FoeMode) -> Result<(), FoeError> {
+        if mode == FoeMode::Write {
+            self.size = 0;
+        }
+        self.closed = None;
+        Ok(())
+    }
+
+    fn read(&mut self, offset: u32, buf: &mut [u8]) -> Result<usize, FoeError> {
+        let data = &self.data[offset as usize..self.size];
+        let len = data.len().min(buf.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        Ok(len)
+    }
+
+    fn write(&mut self, offset: u32, data: &[u8]) -> Result<FoeWrite, FoeError> {
+        if self.busy > 0 {
+            self.busy -= 1;
+            return Ok(FoeWrite::Busy { done: 1, entire: 2 });
+        }
+        let offset = offset as usize;
+        if offset + data.len() > FILE_SIZE {
+            return Err(FoeError::DiskFull);
+        }
+        self.data[offset..offset + data.len()].copy_from_slice(data);
+        self.size = offset + data.len();
+        Ok(FoeWrite::Done)
+    }
+
+    fn close(&mut self, complete: bool) -> Result<(), FoeError> {
+        self.closed = Some(complete);
+        Ok(())
+    }
+}
+
+static mut CONFIG: RamFile = RamFile::new("config.bin", 0x1234, false);
+static mut FIRMWARE: RamFile = RamFile::new("firmware.bin", 0, true);
+
+fn config() -> &'static mut RamFile {
+    unsafe { &mut *addr_of_mut!(CONFIG) }
+}
+
+/// Slave in PREOP with the files registered
+fn master() -> Master {
+    Master::registered(|slave| {
+        slave
+            .add_foe_file(unsafe { &mut *addr_of_mut!(CONFIG) })
+            .unwrap();
+        slave
+            .add_foe_file(unsafe { &mut *addr_of_mut!(FIRMWARE) })
+            .unwrap();
+    })
+}
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|n| (n * 7) as u8).collect()
+}
+
+/// Write acknowledged packet by packet, busy answers repeated by the
+/// master, the short packet ends the file
+#[test]
+fn test_foe_write() {
+    let _lock = common::lock();
+    let mut master = master();
+    let file = pattern(700);
+
+    let response = master.foe(&foe(2, 0x1234, b"config.bin"));
+    assert_eq!(response, foe(4, 0, &[]));
+
+    config().busy = 1;
+    let response = master.foe(&foe(3, 1, &file[..PACKET]));
+    assert_eq!(response, foe(6, 0x0002_0001, &[]));
+    let response = master.foe(&foe(3, 1, &file[..PACKET]));
+    assert_eq!(response, foe(4, 1, &[]));
+    assert_eq!(config().closed, None);
+
+    let response = master.foe(&foe(3, 2, &file[PACKET..]));
+    assert_eq!(response, foe(4, 2, &[]));
+    assert_eq!(config().closed, Some(true));
+    assert_eq!(&config().data[..config().size], &file[..]);
+
+    // Names may be NUL terminated, a file of full packets ends with an
+    // empty one
+    let response = master.foe(&foe(2, 0x1234, b"config.bin\0"));
+    assert_eq!(response, foe(4, 0, &[]));
+    master.foe(&foe(3, 1, &file[..PACKET]));
+    let response = master.foe(&foe(3, 2, &[]));
+    assert_eq!(response, foe(4, 2, &[]));
+    assert_eq!(config().size, PACKET);
+    assert_eq!(config().closed, Some(true));
+}
+
+/// Read sent packet by packet, ended by the ack of the short packet
+#[test]
+fn test_foe_read() {
+    let _lock = common::lock();
+    let mut master = master();
+    let file = pattern(FILE_SIZE);
+    config().data.copy_from_slice(&file);
+    config().size = 900;
+
+    let response = master.foe(&foe(1, 0x1234, b"config.bin"));
+    assert_eq!(response, foe(3, 1, &file[..PACKET]));
+    // Master busy: the packet is sent again
+    let response = master.foe(&foe(6, 0, &[]));
+    assert_eq!(response, foe(3, 1, &file[..PACKET]));
+    let response = master.foe(&foe(4, 1, &[]));
+    assert_eq!(response, foe(3, 2, &file[PACKET..900]));
+    assert_eq!(config().closed, None);
+
+    let (mbx_type, _) = master.request(MbxType::FOE, &foe(4, 2, &[]));
+    assert_eq!(mbx_type, MbxType(0));
+    assert_eq!(config().closed, Some(true));
+}
+
+/// Error codes and text, transfers aborted on errors from either side
+#[test]
+fn test_foe_errors() {
+    let _lock = common::lock();
+    let mut master = master();
+
+    let response = master.foe(&foe(2, 0x1234, b"missing.bin"));
+    assert_eq!(response, foe_error(FoeError::NotFound));
+    let response = master.foe(&foe(1, 0x4321, b"config.bin"));
+    assert_eq!(response, foe_error(FoeError::NoRights));
+    let response = master.foe(&foe(2, 0, b"firmware.bin"));
+    assert_eq!(response, foe_error(FoeError::NotInBootstrap));
+    let response = master.foe(&foe(3, 1, &[1, 2, 3]));
+    assert_eq!(response, foe_error(FoeError::Illegal));
+    let response = master.foe(&foe(9, 0, &[]));
+    assert_eq!(response, foe_error(FoeError::NotDefined));
+
+    // Wrong packet number closes the file
+    master.foe(&foe(2, 0x1234, b"config.bin"));
+    let response = master.foe(&foe(3, 2, &[1, 2, 3]));
+    assert_eq!(response, foe_error(FoeError::PacketNumber));
+    assert_eq!(config().closed, Some(false));
+
+    // File errors are sent to the master
+    master.foe(&foe(2, 0x1234, b"config.bin"));
+    master.foe(&foe(3, 1, &[0; PACKET]));
+    master.foe(&foe(3, 2, &[0; PACKET]));
+    let response = master.foe(&foe(3, 3, &[0; PACKET]));
+    assert_eq!(response, foe_error(FoeError::DiskFull));
+    assert_eq!(config().closed, Some(false));
+
+    // Error from the master, no answer
+    master.foe(&foe(2, 0x1234, b"config.bin"));
+    let (mbx_type, _) = master.request(MbxType::FOE, &foe(5, 0x8000, &[]));
+    assert_eq!(mbx_type, MbxType(0));
+    assert_eq!(config().closed, Some(false));
+
+    // Too short for the FoE header
+    let (mbx_type, _) = master.request(MbxType::FOE, &[2, 0, 0]);
+    assert_eq!(mbx_type, MbxType::ERR);
+}
@@ -0,0 +1,384 @@
+extern crate SOES_rs;
+mod common;
+
+use embassy_futures::block_on;
+use embassy_time::Duration;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::spi::{ErrorType, Operation, SpiDevice};
+use std::collections::VecDeque;
+use SOES_rs::drivers::*;
+use SOES_rs::esc_driver::{AsyncEscDriver, EscDriver, EscError};
+
+/// LAN9252 behind its SPI interface: direct registers, CSR commands and
+/// the PRAM FIFOs over a 8 KiB ESC memory
+struct MockLan9252 {
+    esc: Vec<u8>,
+    csr_data: u32,
+    /// PRAM read/write address and length
+    rd_addr_len: u32,
+    wr_addr_len: u32,
+    /// Bytes in the read FIFO, write position from the DWORD of the address
+    rd_fifo: VecDeque<u8>,
+    wr_position: usize,
+    /// BUSY never clears / AVAIL never sets on the command registers
+    stuck_busy: bool,
+    stuck_unavailable: bool,
+    /// Byte test, ID/revision and ready bit seen by the bring-up
+    byte_test: u32,
+    chip_id: u32,
+    ready: bool,
+    /// Digital reset requests
+    resets: u32,
+}
+
+impl MockLan9252 {
+    fn new() -> Self {
+        Self {
+            esc: vec![0; 0x2000],
+            csr_data: 0,
+            rd_addr_len: 0,
+            wr_addr_len: 0,
+            rd_fifo: VecDeque::new(),
+            wr_position: 0,
+            stuck_busy: false,
+            stuck_unavailable: false,
+            byte_test: ESC_TEST_VALUE,
+            chip_id: 0x9252_0001,
+            ready: true,
+            resets: 0,
+        }
+    }
+
+    fn command_status(&self) -> u32 {
+        if self.stuck_busy {
+            ESC_PRAM_CMD_BUSY
+        } else if self.stuck_unavailable {
+            0
+        } else {
+            ESC_PRAM_CMD_AVAIL
+        }
+    }
+
+    fn read_register(&mut self, address: u16) -> u32 {
+        match address {
+            ESC_CSR_DATA_REG => self.csr_data,
+            ESC_CSR_CMD_REG if self.stuck_busy => ESC_CSR_CMD_BUSY,
+            ESC_PRAM_RD_CMD_REG | ESC_PRAM_WR_CMD_REG => self.command_status(),
+            LAN9252_BYTE_TEST => self.byte_test,
+            ESC_HW_CFG_REG if self.ready => ESC_READY,
+            ESC_ID_REV_REG => self.chip_id,
+            _ => 0,
+        }
+    }
+
+    fn write_register(&mut self, address: u16, value: u32) {
+        match address {
+            ESC_CSR_DATA_REG => self.csr_data = value,
+            ESC_CSR_CMD_REG => {
+                let (start, len) = ((value & 0xFFFF) as usize, (value >> 16 & 0xFF) as usize);
+                if value & ESC_CSR_CMD_READ == ESC_CSR_CMD_READ {
+                    let mut bytes = [0; 4];
+                    bytes[..len].copy_from_slice(&self.esc[start..start + len]);
+                    self.csr_data = u32::from_le_bytes(bytes);
+                } else {
+                    self.esc[start..start + len]
+                        .copy_from_slice(&self.csr_data.to_le_bytes()[..len]);
+                }
+            }
+            ESC_RESET_CTRL_REG if value == ESC_DIGITAL_RST => self.resets += 1,
+            ESC_PRAM_RD_ADDR_LEN_REG => self.rd_addr_len = value,
+            ESC_PRAM_WR_ADDR_LEN_REG => self.wr_addr_len = value,
+            ESC_PRAM_RD_CMD_REG if value == ESC_PRAM_CMD_BUSY => {
+                // Whole DWORDs from the one holding the first byte
+                let (start, end) = pram_range(self.rd_addr_len);
+                self.rd_fifo = self.esc[start & !3..end.next_multiple_of(4)]
+                    .iter()
+                    .copied()
+                    .collect();
+            }
+            ESC_PRAM_WR_CMD_REG if value == ESC_PRAM_CMD_BUSY => self.wr_position = 0,
+            ESC_PRAM_WR_FIFO_REG => self.write_fifo(&value.to_le_bytes()),
+            _ => {}
+        }
+    }
+
+    /// Bytes pushed in the write FIFO, only the ones in the range are stored
+    fn write_fifo(&mut self, data: &[u8]) {
+        let (start, end) = pram_range(self.wr_addr_len);
+        for byte in data {
+            let address = (start & !3) + self.wr_position;
+            if (start..end).contains(&address) {
+                self.esc[address] = *byte;
+            }
+            self.wr_position += 1;
+        }
+    }
+}
+
+/// Start and end of a PRAM access from its address/length register
+fn pram_range(addr_len: u32) -> (usize, usize) {
+    let start = (addr_len & 0xFFFF) as usize;
+    (start, start + (addr_len >> 16) as usize)
+}
+
+impl ErrorType for MockLan9252 {
+    type Error = core::convert::Infallible;
+}
+
+impl SpiDevice for MockLan9252 {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        let (command, data) = operations.split_first_mut().unwrap();
+        let Operation::Write(command) = command else {
+            panic!("transaction without a command");
+        };
+        let address = u16::from_be_bytes([command[1], command[2]]);
+        match command[0] {
+            ESC_CMD_SERIAL_WRITE if command.len() == 7 => {
+                let value = u32::from_le_bytes(command[3..].try_into().unwrap());
+                self.write_register(address, value);
+            }
+            ESC_CMD_SERIAL_WRITE => {
+                assert_eq!(address, ESC_PRAM_WR_FIFO_REG);
+                for operation in data {
+                    let Operation::Write(bytes) = operation else {
+                        panic!("read in a write transaction");
+                    };
+                    self.write_fifo(bytes);
+                }
+            }
+            ESC_CMD_FAST_READ => {
+                for operation in data {
+                    match operation {
+                        // Register, or one DWORD of the read FIFO
+                        Operation::Transfer(rx, _) if address != ESC_PRAM_RD_FIFO_REG => {
+                            rx.copy_from_slice(&self.read_register(address).to_le_bytes());
+                        }
+                        Operation::Transfer(bytes, _) | Operation::Read(bytes) => {
+                            assert_eq!(address, ESC_PRAM_RD_FIFO_REG);
+                            for byte in bytes.iter_mut() {
+                                *byte = self.rd_fifo.pop_front().expect("read FIFO empty");
+                            }
+                        }
+                        _ => panic!("write in a read transaction"),
+                    }
+                }
+            }
+            other => panic!("unexpected command 0x{other:02X}"),
+        }
+        Ok(())
+    }
+}
+
+impl embedded_hal_async::spi::SpiDevice for MockLan9252 {
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        SpiDevice::transaction(self, operations)
+    }
+}
+
+/// RST# pin recording its levels, failing when `broken`
+#[derive(Default)]
+struct MockPin {
+    levels: Vec<bool>,
+    broken: bool,
+}
+
+impl digital::ErrorType for MockPin {
+    type Error = digital::ErrorKind;
+}
+
+impl OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set_state(false.into())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set_state(true.into())
+    }
+
+    fn set_state(&mut self, state: digital::PinState) -> Result<(), Self::Error> {
+        if self.broken {
+            return Err(digital::ErrorKind::Other);
+        }
+        self.levels.push(state.into());
+        Ok(())
+    }
+}
+
+/// Delay adding up the time waited
+#[derive(Default)]
+struct MockDelay(u64);
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.0 += u64::from(ns);
+    }
+}
+
+fn blocking(spi: MockLan9252) -> Lan9252<MockLan9252> {
+    let mut esc = Lan9252::new(spi);
+    esc.set_timeouts(Lan9252Timeouts {
+        csr: 10,
+        pram: 10,
+        reset: 10,
+    });
+    esc
+}
+
+fn asynchronous(spi: MockLan9252) -> Lan9252Async<MockLan9252> {
+    let mut esc = Lan9252Async::new(spi);
+    esc.set_timeouts(Lan9252AsyncTimeouts {
+        csr: Duration::from_millis(2),
+        pram: Duration::from_millis(2),
+        reset: Duration::from_millis(2),
+    });
+    esc
+}
+
+/// BUSY never clearing or AVAIL never set: the accesses time out
+#[test]
+fn test_busy_timeout() {
+    let mut buf = [0; 8];
+    for (stuck_busy, stuck_unavailable) in [(true, false), (false, true)] {
+        let spi = MockLan9252 {
+            stuck_busy,
+            stuck_unavailable,
+            ..MockLan9252::new()
+        };
+        let mut esc = blocking(spi);
+        assert_eq!(esc.read(0x1100, &mut buf), Err(EscError::Timeout));
+        assert_eq!(esc.write(0x1100, &buf), Err(EscError::Timeout));
+        let mut esc = asynchronous(esc.release());
+        assert_eq!(block_on(esc.read(0x1100, &mut buf)), Err(EscError::Timeout));
+        assert_eq!(block_on(esc.write(0x1100, &buf)), Err(EscError::Timeout));
+    }
+
+    // CSR command never done
+    let spi = MockLan9252 {
+        stuck_busy: true,
+        ..MockLan9252::new()
+    };
+    let mut esc = blocking(spi);
+    assert_eq!(esc.read(0x0130, &mut buf[..2]), Err(EscError::Timeout));
+    let mut esc = asynchronous(esc.release());
+    assert_eq!(
+        block_on(esc.write(0x0120, &buf[..2])),
+        Err(EscError::Timeout)
+    );
+}
+
+/// PRAM accesses of lengths and addresses not multiple of 4: the data
+/// lands at its address and the read FIFO is drained
+#[test]
+fn test_pram_unaligned() {
+    let data: Vec<u8> = (1..=11).collect();
+    for (address, len) in [(0x1000, 7), (0x1001, 6), (0x1003, 11), (0x1002, 1)] {
+        let mut esc = blocking(MockLan9252::new());
+        esc.write(address, &data[..len]).unwrap();
+        let mut spi = esc.release();
+        let at = address as usize;
+        assert_eq!(spi.esc[at..at + len], data[..len]);
+        assert!(spi.esc[at + len..].iter().all(|byte| *byte == 0));
+
+        spi.esc[at + len] = 0xEE;
+        let mut esc = blocking(spi);
+        let mut buf = vec![0; len];
+        esc.read(address, &mut buf).unwrap();
+        assert_eq!(buf, data[..len]);
+        assert!(esc.release().rd_fifo.is_empty());
+
+        let mut esc = asynchronous(MockLan9252::new());
+        block_on(esc.write(address, &data[..len])).unwrap();
+        let spi = esc.release();
+        assert_eq!(spi.esc[at..at + len], data[..len]);
+
+        let mut esc = asynchronous(spi);
+        let mut buf = vec![0; len];
+        block_on(esc.read(address, &mut buf)).unwrap();
+        assert_eq!(buf, data[..len]);
+        assert!(esc.release().rd_fifo.is_empty());
+    }
+}
+
+/// Bring-up: reset, byte test, ready bit and chip identification
+#[test]
+fn test_bring_up() {
+    let mut esc = blocking(MockLan9252::new());
+    let chip = esc.bring_up().unwrap();
+    assert_eq!(
+        chip,
+        Lan9252ChipId {
+            id: 0x9252,
+            revision: 1
+        }
+    );
+    assert_eq!(esc.release().resets, 1);
+    let mut esc = asynchronous(MockLan9252::new());
+    assert_eq!(block_on(esc.bring_up()), Ok(chip));
+
+    let cases = [
+        // Byte order test failing: SPI not working
+        (
+            MockLan9252 {
+                byte_test: ESC_TEST_VALUE.swap_bytes(),
+                ..MockLan9252::new()
+            },
+            EscError::NotResponding,
+        ),
+        // Device never ready
+        (
+            MockLan9252 {
+                ready: false,
+                ..MockLan9252::new()
+            },
+            EscError::Timeout,
+        ),
+        // Another chip, or a LAN9252 of revision 0
+        (
+            MockLan9252 {
+                chip_id: 0x9253_0001,
+                ..MockLan9252::new()
+            },
+            EscError::NotResponding,
+        ),
+        (
+            MockLan9252 {
+                chip_id: 0x9252_0000,
+                ..MockLan9252::new()
+            },
+            EscError::NotResponding,
+        ),
+    ];
+    for (spi, err) in cases {
+        let mut esc = blocking(spi);
+        assert_eq!(esc.bring_up(), Err(err));
+        let mut esc = asynchronous(esc.release());
+        assert_eq!(block_on(esc.bring_up()), Err(err));
+    }
+}
+
+/// RST# held low for the minimum pulse width, pin failures reported
+#[test]
+fn test_hard_reset() {
+    let mut esc = blocking(MockLan9252::new());
+    let (mut rst, mut delay) = (MockPin::default(), MockDelay::default());
+    esc.hard_reset(&mut rst, &mut delay).unwrap();
+    assert_eq!(rst.levels, [false, true]);
+    assert!(delay.0 >= u64::from(LAN9252_RST_PULSE_US) * 1000);
+
+    let mut esc = asynchronous(esc.release());
+    let mut rst = MockPin::default();
+    block_on(esc.hard_reset(&mut rst)).unwrap();
+    assert_eq!(rst.levels, [false, true]);
+
+    let mut rst = MockPin {
+        broken: true,
+        ..MockPin::default()
+    };
+    assert_eq!(block_on(esc.hard_reset(&mut rst)), Err(EscError::Bus));
+    let mut esc = blocking(esc.release());
+    assert_eq!(esc.hard_reset(&mut rst, &mut delay), Err(EscError::Bus));
+}
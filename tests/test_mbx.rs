@@ -0,0 +1,173 @@
+extern crate SOES_rs;
+mod common;
+
+use common::{
+    cfg, minimal_od, sdo, sdo_response, Master, MockEsc, ALCONTROL, ALEVENT, EVENT_SM0, EVENT_SM1,
+    EVENT_SM_CHANGE, SM0, SM1,
+};
+use core::ptr::addr_of_mut;
+use SOES_rs::mbx::{MbxError, MbxHeader, MbxOut, MbxProtocol, MbxType};
+use SOES_rs::soes::EcatSlave;
+
+minimal_od!();
+
+/// VoE handler answering the request reversed
+struct Echo;
+
+impl MbxProtocol for Echo {
+    fn mbx_type(&self) -> MbxType {
+        MbxType::VOE
+    }
+
+    fn receive(
+        &mut self,
+        _header: &MbxHeader,
+        data: &[u8],
+        out: &mut MbxOut,
+    ) -> Result<(), MbxError> {
+        if data.len() < 2 {
+            return Err(MbxError::SIZE_TOO_SHORT);
+        }
+        out.send(MbxType::VOE, |buf| {
+            for (dst, src) in buf.iter_mut().zip(data.iter().rev()) {
+                *dst = *src;
+            }
+            data.len()
+        })
+    }
+}
+
+static mut ECHO: Echo = Echo;
+
+fn request(mbx_type: MbxType, counter: u8, length: u16) -> MbxHeader {
+    MbxHeader {
+        length,
+        address: 0,
+        channel: 0,
+        priority: 0,
+        mbx_type,
+        counter,
+    }
+}
+
+#[test]
+fn test_mbx_header() {
+    let bytes = [0x0A, 0x00, 0x34, 0x12, 0x45, 0x33];
+    let header = MbxHeader::from_bytes(&bytes);
+    assert_eq!(header.length, 10);
+    assert_eq!(header.address, 0x1234);
+    assert_eq!(header.channel, 5);
+    assert_eq!(header.priority, 1);
+    assert_eq!(header.mbx_type, MbxType::COE);
+    assert_eq!(header.counter, 3);
+    assert_eq!(header.to_bytes(), bytes);
+}
+
+/// Requests dispatched to a registered protocol, mailbox errors, repeat
+/// requests and mailbox counters
+#[test]
+fn test_mbx_dispatch() {
+    let _lock = common::lock();
+    let mut slave = EcatSlave::new(MockEsc::new(), cfg());
+    slave.init().unwrap();
+    slave
+        .add_mbx_protocol(unsafe { &mut *addr_of_mut!(ECHO) })
+        .unwrap();
+
+    // INIT -> PREOP starts the mailboxes
+    slave.driver().set_u16(ALCONTROL, 0x02);
+    slave.driver().set_u16(ALEVENT, 0x01);
+    slave.poll();
+    slave.driver().clear_events();
+
+    // Request handled by the protocol, response posted in SM1
+    slave
+        .driver()
+        .master_write(request(MbxType::VOE, 1, 3), &[1, 2, 3]);
+    slave.poll();
+    slave.driver().clear_events();
+    let (header, data) = slave.driver().response();
+    assert_eq!(header.mbx_type, MbxType::VOE);
+    assert_eq!(header.counter, 1);
+    assert_eq!(data, &[3, 2, 1]);
+
+    // Master reads the response
+    slave.driver().set_u16(ALEVENT, EVENT_SM1);
+    slave.poll();
+    slave.driver().clear_events();
+
+    // Repeat request: the last response is written again, toggle acked
+    slave.driver().clear_response();
+    slave.driver().regs[SM1 + 6] |= 0x02;
+    slave.driver().set_u16(ALEVENT, EVENT_SM_CHANGE);
+    slave.poll();
+    slave.driver().clear_events();
+    let (header, data) = slave.driver().response();
+    assert_eq!(header.counter, 1);
+    assert_eq!(data, &[3, 2, 1]);
+    assert_eq!(slave.driver().regs[SM1 + 7] & 0x02, 0x02);
+
+    // Same mailbox counter again: dropped
+    slave.driver().clear_response();
+    slave
+        .driver()
+        .master_write(request(MbxType::VOE, 1, 3), &[4, 5, 6]);
+    slave.poll();
+    slave.driver().clear_events();
+    assert_eq!(slave.driver().response().0.length, 0);
+
+    // No handler for SoE
+    slave
+        .driver()
+        .master_write(request(MbxType::SOE, 2, 2), &[0, 0]);
+    slave.poll();
+    slave.driver().clear_events();
+    let (header, data) = slave.driver().response();
+    assert_eq!(header.mbx_type, MbxType::ERR);
+    assert_eq!(header.counter, 2);
+    assert_eq!(data, &[0x01, 0x00, 0x02, 0x00]);
+
+    // The next request waits until the error is read
+    slave
+        .driver()
+        .master_write(request(MbxType::VOE, 3, 1), &[7]);
+    slave.poll();
+    assert_eq!(slave.driver().response().0.counter, 2);
+
+    // Handler error answered with a mailbox error
+    slave.driver().set_u16(ALEVENT, EVENT_SM1);
+    slave.poll();
+    slave.driver().set_u16(ALEVENT, EVENT_SM0);
+    slave.poll();
+    slave.driver().clear_events();
+    let (header, data) = slave.driver().response();
+    assert_eq!(header.mbx_type, MbxType::ERR);
+    assert_eq!(data, &[0x01, 0x00, 0x06, 0x00]);
+}
+
+/// Mailbox events read the SM status only: the activate registers (0x806 +
+/// n * 8) clear the SM change event, left to the AL state machine
+#[test]
+fn test_mbx_sm_activate_reads() {
+    let _lock = common::lock();
+    let mut master = Master::new();
+    master.slave.driver().reads.clear();
+
+    // Request, response read by the master
+    let response = master.coe(&sdo(0x40, 0x1000, 0, [0; 4]));
+    assert_eq!(
+        response,
+        sdo_response(0x43, 0x1000, 0, 5001u32.to_le_bytes())
+    );
+    let esc = master.slave.driver();
+    assert!(esc.reads_of(SM0 + 5) > 0);
+    assert_eq!(esc.reads_of(SM0 + 6), 0);
+    assert_eq!(esc.reads_of(SM1 + 6), 0);
+
+    // SM change with a mailbox event: read once, by the AL state machine
+    esc.set_u16(ALEVENT, EVENT_SM1 | EVENT_SM_CHANGE);
+    master.slave.poll();
+    let esc = master.slave.driver();
+    assert_eq!(esc.reads_of(SM0 + 6), 1);
+    assert_eq!(esc.reads_of(SM1 + 6), 1);
+}
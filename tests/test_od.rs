@@ -0,0 +1,66 @@
+extern crate SOES_rs;
+use core::ptr::addr_of_mut;
+use SOES_rs::bindings::{ATYPE_TXPDO, DTYPE_UNSIGNED32, OTYPE_RECORD};
+use SOES_rs::object_dictionary;
+use SOES_rs::od::{Access, DataType, Entry, Object};
+
+struct Objects {
+    serial: u32,
+    counter: u32,
+}
+
+static mut OBJ: Objects = Objects {
+    serial: 0,
+    counter: 0,
+};
+
+static SDO1000: Entry =
+    Entry::new(0x0, c"Device Type", DataType::Unsigned32, Access::RO).value(5001);
+static SDO1008: Entry = Entry::string(0x0, c"Device Name", c"test");
+static SDO1018: [Entry; 3] = [
+    Entry::max_subindex(4),
+    Entry::new(0x01, c"Vendor ID", DataType::Unsigned32, Access::RO),
+    Entry::new(0x04, c"Serial Number", DataType::Unsigned32, Access::RO)
+        .bind(unsafe { addr_of_mut!(OBJ.serial) }),
+];
+static SDO6000: Entry = Entry::new(0x0, c"Counter", DataType::Unsigned32, Access::RO)
+    .txpdo()
+    .bind(unsafe { addr_of_mut!(OBJ.counter) });
+
+object_dictionary![
+    Object::var(0x1000, &SDO1000),
+    Object::var(0x1008, &SDO1008),
+    Object::record(0x1018, c"Identity Object", &SDO1018),
+    Object::var(0x6000, &SDO6000),
+];
+
+/// The Rust declaration produces the tables the CoE layer walks
+#[test]
+fn test_object_dictionary_tables() {
+    let objects = SDOobjects.objects();
+    assert_eq!(objects.len(), 4);
+
+    let device_type = SDOobjects.find(0x1000).unwrap();
+    assert_eq!(device_type.entries()[0].datatype(), DTYPE_UNSIGNED32 as u16);
+    assert_eq!(device_type.entries()[0].default_value(), 5001);
+
+    let name = SDOobjects.find(0x1008).unwrap();
+    assert_eq!(name.entries()[0].bit_length(), 4 * 8);
+
+    // Sparse record: entries stop at the max subindex
+    let identity = SDOobjects.find(0x1018).unwrap();
+    assert_eq!(identity.object_type(), OTYPE_RECORD as u16);
+    assert_eq!(identity.max_subindex(), 4);
+    let subindexes: Vec<u8> = identity.entries().iter().map(Entry::subindex).collect();
+    assert_eq!(subindexes, [0, 1, 4]);
+    assert_eq!(identity.entries()[2].data(), unsafe {
+        addr_of_mut!(OBJ.serial) as *mut core::ffi::c_void
+    });
+
+    let counter = SDOobjects.find(0x6000).unwrap();
+    assert!(counter.entries()[0]
+        .access()
+        .contains(Access(ATYPE_TXPDO as u16)));
+
+    assert!(SDOobjects.find(0x2000).is_none());
+}
@@ -0,0 +1,316 @@
+#![cfg(feature = "std")]
+
+extern crate SOES_rs;
+use core::ptr::addr_of_mut;
+use SOES_rs::object_dictionary;
+use SOES_rs::od::{Access, DataType, Entry, EtherCatObjects, Object};
+use SOES_rs::sii::{self, Mismatch, ParseError, SiiConfig, SiiError};
+
+// The stack logs through defmt, discard it on the host
+#[defmt::global_logger]
+struct NullLogger;
+
+unsafe impl defmt::Logger for NullLogger {
+    fn acquire() {}
+    unsafe fn flush() {}
+    unsafe fn release() {}
+    unsafe fn write(_bytes: &[u8]) {}
+}
+
+defmt::timestamp!("{=u8}", 0);
+
+#[derive(EtherCatObjects)]
+#[ethercat(storage = OBJ)]
+struct ProcessData {
+    serial: u32,
+    #[txpdo(index = 0x6000, name = "Key1")]
+    key1: bool,
+    #[txpdo(index = 0x6001, name = "Counter")]
+    counter: u32,
+    #[rxpdo(index = 0x7000, name = "LedIn")]
+    led_in: u16,
+}
+
+static mut OBJ: ProcessData = ProcessData {
+    serial: 0,
+    key1: false,
+    counter: 0,
+    led_in: 0,
+};
+
+static SDO1000: Entry =
+    Entry::new(0x0, c"Device Type", DataType::Unsigned32, Access::RO).value(5001);
+static SDO1008: Entry = Entry::string(0x0, c"Device Name", c"SII test");
+static SDO1018: [Entry; 5] = [
+    Entry::max_subindex(4),
+    Entry::new(0x01, c"Vendor ID", DataType::Unsigned32, Access::RO).value(0x1337),
+    Entry::new(0x02, c"Product Code", DataType::Unsigned32, Access::RO).value(0xAB123),
+    Entry::new(0x03, c"Revision Number", DataType::Unsigned32, Access::RO).value(2),
+    Entry::new(0x04, c"Serial Number", DataType::Unsigned32, Access::RO)
+        .value(42)
+        .bind(unsafe { addr_of_mut!(OBJ.serial) }),
+];
+
+object_dictionary![
+    Object::var(0x1000, &SDO1000),
+    Object::var(0x1008, &SDO1008),
+    Object::record(0x1018, c"Identity Object", &SDO1018);
+    ProcessData
+];
+
+fn word(image: &[u8], word: usize) -> u16 {
+    u16::from_le_bytes([image[word * 2], image[word * 2 + 1]])
+}
+
+fn dword(image: &[u8], word: usize) -> u32 {
+    u32::from_le_bytes(image[word * 2..word * 2 + 4].try_into().unwrap())
+}
+
+/// Categories by type, from word 0x40 to the end marker
+fn categories(image: &[u8]) -> Vec<(u16, Vec<u8>)> {
+    let mut categories = Vec::new();
+    let mut position = 0x80;
+    loop {
+        let category = word(image, position / 2);
+        if category == 0xFFFF {
+            return categories;
+        }
+        let len = word(image, position / 2 + 1) as usize * 2;
+        categories.push((category, image[position + 4..position + 4 + len].to_vec()));
+        position += 4 + len;
+    }
+}
+
+fn config() -> SiiConfig {
+    SiiConfig {
+        group: "DigIn".into(),
+        device_type: "IODemoApp".into(),
+        ..SiiConfig::default()
+    }
+}
+
+/// Config area checksum, identity from 0x1018 and the mailboxes
+#[test]
+fn test_sii_header() {
+    let image = sii::image(&config()).unwrap();
+    assert_eq!(image.len(), 2048);
+    assert_eq!(
+        &image[..14],
+        &[0x80, 0x06, 0x00, 0x44, 0x64, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+    );
+    // Same checksum as the SOES ESI tool
+    assert_eq!(word(&image, 7), 0x00F3);
+    assert_eq!(sii::crc8(&image[..14]), 0xF3);
+
+    assert_eq!(dword(&image, 0x08), 0x1337);
+    assert_eq!(dword(&image, 0x0A), 0xAB123);
+    assert_eq!(dword(&image, 0x0C), 2);
+    assert_eq!(dword(&image, 0x0E), 42);
+    let mailboxes: Vec<u16> = (0x14..0x1D).map(|n| word(&image, n)).collect();
+    assert_eq!(
+        mailboxes,
+        [
+            0x1000,
+            0x200,
+            0x1200,
+            0x200,
+            0x1000,
+            0x200,
+            0x1200,
+            0x200,
+            sii::MBX_COE
+        ]
+    );
+    assert_eq!(word(&image, 0x3E), 15);
+    assert_eq!(word(&image, 0x3F), 1);
+    assert!(image[0x200..].iter().all(|byte| *byte == 0xFF));
+}
+
+/// Strings, general, FMMU, sync managers and PDOs from the dictionary
+#[test]
+fn test_sii_categories() {
+    let image = sii::image(&config()).unwrap();
+    let categories = categories(&image);
+    let types: Vec<u16> = categories.iter().map(|(category, _)| *category).collect();
+    assert_eq!(types, [10, 30, 40, 41, 50, 51]);
+
+    let mut strings = vec![];
+    let data = &categories[0].1;
+    let mut position = 1;
+    for _ in 0..data[0] {
+        let len = data[position] as usize;
+        strings.push(std::str::from_utf8(&data[position + 1..position + 1 + len]).unwrap());
+        position += 1 + len;
+    }
+    assert_eq!(
+        strings,
+        [
+            "DigIn",
+            "IODemoApp",
+            "SII test",
+            "Inputs",
+            "Key1",
+            "Counter",
+            "Outputs",
+            "LedIn"
+        ]
+    );
+
+    // Group, no image, order, name, CoE details, physical ports
+    let general = &categories[1].1;
+    assert_eq!(general.len(), 32);
+    assert_eq!(&general[..6], &[1, 0, 2, 3, 0, 0x13]);
+    assert_eq!(&general[16..18], &[0x11, 0x00]);
+
+    assert_eq!(categories[2].1, [1, 2, 3, 0]);
+
+    let syncm = &categories[3].1;
+    assert_eq!(&syncm[..8], &[0x00, 0x10, 0x00, 0x02, 0x26, 0, 1, 1]);
+    assert_eq!(&syncm[8..16], &[0x00, 0x12, 0x00, 0x02, 0x22, 0, 1, 2]);
+    assert_eq!(&syncm[16..24], &[0x00, 0x16, 0x02, 0x00, 0x24, 0, 1, 3]);
+    assert_eq!(&syncm[24..32], &[0x00, 0x1A, 0x05, 0x00, 0x20, 0, 1, 4]);
+
+    // 0x1A00 on SM3: Key1, padding to a byte, Counter
+    let txpdo = &categories[4].1;
+    assert_eq!(&txpdo[..8], &[0x00, 0x1A, 3, 3, 0, 4, 0, 0]);
+    assert_eq!(&txpdo[8..16], &[0x00, 0x60, 0, 5, 0x01, 1, 0, 0]);
+    assert_eq!(&txpdo[16..24], &[0, 0, 0, 0, 0, 7, 0, 0]);
+    assert_eq!(&txpdo[24..32], &[0x01, 0x60, 0, 6, 0x07, 32, 0, 0]);
+
+    let rxpdo = &categories[5].1;
+    assert_eq!(&rxpdo[..8], &[0x00, 0x16, 1, 2, 0, 7, 0, 0]);
+    assert_eq!(&rxpdo[8..16], &[0x00, 0x70, 0, 8, 0x06, 16, 0, 0]);
+}
+
+#[test]
+fn test_sii_errors() {
+    let small = SiiConfig {
+        eeprom_size: 128,
+        ..config()
+    };
+    assert_eq!(sii::image(&small), Err(SiiError::Capacity));
+
+    let long = SiiConfig {
+        image: "x".repeat(256),
+        ..config()
+    };
+    assert_eq!(sii::image(&long), Err(SiiError::Strings));
+}
+
+/// 32 byte data records, checksums to zero, end of file record
+#[test]
+fn test_sii_intel_hex() {
+    let image = sii::image(&config()).unwrap();
+    let hex = sii::intel_hex(&image);
+    let lines: Vec<&str> = hex.lines().collect();
+    assert_eq!(lines.len(), 2048 / 32 + 1);
+    assert_eq!(lines[lines.len() - 1], ":00000001FF");
+    assert!(lines[1].starts_with(":20002000"));
+
+    let mut data = vec![];
+    for line in &lines[..lines.len() - 1] {
+        let bytes: Vec<u8> = (1..line.len())
+            .step_by(2)
+            .map(|n| u8::from_str_radix(&line[n..n + 2], 16).unwrap())
+            .collect();
+        assert_eq!(
+            bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)),
+            0
+        );
+        data.extend_from_slice(&bytes[4..bytes.len() - 1]);
+    }
+    assert_eq!(data, image);
+}
+
+/// A generated image decodes back to the dictionary and matches it
+#[test]
+fn test_sii_parse() {
+    let data = sii::image(&config()).unwrap();
+    let image = sii::parse(&data).unwrap();
+    assert_eq!(image.checksum, 0xF3);
+    assert_eq!(image.computed_checksum, 0xF3);
+    assert_eq!(
+        (
+            image.vendor_id,
+            image.product_code,
+            image.revision,
+            image.serial
+        ),
+        (0x1337, 0xAB123, 2, 42)
+    );
+    assert_eq!(image.mailbox, [0x1000, 0x200, 0x1200, 0x200]);
+    assert_eq!(image.eeprom_size, 2048);
+
+    let general = image.general.clone().unwrap();
+    assert_eq!(image.string(general.name), Some("SII test"));
+    assert_eq!(image.string(general.image), None);
+    assert_eq!(general.coe_details, 0x13);
+    assert_eq!(image.fmmu, [1, 2, 3, 0]);
+    assert_eq!(image.sync_managers.len(), 4);
+    assert_eq!(image.sync_managers[3].length, 5);
+    assert!(image.sync_managers.iter().all(|sm| sm.enable == 1));
+
+    let txpdo = &image.txpdos[0];
+    assert_eq!((txpdo.index, txpdo.sm), (0x1A00, 3));
+    assert_eq!(image.string(txpdo.name), Some("Inputs"));
+    let counter = &txpdo.entries[2];
+    assert_eq!((counter.index, counter.bit_length), (0x6001, 32));
+    assert_eq!(image.string(counter.name), Some("Counter"));
+    assert_eq!(image.rxpdos[0].entries[0].index, 0x7000);
+
+    assert_eq!(image.check(), []);
+}
+
+/// Each difference with the firmware is reported
+#[test]
+fn test_sii_check() {
+    let mut data = sii::image(&config()).unwrap();
+    data[0] = 0x05; // PDI control changed without the checksum
+    data[0x14] = 0x24; // product code
+    data[0x30] = 0x00; // standard receive mailbox offset
+    data[0x31] = 0x11;
+
+    // SM2 start and a process data length not matching the PDOs
+    let syncm = data
+        .windows(4)
+        .position(|header| header == [0x29, 0x00, 0x10, 0x00])
+        .unwrap()
+        + 4;
+    data[syncm + 17] = 0x17;
+    data[syncm + 26] = 0x09;
+
+    let mismatch = |field: &str, image, firmware| Mismatch {
+        field: field.into(),
+        image,
+        firmware,
+    };
+    let computed = sii::crc8(&data[..14]) as u32;
+    assert_eq!(
+        sii::parse(&data).unwrap().check(),
+        [
+            mismatch("config checksum", 0xF3, computed),
+            mismatch("product code", 0xAB124, 0xAB123),
+            mismatch("mailbox receive offset", 0x1100, 0x1000),
+            mismatch("SM2 start", 0x1700, 0x1600),
+            mismatch("SM3 length", 9, 5),
+        ]
+    );
+    assert_eq!(
+        mismatch("SM3 length", 9, 5).to_string(),
+        "SM3 length: image 0x9, firmware 0x5"
+    );
+
+    // Process data lengths of 0 are left to the master
+    data[syncm + 26] = 0;
+    assert_eq!(sii::parse(&data).unwrap().check().len(), 4);
+}
+
+#[test]
+fn test_sii_parse_errors() {
+    let data = sii::image(&config()).unwrap();
+    assert_eq!(sii::parse(&data[..0x40]), Err(ParseError::TooShort));
+    // Strings category cut, then no end marker
+    assert_eq!(sii::parse(&data[..0x90]), Err(ParseError::Truncated(10)));
+    let end = data.len() - data.iter().rev().take_while(|byte| **byte == 0xFF).count();
+    assert_eq!(sii::parse(&data[..end]), Err(ParseError::Truncated(51)));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "std")]
+
+extern crate SOES_rs;
+mod common;
+
+use SOES_rs::object_dictionary;
+use SOES_rs::od::{Access, DataType, Entry, Object};
+use SOES_rs::{esi, sii};
+
+static SDO1000: Entry =
+    Entry::new(0x0, c"Device Type", DataType::Unsigned32, Access::RO).value(5001);
+static SDO1008: Entry = Entry::string(0x0, c"Device Name", c"No PDO");
+static SDO1018: [Entry; 3] = [
+    Entry::max_subindex(2),
+    Entry::new(0x01, c"Vendor ID", DataType::Unsigned32, Access::RO).value(0x1337),
+    Entry::new(0x02, c"Product Code", DataType::Unsigned32, Access::RO).value(0xAB124),
+];
+
+object_dictionary![
+    Object::var(0x1000, &SDO1000),
+    Object::var(0x1008, &SDO1008),
+    Object::record(0x1018, c"Identity Object", &SDO1018),
+];
+
+/// Without PDO the process data sync managers are disabled
+#[test]
+fn test_sii_no_pdo() {
+    let config = sii::SiiConfig::default();
+    let image = sii::parse(&sii::image(&config).unwrap()).unwrap();
+    let enable: Vec<_> = image.sync_managers.iter().map(|sm| sm.enable).collect();
+    assert_eq!(enable, [1, 1, 0, 0]);
+    assert_eq!(image.sync_managers[2].length, 0);
+    assert_eq!(image.check(), []);
+
+    let xml = esi::xml(&config).unwrap();
+    assert!(xml.contains("ControlByte=\"#x26\" Enable=\"1\">MBoxOut</Sm>"));
+    assert!(xml.contains("ControlByte=\"#x24\" Enable=\"0\">Outputs</Sm>"));
+    assert!(xml.contains("ControlByte=\"#x20\" Enable=\"0\">Inputs</Sm>"));
+}
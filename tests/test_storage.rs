@@ -0,0 +1,276 @@
+extern crate SOES_rs;
+mod common;
+
+use common::{
+    cfg, crc32, flash, minimal_od, sdo, sdo_abort, sdo_response, Master, MockEsc, RamFlash,
+};
+use core::ptr::{addr_of, addr_of_mut};
+use std::sync::Mutex;
+use SOES_rs::coe::{ObjectHandler, SdoAbort, SdoAccess, SdoValue};
+use SOES_rs::od::{Access, DataType, Entry, Object};
+use SOES_rs::soes::EcatSlave;
+use SOES_rs::storage::{self, FlashStorage, ParameterStorage, StorageError, LOAD, SAVE};
+
+struct Parameters {
+    speed: u16,
+    counter: u32,
+    label: [u8; 8],
+    table: [u8; 256],
+}
+
+static mut PARAMS: Parameters = Parameters {
+    speed: 0,
+    counter: 0,
+    label: [0; 8],
+    table: [0; 256],
+};
+
+static SDO2000: [Entry; 5] = [
+    Entry::max_subindex(4),
+    Entry::new(1, c"Speed", DataType::Unsigned16, Access::RW)
+        .backup()
+        .value(100)
+        .bind(unsafe { addr_of_mut!(PARAMS.speed) }),
+    Entry::new(2, c"Counter", DataType::Unsigned32, Access::RW)
+        .value(7)
+        .bind(unsafe { addr_of_mut!(PARAMS.counter) }),
+    Entry::new(3, c"Label", DataType::OctetString, Access::RW)
+        .bitlength(64)
+        .backup()
+        .bind(unsafe { addr_of_mut!(PARAMS.label) }),
+    Entry::new(4, c"Table", DataType::OctetString, Access::RW)
+        .bitlength(2048)
+        .backup()
+        .bind(unsafe { addr_of_mut!(PARAMS.table) }),
+];
+
+minimal_od!(
+    storage::STORE_PARAMETERS,
+    storage::RESTORE_DEFAULTS,
+    Object::record(0x2000, c"Parameters", &SDO2000),
+);
+
+/// Erased flash, parameters stored in its first `size` bytes
+fn storage(size: u32) {
+    flash().fill(0xFF);
+    let storage = Box::leak(Box::new(FlashStorage::new(RamFlash, 0, size)));
+    let mut slave = EcatSlave::new(MockEsc::new(), cfg());
+    slave.set_parameter_storage(storage);
+}
+
+// Storage commands seen by the object handler, refused while locked
+static COMMANDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static LOCKED: Mutex<bool> = Mutex::new(false);
+
+struct Interlock;
+
+static mut INTERLOCK: Interlock = Interlock;
+
+impl ObjectHandler for Interlock {
+    fn download_pre(&mut self, access: &SdoAccess, value: SdoValue) -> Result<(), SdoAbort> {
+        if access.index == 0x1010 {
+            COMMANDS
+                .lock()
+                .unwrap()
+                .push(format!("download_pre {value:?}"));
+            if *LOCKED.lock().unwrap() {
+                return Err(SdoAbort::DataStoreLocal);
+            }
+        }
+        Ok(())
+    }
+
+    fn download_post(&mut self, access: &SdoAccess) -> Result<(), SdoAbort> {
+        if access.index == 0x1010 {
+            COMMANDS.lock().unwrap().push("download_post".into());
+        }
+        Ok(())
+    }
+}
+
+fn speed() -> u16 {
+    unsafe { PARAMS.speed }
+}
+
+fn label() -> [u8; 8] {
+    unsafe { *addr_of!(PARAMS.label) }
+}
+
+/// Speed 555 and label "axis-one" written, counter 9 (not a backup entry)
+fn write_parameters(master: &mut Master) {
+    master.coe(&sdo(0x2B, 0x2000, 1, [0x2B, 0x02, 0, 0]));
+    master.coe(&sdo(0x23, 0x2000, 2, [9, 0, 0, 0]));
+    let mut request = sdo(0x21, 0x2000, 3, [8, 0, 0, 0]);
+    request.extend_from_slice(b"axis-one");
+    master.coe(&request);
+}
+
+fn save(master: &mut Master) {
+    let response = master.coe(&sdo(0x23, 0x1010, 1, SAVE.to_le_bytes()));
+    assert_eq!(response, sdo_response(0x60, 0x1010, 1, [0; 4]));
+}
+
+/// Backup entries saved with the "save" signature on 0x1010 are loaded
+/// over the defaults at start
+#[test]
+fn test_save_load() {
+    let _lock = common::lock();
+    storage(512);
+
+    // Nothing stored: defaults
+    let mut master = Master::new();
+    assert_eq!(speed(), 100);
+    assert_eq!(unsafe { PARAMS.counter }, 7);
+
+    write_parameters(&mut master);
+    save(&mut master);
+    let response = master.coe(&sdo(0x40, 0x1010, 1, [0; 4]));
+    assert_eq!(response, sdo_response(0x43, 0x1010, 1, [1, 0, 0, 0]));
+
+    unsafe { PARAMS.speed = 1 };
+    Master::new();
+    assert_eq!(speed(), 555);
+    assert_eq!(&label(), b"axis-one");
+    assert_eq!(unsafe { PARAMS.counter }, 7);
+}
+
+/// Commands without their signature are refused, nothing is written
+#[test]
+fn test_bad_signature() {
+    let _lock = common::lock();
+    storage(512);
+    let mut master = Master::new();
+    write_parameters(&mut master);
+
+    let response = master.coe(&sdo(0x23, 0x1010, 1, *b"evas"));
+    assert_eq!(response, sdo_abort(0x1010, 1, SdoAbort::DataStore));
+    let response = master.coe(&sdo(0x23, 0x1011, 1, SAVE.to_le_bytes()));
+    assert_eq!(response, sdo_abort(0x1011, 1, SdoAbort::DataStore));
+    assert!(flash().iter().all(|byte| *byte == 0xFF));
+
+    Master::new();
+    assert_eq!(speed(), 100);
+}
+
+/// Stored data failing its CRC is ignored
+#[test]
+fn test_crc_corruption() {
+    let _lock = common::lock();
+    storage(512);
+    let mut master = Master::new();
+    write_parameters(&mut master);
+    save(&mut master);
+
+    flash()[20] ^= 0x01;
+    Master::new();
+    assert_eq!(speed(), 100);
+    flash()[20] ^= 0x01;
+    Master::new();
+    assert_eq!(speed(), 555);
+}
+
+/// "load" on 0x1011 erases the saved parameters: defaults at the next
+/// start, the running values are kept
+#[test]
+fn test_restore_defaults() {
+    let _lock = common::lock();
+    storage(512);
+    let mut master = Master::new();
+    write_parameters(&mut master);
+    save(&mut master);
+
+    let mut master = Master::new();
+    let response = master.coe(&sdo(0x23, 0x1011, 1, LOAD.to_le_bytes()));
+    assert_eq!(response, sdo_response(0x60, 0x1011, 1, [0; 4]));
+    assert_eq!(speed(), 555);
+    Master::new();
+    assert_eq!(speed(), 100);
+}
+
+/// Parameters larger than the storage area are not saved
+#[test]
+fn test_capacity_overflow() {
+    let _lock = common::lock();
+    // Header and records take 296 bytes
+    storage(256);
+    let mut storage = FlashStorage::new(RamFlash, 0, 256);
+    assert_eq!(storage.save(), Err(StorageError::Capacity));
+
+    let mut master = Master::new();
+    write_parameters(&mut master);
+    let response = master.coe(&sdo(0x23, 0x1010, 1, SAVE.to_le_bytes()));
+    assert_eq!(response, sdo_abort(0x1010, 1, SdoAbort::DataStore));
+    assert!(flash().iter().all(|byte| *byte == 0xFF));
+}
+
+/// A record no longer matching the size of its entry is skipped, the
+/// following ones are loaded
+#[test]
+fn test_record_size_changed() {
+    let _lock = common::lock();
+    storage(512);
+
+    // Speed saved as a 32-bit value by an older firmware, then the label
+    let mut data = vec![0x00, 0x20, 1, 0, 4, 0];
+    data.extend_from_slice(&555u32.to_le_bytes());
+    data.extend_from_slice(&[0x00, 0x20, 3, 0, 8, 0]);
+    data.extend_from_slice(b"axis-two");
+    let mut image = b"SOES".to_vec();
+    image.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    image.extend_from_slice(&crc32(&data).to_le_bytes());
+    image.extend_from_slice(&data);
+    flash()[..image.len()].copy_from_slice(&image);
+
+    Master::new();
+    assert_eq!(speed(), 100);
+    assert_eq!(&label(), b"axis-two");
+}
+
+/// The save command as a normal download split over two segments
+#[test]
+fn test_segmented_command() {
+    let _lock = common::lock();
+    storage(512);
+    let mut master = Master::new();
+    write_parameters(&mut master);
+
+    let mut request = sdo(0x21, 0x1010, 1, [4, 0, 0, 0]);
+    request.extend_from_slice(b"sa");
+    let response = master.coe(&request);
+    assert_eq!(response, sdo_response(0x60, 0x1010, 1, [0; 4]));
+    let response = master.coe(&[0x00, 0x20, 0x0B, b'v', b'e', 0, 0, 0, 0, 0]);
+    assert_eq!(response, [0x00, 0x30, 0x20, 0, 0, 0, 0, 0, 0, 0]);
+
+    unsafe { PARAMS.speed = 1 };
+    Master::new();
+    assert_eq!(speed(), 555);
+}
+
+/// Storage commands go through the object handler, which can refuse them
+#[test]
+fn test_object_handler() {
+    let _lock = common::lock();
+    storage(512);
+    let mut master = Master::new();
+    master
+        .slave
+        .set_object_handler(unsafe { &mut *addr_of_mut!(INTERLOCK) });
+    write_parameters(&mut master);
+
+    *LOCKED.lock().unwrap() = true;
+    let response = master.coe(&sdo(0x23, 0x1010, 1, SAVE.to_le_bytes()));
+    assert_eq!(response, sdo_abort(0x1010, 1, SdoAbort::DataStoreLocal));
+    assert!(flash().iter().all(|byte| *byte == 0xFF));
+    let pre = format!("download_pre {:?}", SdoValue::U32(SAVE));
+    assert_eq!(COMMANDS.lock().unwrap()[..], [pre.as_str()]);
+
+    *LOCKED.lock().unwrap() = false;
+    COMMANDS.lock().unwrap().clear();
+    save(&mut master);
+    assert_eq!(
+        COMMANDS.lock().unwrap()[..],
+        [pre.as_str(), "download_post"]
+    );
+    Master::new();
+    assert_eq!(speed(), 555);
+}